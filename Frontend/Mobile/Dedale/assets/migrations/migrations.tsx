@@ -914,4 +914,99 @@ export const migrations: Migration[] = [
       console.log("✅ Migration v7 terminée - Schéma aligné avec Tauri");
     },
   },
+  {
+    version: 8,
+    name: "create_action_dependency_table",
+    up: (db: SQLiteDatabase) => {
+      console.log("🔄 Migration v8: Dépendances entre actions");
+
+      // Dépendances reçues avec le planning : action_id ne peut commencer qu'après depends_on_id
+      db.execSync(`
+        CREATE TABLE IF NOT EXISTS action_dependency (
+          action_id TEXT NOT NULL,
+          depends_on_id TEXT NOT NULL,
+          PRIMARY KEY (action_id, depends_on_id),
+          FOREIGN KEY (action_id) REFERENCES action (id) ON DELETE CASCADE
+        )
+      `);
+
+      // État bloqué calculé par le serveur à l'export, utilisé pour les prédécesseurs
+      // d'autres équipes (absents de la base locale)
+      try {
+        db.execSync("ALTER TABLE action ADD COLUMN blocked INTEGER DEFAULT 0");
+      } catch (e) {
+        console.log("Colonne blocked déjà présente");
+      }
+
+      console.log("✅ Migration v8 terminée");
+    },
+  },
+  {
+    version: 9,
+    name: "create_action_status_change_table",
+    up: (db: SQLiteDatabase) => {
+      console.log("🔄 Migration v9: Statuts des actions saisis sur le terrain");
+
+      // Statut courant de l'action (planned, en_route, in_progress, done, blocked, cancelled)
+      try {
+        db.execSync("ALTER TABLE action ADD COLUMN status TEXT");
+      } catch (e) {
+        console.log("Colonne status déjà présente");
+      }
+
+      // Historique des changements, envoyé au desktop lors de l'export (synced = 1 ensuite)
+      db.execSync(`
+        CREATE TABLE IF NOT EXISTS action_status_change (
+          id TEXT PRIMARY KEY NOT NULL,
+          action_id TEXT NOT NULL,
+          status TEXT NOT NULL,
+          note TEXT,
+          changed_at TEXT NOT NULL,
+          synced INTEGER DEFAULT 0,
+          FOREIGN KEY (action_id) REFERENCES action (id) ON DELETE CASCADE
+        )
+      `);
+
+      db.execSync(`
+        CREATE TABLE IF NOT EXISTS action_status_picture (
+          id TEXT PRIMARY KEY NOT NULL,
+          status_change_id TEXT NOT NULL,
+          image TEXT NOT NULL,
+          FOREIGN KEY (status_change_id) REFERENCES action_status_change (id) ON DELETE CASCADE
+        )
+      `);
+
+      db.execSync(
+        "CREATE INDEX IF NOT EXISTS idx_action_status_change_action_id ON action_status_change(action_id)"
+      );
+
+      console.log("✅ Migration v9 terminée");
+    },
+  },
+  {
+    version: 10,
+    name: "add_km_marker_columns_to_interest",
+    up: (db: SQLiteDatabase) => {
+      console.log("🔄 Migration v10: Bornes kilométriques");
+
+      // Bornes générées par le desktop et reçues avec le planning
+      for (const column of [
+        "parcours_id TEXT",
+        "marker_km REAL",
+        "bearing REAL",
+      ]) {
+        try {
+          db.execSync(`ALTER TABLE interest ADD COLUMN ${column}`);
+        } catch (e) {
+          console.log(`Colonne ${column} déjà présente`);
+        }
+      }
+
+      db.execSync(
+        "CREATE INDEX IF NOT EXISTS idx_interest_event_id ON interest(event_id)"
+      );
+
+      console.log("✅ Migration v10 terminée");
+    },
+  },
 ];
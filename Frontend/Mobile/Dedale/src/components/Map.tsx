@@ -9,12 +9,19 @@ import MapView, {
   MapPressEvent,
 } from "react-native-maps";
 import * as Location from "expo-location";
-import { InterestPointsType, GeometryType } from "../types/database";
+import {
+  InterestPointsType,
+  GeometryType,
+  KmMarkerType,
+} from "../types/database";
 import CustomButton from "./CustomButton";
 import { useEvent } from "../context/EventContext";
 import { usePoints } from "../context/PointsContext";
 import { useGeometries } from "../context/GeometriesContext";
 import { getShortAddressFromCoords } from "../services/Helper";
+import { getKmMarkers } from "../services/databaseAcces";
+import { getDatabase } from "../../assets/migrations";
+import { useFocusEffect } from "@react-navigation/native";
 
 // Fonction utilitaire exportée pour les tests
 export const parseWKT = (wkt: string) => {
@@ -102,6 +109,7 @@ export default function OfflineMap({
   const [listPoint, setListPoint] = React.useState<InterestPointsType[]>([]);
   const [listGeometry, setListGeometry] = React.useState<GeometryType[]>([]);
   const [pointAddresses, setPointAddresses] = React.useState<Record<string, string>>({});
+  const [kmMarkers, setKmMarkers] = React.useState<KmMarkerType[]>([]);
 
   const internalMapRef = React.useRef<MapView | null>(null);
   const mapRef = externalMapRef || internalMapRef;
@@ -139,6 +147,22 @@ export default function OfflineMap({
     }
   }, [selectedEventId, geometriesByEvent]);
 
+  // Bornes kilométriques reçues avec le planning (rechargées au retour sur la carte)
+  useFocusEffect(
+    React.useCallback(() => {
+      if (!selectedEventId) {
+        setKmMarkers([]);
+        return;
+      }
+      try {
+        setKmMarkers(getKmMarkers(selectedEventId, getDatabase()));
+      } catch (error) {
+        console.error("Erreur chargement des bornes kilométriques:", error);
+        setKmMarkers([]);
+      }
+    }, [selectedEventId])
+  );
+
   // Fetch addresses for all points
   React.useEffect(() => {
     const fetchAddresses = async () => {
@@ -317,6 +341,15 @@ export default function OfflineMap({
 
           return null;
         })}
+        {kmMarkers.map((marker) => (
+          <Marker
+            key={`km-${marker.id}`}
+            coordinate={{ longitude: marker.x, latitude: marker.y }}
+            title={`Km ${marker.marker_km}`}
+            description={marker.description || undefined}
+            pinColor="orange"
+          />
+        ))}
         {customMarker}
       </MapView>
       {(pointsLoading || geometriesLoading) && (
@@ -23,6 +23,11 @@ import { useWebSocket } from "../context/WebSocketContext";
 import { useEvent } from "../context/EventContext";
 import { usePoints } from "../context/PointsContext";
 import { useGeometries } from "../context/GeometriesContext";
+import {
+  getPendingActionStatuses,
+  markActionStatusesSynced,
+  replaceKmMarkers,
+} from "../services/databaseAcces";
 import {
   EventType,
   TransferEventType,
@@ -31,6 +36,7 @@ import {
   EquipementType,
   ParcoursType,
   ZoneType,
+  ActionStatusExport,
 } from "../types/database";
 
 // Types pour l'export
@@ -43,6 +49,7 @@ type EventExportData = {
   event: any;
   points: PointWithDetails[];
   equipements?: any[];
+  actionStatuses: ActionStatusExport[];
 };
 
 // Nouveau type pour les données reçues du desktop
@@ -576,6 +583,8 @@ const QRCodeScanner = ({
       let equipementsCount = 0;
       let coordsCount = 0;
       let skippedTeams = 0;
+      // Bornes kilométriques de l'événement (identiques pour chaque équipe)
+      let kmMarkers: any[] | null = null;
 
       const actionsArray = planningData.actions || [];
 
@@ -590,6 +599,10 @@ const QRCodeScanner = ({
           continue;
         }
 
+        if (Array.isArray(actionGroup.kmMarkers)) {
+          kmMarkers = actionGroup.kmMarkers;
+        }
+
         // Insérer/mettre à jour l'équipe
         const existingTeam = db.getFirstSync(
           "SELECT id FROM team WHERE id = ?",
@@ -719,9 +732,12 @@ const QRCodeScanner = ({
                 ? 1
                 : 0;
 
+          const isBlocked = action.blocked ? 1 : 0;
+          const status = action.status || (isDone ? "done" : "planned");
+
           if (!existingAction) {
             db.runSync(
-              "INSERT INTO action (id, team_id, equipement_id, type, scheduled_time, is_done) VALUES (?, ?, ?, ?, ?, ?)",
+              "INSERT INTO action (id, team_id, equipement_id, type, scheduled_time, is_done, blocked, status) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
               [
                 action.id,
                 action.team_id || action.teamId,
@@ -729,25 +745,56 @@ const QRCodeScanner = ({
                 action.type || action.actionType || null,
                 action.scheduled_time || action.scheduledTime || null,
                 isDone,
+                isBlocked,
+                status,
               ]
             );
             actionsCount++;
           } else {
             db.runSync(
-              "UPDATE action SET type = ?, scheduled_time = ?, is_done = ? WHERE id = ?",
+              "UPDATE action SET type = ?, scheduled_time = ?, blocked = ? WHERE id = ?",
               [
                 action.type || action.actionType || null,
                 action.scheduled_time || action.scheduledTime || null,
-                isDone,
+                isBlocked,
                 action.id,
               ]
             );
+
+            // Un statut saisi sur le terrain et pas encore remonté prime sur celui du desktop
+            const pendingChange = db.getFirstSync(
+              "SELECT id FROM action_status_change WHERE action_id = ? AND COALESCE(synced, 0) = 0",
+              [action.id]
+            );
+            if (!pendingChange) {
+              db.runSync(
+                "UPDATE action SET is_done = ?, status = ? WHERE id = ?",
+                [isDone, status, action.id]
+              );
+            }
+          }
+
+          // Dépendances : remplacées à chaque import
+          const dependsOn: string[] =
+            action.depends_on || action.dependsOn || [];
+          db.runSync("DELETE FROM action_dependency WHERE action_id = ?", [
+            action.id,
+          ]);
+          for (const dependsOnId of dependsOn) {
+            db.runSync(
+              "INSERT OR IGNORE INTO action_dependency (action_id, depends_on_id) VALUES (?, ?)",
+              [action.id, dependsOnId]
+            );
           }
         }
       }
 
+      if (kmMarkers) {
+        replaceKmMarkers(selectedEventId, kmMarkers, db);
+      }
+
       console.log(
-        `✅ Planning importé: ${teamsCount} équipes, ${actionsCount} actions, ${equipementsCount} équipements, ${coordsCount} coordonnées`
+        `✅ Planning importé: ${teamsCount} équipes, ${actionsCount} actions, ${equipementsCount} équipements, ${coordsCount} coordonnées, ${kmMarkers?.length ?? 0} bornes`
       );
 
       if (skippedTeams > 0) {
@@ -811,6 +858,8 @@ const QRCodeScanner = ({
         event: eventToSend || event,
         points: pointsWithDetails,
         equipements: equipementsWithCoords,
+        // Statuts, notes et photos des actions pas encore remontés au desktop
+        actionStatuses: getPendingActionStatuses(eventId, db),
       };
     } catch (error) {
       console.error("Erreur récupération données export:", error);
@@ -854,9 +903,13 @@ const QRCodeScanner = ({
           handleCloseConnection();
         });
 
+        // Changements de statut envoyés, marqués comme synchronisés une fois l'import confirmé
+        let sentStatusIds: string[] = [];
+
         client.setOnResponse((response: WebSocketResponse) => {
           console.log("📨 Réponse serveur:", response);
           if (response.code === 3) {
+            markActionStatusesSynced(sentStatusIds, db);
             setTransferStatus("✅ " + response.message);
             setTimeout(() => {
               handleCloseConnection();
@@ -883,6 +936,7 @@ const QRCodeScanner = ({
                 exportData.points.length,
                 "points"
               );
+              sentStatusIds = exportData.actionStatuses.map((s) => s.id);
               client.send(JSON.stringify(exportData));
               setTransferStatus(
                 `Envoi de ${exportData.points.length} point(s)...`
@@ -6,6 +6,9 @@ import {
   ScrollView,
   ActivityIndicator,
   Pressable,
+  Modal,
+  TextInput,
+  Alert,
 } from "react-native";
 import { useNavigation, useFocusEffect } from "@react-navigation/native";
 import { NativeStackNavigationProp } from "@react-navigation/native-stack";
@@ -20,6 +23,19 @@ import { useEvent } from "../context/EventContext";
 import { RootStackParamList } from "../types/navigation";
 import QRCodeScanner from "../components/QrCodeScanner";
 import Colors from "../constants/colors";
+import { ActionStatus } from "../types/database";
+import { recordActionStatus } from "../services/databaseAcces";
+import { imageToBase64, pickImage } from "../services/ImageHelper";
+
+// Statuts proposés sur le terrain, dans l'ordre d'avancement
+const STATUS_OPTIONS: { value: ActionStatus; label: string }[] = [
+  { value: "planned", label: "En attente" },
+  { value: "en_route", label: "En route" },
+  { value: "in_progress", label: "En cours" },
+  { value: "done", label: "Terminée" },
+  { value: "blocked", label: "Bloquée" },
+  { value: "cancelled", label: "Annulée" },
+];
 
 interface Action {
   id: string;
@@ -28,7 +44,10 @@ interface Action {
   type: string | null;
   scheduled_time: string | null;
   is_done: number;
+  status?: ActionStatus | null;
   equipement_name?: string;
+  // Actions préalables pas encore terminées
+  pending_dependencies?: number;
 }
 
 interface Team {
@@ -49,6 +68,10 @@ export default function PlanningScreen() {
   const [actions, setActions] = useState<Action[]>([]);
   const [loading, setLoading] = useState(true);
   const [scanQR, setScanQR] = useState(false);
+  const [statusAction, setStatusAction] = useState<Action | null>(null);
+  const [newStatus, setNewStatus] = useState<ActionStatus>("planned");
+  const [statusNote, setStatusNote] = useState("");
+  const [statusPhotos, setStatusPhotos] = useState<string[]>([]);
 
   const loadData = (database: SQLiteDatabase) => {
     try {
@@ -80,7 +103,14 @@ export default function PlanningScreen() {
 
         if (teamResult) {
           const actionsResult = database.getAllSync<Action>(
-            `SELECT a.*, t.name as equipement_name 
+            `SELECT a.*, t.name as equipement_name,
+               (SELECT COUNT(*) FROM action_dependency d
+                LEFT JOIN action p ON p.id = d.depends_on_id
+                WHERE d.action_id = a.id
+                  AND CASE
+                    WHEN p.id IS NULL THEN COALESCE(a.blocked, 0) = 1
+                    ELSE COALESCE(p.is_done, 0) = 0
+                  END) as pending_dependencies
              FROM action a 
              LEFT JOIN equipement e ON a.equipement_id = e.id
              LEFT JOIN type t ON e.type_id = t.id
@@ -157,6 +187,61 @@ export default function PlanningScreen() {
     }
   };
 
+  const getStatus = (action: Action): ActionStatus =>
+    action.status || (action.is_done ? "done" : "planned");
+
+  const getStatusLabel = (status: ActionStatus) =>
+    STATUS_OPTIONS.find((option) => option.value === status)?.label ||
+    "En attente";
+
+  // Une action reste bloquée tant qu'une action préalable n'est pas terminée
+  // (pour une action d'une autre équipe, l'état reçu avec le planning fait foi)
+  const isBlocked = (action: Action) =>
+    !action.is_done &&
+    ((action.pending_dependencies ?? 0) > 0 || getStatus(action) === "blocked");
+
+  const isPending = (action: Action) =>
+    !action.is_done && getStatus(action) !== "cancelled";
+
+  const openStatusModal = (action: Action) => {
+    setStatusAction(action);
+    setNewStatus(getStatus(action));
+    setStatusNote("");
+    setStatusPhotos([]);
+  };
+
+  const handleAddStatusPhoto = async () => {
+    try {
+      const uri = await pickImage();
+      if (uri) {
+        const base64 = await imageToBase64(uri);
+        setStatusPhotos((photos) => [...photos, base64]);
+      }
+    } catch (error) {
+      console.error("Erreur:", error);
+      Alert.alert("Erreur", "Impossible d'ajouter la photo");
+    }
+  };
+
+  // Le changement est historisé localement et remonté au desktop lors du prochain export
+  const handleSaveStatus = () => {
+    if (!db || !statusAction) return;
+    try {
+      recordActionStatus(
+        statusAction.id,
+        newStatus,
+        db,
+        statusNote.trim() || null,
+        statusPhotos
+      );
+      setStatusAction(null);
+      loadData(db);
+    } catch (error) {
+      console.error("Erreur enregistrement statut:", error);
+      Alert.alert("Erreur", "Impossible d'enregistrer le statut");
+    }
+  };
+
   if (scanQR) {
     return (
       <SafeAreaView style={styles.container} edges={["top"]}>
@@ -265,7 +350,7 @@ export default function PlanningScreen() {
           <View style={styles.summaryDivider} />
           <View style={styles.summaryItem}>
             <Text style={styles.summaryNumber}>
-              {actions.filter((a) => !a.is_done).length}
+              {actions.filter(isPending).length}
             </Text>
             <Text style={styles.summaryLabel}>En attente</Text>
           </View>
@@ -275,11 +360,13 @@ export default function PlanningScreen() {
 
         {actions.length > 0 ? (
           actions.map((action) => (
-            <View
+            <Pressable
               key={action.id}
+              onPress={() => openStatusModal(action)}
               style={[
                 styles.actionCard,
                 action.is_done ? styles.actionCardDone : undefined,
+                isBlocked(action) ? styles.actionCardBlocked : undefined,
               ]}
             >
               <View style={styles.actionHeader}>
@@ -289,11 +376,17 @@ export default function PlanningScreen() {
                 <View
                   style={[
                     styles.statusBadge,
-                    action.is_done ? styles.statusDone : styles.statusPending,
+                    action.is_done
+                      ? styles.statusDone
+                      : isBlocked(action)
+                        ? styles.statusBlocked
+                        : styles.statusPending,
                   ]}
                 >
                   <Text style={styles.statusText}>
-                    {action.is_done ? "Terminée" : "En attente"}
+                    {isBlocked(action)
+                      ? "Bloquée"
+                      : getStatusLabel(getStatus(action))}
                   </Text>
                 </View>
               </View>
@@ -314,8 +407,17 @@ export default function PlanningScreen() {
                     </Text>
                   </View>
                 )}
+
+                {(action.pending_dependencies ?? 0) > 0 && !action.is_done && (
+                  <View style={styles.detailRow}>
+                    <Feather name="lock" size={14} color="#C62828" />
+                    <Text style={styles.blockedText}>
+                      {`En attente de ${action.pending_dependencies} action(s) préalable(s)`}
+                    </Text>
+                  </View>
+                )}
               </View>
-            </View>
+            </Pressable>
           ))
         ) : (
           <View style={styles.emptyState}>
@@ -328,7 +430,7 @@ export default function PlanningScreen() {
         <View style={{ height: 100 }} />
       </ScrollView>
 
-      {actions.filter((a) => !a.is_done).length > 0 && (
+      {actions.filter(isPending).length > 0 && (
         <View
           style={[
             styles.bottomButtonContainer,
@@ -356,6 +458,80 @@ export default function PlanningScreen() {
           </Pressable>
         </View>
       )}
+
+      {/* Modal de changement de statut */}
+      <Modal
+        visible={statusAction !== null}
+        transparent={true}
+        animationType="slide"
+        onRequestClose={() => setStatusAction(null)}
+      >
+        <View style={styles.modalOverlay}>
+          <View style={styles.modalContent}>
+            <Text style={styles.modalTitle}>Statut de l'action</Text>
+            {statusAction?.equipement_name && (
+              <Text style={styles.detailText}>
+                {statusAction.equipement_name}
+              </Text>
+            )}
+
+            <View style={styles.statusOptions}>
+              {STATUS_OPTIONS.map((option) => (
+                <Pressable
+                  key={option.value}
+                  onPress={() => setNewStatus(option.value)}
+                  style={[
+                    styles.statusOption,
+                    newStatus === option.value && styles.statusOptionSelected,
+                  ]}
+                >
+                  <Text
+                    style={[
+                      styles.statusOptionText,
+                      newStatus === option.value &&
+                        styles.statusOptionTextSelected,
+                    ]}
+                  >
+                    {option.label}
+                  </Text>
+                </Pressable>
+              ))}
+            </View>
+
+            <TextInput
+              style={styles.noteInput}
+              placeholder="Note (optionnelle)"
+              value={statusNote}
+              onChangeText={setStatusNote}
+              multiline
+            />
+
+            <Pressable style={styles.photoButton} onPress={handleAddStatusPhoto}>
+              <Feather name="camera" size={18} color={Colors.secondary} />
+              <Text style={styles.photoButtonText}>
+                {statusPhotos.length > 0
+                  ? `${statusPhotos.length} photo(s) ajoutée(s)`
+                  : "Ajouter une photo"}
+              </Text>
+            </Pressable>
+
+            <View style={styles.modalButtons}>
+              <Pressable
+                style={[styles.modalButton, styles.modalButtonCancel]}
+                onPress={() => setStatusAction(null)}
+              >
+                <Text style={styles.modalButtonCancelText}>Annuler</Text>
+              </Pressable>
+              <Pressable
+                style={[styles.modalButton, styles.modalButtonSave]}
+                onPress={handleSaveStatus}
+              >
+                <Text style={styles.modalButtonSaveText}>Enregistrer</Text>
+              </Pressable>
+            </View>
+          </View>
+        </View>
+      </Modal>
     </View>
   );
 }
@@ -463,6 +639,10 @@ const styles = StyleSheet.create({
     borderLeftColor: Colors.accent,
     opacity: 0.8,
   },
+  actionCardBlocked: {
+    borderLeftColor: "#9E9E9E",
+    backgroundColor: "#FAFAFA",
+  },
   actionHeader: {
     flexDirection: "row",
     justifyContent: "space-between",
@@ -485,6 +665,9 @@ const styles = StyleSheet.create({
   statusDone: {
     backgroundColor: "#E8F5E9",
   },
+  statusBlocked: {
+    backgroundColor: "#FFEBEE",
+  },
   statusText: {
     fontSize: 12,
     fontWeight: "500",
@@ -502,6 +685,10 @@ const styles = StyleSheet.create({
     fontSize: 14,
     color: "#666",
   },
+  blockedText: {
+    fontSize: 14,
+    color: "#C62828",
+  },
   emptyState: {
     alignItems: "center",
     paddingVertical: 48,
@@ -567,4 +754,88 @@ const styles = StyleSheet.create({
     fontSize: 16,
     fontWeight: "600",
   },
+  modalOverlay: {
+    flex: 1,
+    justifyContent: "flex-end",
+    backgroundColor: "rgba(0, 0, 0, 0.4)",
+  },
+  modalContent: {
+    backgroundColor: "#fff",
+    borderTopStartRadius: 24,
+    borderTopEndRadius: 24,
+    padding: 20,
+    gap: 12,
+  },
+  modalTitle: {
+    fontSize: 20,
+    fontWeight: "bold",
+    color: "#333",
+  },
+  statusOptions: {
+    flexDirection: "row",
+    flexWrap: "wrap",
+    gap: 8,
+  },
+  statusOption: {
+    paddingHorizontal: 12,
+    paddingVertical: 8,
+    borderRadius: 16,
+    borderWidth: 1,
+    borderColor: "#e0e0e0",
+  },
+  statusOptionSelected: {
+    backgroundColor: Colors.secondary,
+    borderColor: Colors.secondary,
+  },
+  statusOptionText: {
+    fontSize: 14,
+    color: "#333",
+  },
+  statusOptionTextSelected: {
+    color: "#fff",
+    fontWeight: "600",
+  },
+  noteInput: {
+    borderWidth: 1,
+    borderColor: "#e0e0e0",
+    borderRadius: 12,
+    padding: 12,
+    minHeight: 80,
+    textAlignVertical: "top",
+  },
+  photoButton: {
+    flexDirection: "row",
+    alignItems: "center",
+    gap: 8,
+    paddingVertical: 8,
+  },
+  photoButtonText: {
+    fontSize: 14,
+    color: Colors.secondary,
+    fontWeight: "500",
+  },
+  modalButtons: {
+    flexDirection: "row",
+    gap: 12,
+  },
+  modalButton: {
+    flex: 1,
+    paddingVertical: 14,
+    borderRadius: 12,
+    alignItems: "center",
+  },
+  modalButtonCancel: {
+    backgroundColor: "#e0e0e0",
+  },
+  modalButtonSave: {
+    backgroundColor: Colors.secondary,
+  },
+  modalButtonCancelText: {
+    color: "#333",
+    fontWeight: "600",
+  },
+  modalButtonSaveText: {
+    color: "#fff",
+    fontWeight: "600",
+  },
 });
\ No newline at end of file
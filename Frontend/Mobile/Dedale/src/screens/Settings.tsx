@@ -20,9 +20,14 @@ import {
   InterestPointsType,
   PictureType,
   EquipementType,
+  ActionStatusExport,
 } from "../types/database";
 import Colors from "../constants/colors";
 import { WebSocketResponse } from "../components/WebSocketClient";
+import {
+  getPendingActionStatuses,
+  markActionStatusesSynced,
+} from "../services/databaseAcces";
 
 type PointWithDetails = InterestPointsType & {
   pictures: PictureType[];
@@ -33,6 +38,7 @@ type EventExportData = {
   event: any;
   points: PointWithDetails[];
   equipements: EquipementType[];
+  actionStatuses: ActionStatusExport[];
 };
 
 export default function SettingsScreen() {
@@ -125,6 +131,8 @@ export default function SettingsScreen() {
         event,
         points: pointsWithDetails,
         equipements: equipements || [],
+        // Statuts, notes et photos des actions pas encore remontés au desktop
+        actionStatuses: getPendingActionStatuses(eventId, db),
       };
     } catch (error) {
       console.error(
@@ -171,6 +179,7 @@ export default function SettingsScreen() {
     console.log("📤 Export événement avec données complètes:", {
       event: exportData.event.name,
       pointsCount: exportData.points.length,
+      actionStatusesCount: exportData.actionStatuses.length,
     });
 
     // Envoyer avec gestion d'erreur
@@ -200,6 +209,10 @@ export default function SettingsScreen() {
 
           case 3:
             // Desktop accepts and successfully imports
+            markActionStatusesSynced(
+              exportData.actionStatuses.map((s) => s.id),
+              db
+            );
             Alert.alert(
               "Export réussi",
               response.message ||
@@ -18,6 +18,7 @@ import MapView, { Marker, Polyline, Region } from "react-native-maps";
 import * as Location from "expo-location";
 import { getDatabase } from "../../assets/migrations";
 import Colors from "../constants/colors";
+import { recordActionStatus } from "../services/databaseAcces";
 
 interface ActionWithPoint {
   id: string;
@@ -350,7 +351,8 @@ export default function TeamGuidanceScreen() {
 
   const markActionAsDone = (action: ActionWithPoint) => {
     const db = getDatabase();
-    db.runSync("UPDATE action SET is_done = 1 WHERE id = ?", [action.id]);
+    // Historisé pour être remonté au desktop lors du prochain export
+    recordActionStatus(action.id, "done", db);
 
     const updatedActions = [...actions];
     const actionIndex = updatedActions.findIndex((a) => a.id === action.id);
@@ -1,4 +1,9 @@
 import { generateUUID } from "./Helper";
+import {
+  ActionStatus,
+  ActionStatusExport,
+  KmMarkerType,
+} from "../types/database";
 
 // Comment functions - Now integrated into point table
 export const updateComment = (pointId: string, newValue: string, db: any) => {
@@ -113,3 +118,109 @@ export const updateTimeStamp = (pointId: string, db: any) => {
     console.error("Failed to update point's modified_at timestamp:", error);
   }
 };
+
+// Action status functions - Changements saisis sur le terrain, remontés au desktop
+export const recordActionStatus = (
+  actionId: string,
+  status: ActionStatus,
+  db: any,
+  note?: string | null,
+  photos: string[] = []
+) => {
+  const changeId = generateUUID();
+  const changedAt = new Date().toISOString();
+
+  db.runSync(
+    "INSERT INTO action_status_change (id, action_id, status, note, changed_at, synced) VALUES (?, ?, ?, ?, ?, 0)",
+    [changeId, actionId, status, note || null, changedAt]
+  );
+  photos.forEach((image) => {
+    db.runSync(
+      "INSERT INTO action_status_picture (id, status_change_id, image) VALUES (?, ?, ?)",
+      [generateUUID(), changeId, image]
+    );
+  });
+  db.runSync("UPDATE action SET status = ?, is_done = ? WHERE id = ?", [
+    status,
+    status === "done" ? 1 : 0,
+    actionId,
+  ]);
+
+  return changeId;
+};
+
+// Changements pas encore envoyés au desktop pour les actions d'un événement
+export const getPendingActionStatuses = (
+  eventId: string | number,
+  db: any
+): ActionStatusExport[] => {
+  const changes = db.getAllSync(
+    `SELECT c.id, c.action_id, c.status, c.note, c.changed_at
+     FROM action_status_change c
+     INNER JOIN action a ON a.id = c.action_id
+     INNER JOIN team t ON t.id = a.team_id
+     WHERE t.event_id = ? AND COALESCE(c.synced, 0) = 0
+     ORDER BY c.changed_at ASC`,
+    [eventId]
+  );
+
+  return (changes || []).map((change: any) => {
+    const pictures = db.getAllSync(
+      "SELECT image FROM action_status_picture WHERE status_change_id = ?",
+      [change.id]
+    );
+    return {
+      id: change.id,
+      actionId: change.action_id,
+      status: change.status,
+      note: change.note,
+      changedAt: change.changed_at,
+      photos: (pictures || []).map((p: any) => p.image),
+    };
+  });
+};
+
+// Marque les changements comme reçus par le desktop (ils ne seront plus renvoyés)
+export const markActionStatusesSynced = (changeIds: string[], db: any) => {
+  changeIds.forEach((changeId) => {
+    db.runSync("UPDATE action_status_change SET synced = 1 WHERE id = ?", [
+      changeId,
+    ]);
+  });
+};
+
+// Km marker functions - Bornes remplacées à chaque import du planning
+export const replaceKmMarkers = (
+  eventId: string,
+  markers: KmMarkerType[],
+  db: any
+) => {
+  db.runSync(
+    "DELETE FROM interest WHERE event_id = ? AND marker_km IS NOT NULL",
+    [eventId]
+  );
+  markers.forEach((marker) => {
+    db.runSync(
+      "INSERT OR REPLACE INTO interest (id, event_id, x, y, description, parcours_id, marker_km, bearing) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+      [
+        marker.id,
+        eventId,
+        marker.x,
+        marker.y,
+        marker.description || null,
+        marker.parcours_id || null,
+        marker.marker_km,
+        marker.bearing ?? null,
+      ]
+    );
+  });
+};
+
+export const getKmMarkers = (eventId: string, db: any): KmMarkerType[] => {
+  return (
+    db.getAllSync(
+      "SELECT * FROM interest WHERE event_id = ? AND marker_km IS NOT NULL ORDER BY parcours_id, marker_km",
+      [eventId]
+    ) || []
+  );
+};
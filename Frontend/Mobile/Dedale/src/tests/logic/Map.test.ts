@@ -22,6 +22,14 @@ jest.mock('react-native-maps', () => {
   };
 });
 
+jest.mock('../../../assets/migrations', () => ({
+  getDatabase: jest.fn(),
+}));
+
+jest.mock('@react-navigation/native', () => ({
+  useFocusEffect: jest.fn(),
+}));
+
 jest.mock('expo-location', () => ({
   requestForegroundPermissionsAsync: jest.fn(),
   getCurrentPositionAsync: jest.fn(),
@@ -32,18 +32,30 @@ jest.mock('../../components/QrCodeScanner', () => {
   return () => <View testID="mock-qr-scanner"><Text>Scanner View</Text></View>;
 });
 
+jest.mock('../../services/ImageHelper', () => ({
+  pickImage: jest.fn(),
+  imageToBase64: jest.fn(),
+}));
+
+jest.mock('../../services/Helper', () => ({
+  generateUUID: () => 'uuid-status-1',
+}));
+
 const mockGetFirstSync = jest.fn();
 const mockGetAllSync = jest.fn();
+const mockRunSync = jest.fn();
 
 jest.mock('../../../assets/migrations', () => ({
   getDatabase: () => ({
     getFirstSync: mockGetFirstSync,
     getAllSync: mockGetAllSync,
+    runSync: mockRunSync,
   }),
   __esModule: true,
   default: () => ({
     getFirstSync: mockGetFirstSync,
     getAllSync: mockGetAllSync,
+    runSync: mockRunSync,
   }),
 }));
 
@@ -132,6 +144,77 @@ describe('PlanningScreen - Screens', () => {
     expect(queryByTestId('mock-qr-scanner')).toBeNull();
   });
 
+  test('devrait afficher une action bloquée par une action préalable', async () => {
+    // Arrange
+    mockGetAllSync.mockReturnValue([
+      {
+        id: '1',
+        type: 'retrait',
+        scheduled_time: '2025-01-01T18:00:00',
+        is_done: 0,
+        equipement_name: 'Scène',
+        pending_dependencies: 0
+      },
+      {
+        id: '2',
+        type: 'retrait',
+        scheduled_time: '2025-01-01T19:00:00',
+        is_done: 0,
+        equipement_name: 'Barrière',
+        pending_dependencies: 1
+      }
+    ]);
+
+    // Act
+    const { getByText, getAllByText } = render(<PlanningScreen />);
+
+    // Assert
+    expect(getByText('Bloquée')).toBeTruthy();
+    expect(getAllByText('En attente')).toHaveLength(2);
+    expect(getByText('En attente de 1 action(s) préalable(s)')).toBeTruthy();
+    expect(getByText('Icon-lock')).toBeTruthy();
+  });
+
+  test('devrait enregistrer un statut avec une note depuis le planning', async () => {
+    // Act
+    const { getByText, getByPlaceholderText } = render(<PlanningScreen />);
+
+    fireEvent.press(getByText('Podium'));
+    fireEvent.press(getByText('En cours'));
+    fireEvent.changeText(getByPlaceholderText('Note (optionnelle)'), 'Accès livraison fermé');
+    fireEvent.press(getByText('Enregistrer'));
+
+    // Assert
+    expect(mockRunSync).toHaveBeenCalledWith(
+      'INSERT INTO action_status_change (id, action_id, status, note, changed_at, synced) VALUES (?, ?, ?, ?, ?, 0)',
+      ['uuid-status-1', '2', 'in_progress', 'Accès livraison fermé', expect.any(String)]
+    );
+    expect(mockRunSync).toHaveBeenCalledWith(
+      'UPDATE action SET status = ?, is_done = ? WHERE id = ?',
+      ['in_progress', 0, '2']
+    );
+  });
+
+  test('devrait afficher le statut terrain d\'une action', async () => {
+    // Arrange
+    mockGetAllSync.mockReturnValue([
+      {
+        id: '1',
+        type: 'pose',
+        scheduled_time: '2025-01-01T10:00:00',
+        is_done: 0,
+        status: 'en_route',
+        equipement_name: 'Barrière'
+      }
+    ]);
+
+    // Act
+    const { getByText } = render(<PlanningScreen />);
+
+    // Assert
+    expect(getByText('En route')).toBeTruthy();
+  });
+
   test('devrait gérer l\'état vide (aucune équipe trouvée)', async () => {
     // Arrange
     mockGetFirstSync.mockReturnValue(null);
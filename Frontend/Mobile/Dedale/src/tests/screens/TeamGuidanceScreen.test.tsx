@@ -208,8 +208,12 @@ describe('Screen: TeamGuidance', () => {
 
     // Assert
     expect(mockRunSync).toHaveBeenCalledWith(
-      "UPDATE action SET is_done = 1 WHERE id = ?",
-      ['action-1']
+      "INSERT INTO action_status_change (id, action_id, status, note, changed_at, synced) VALUES (?, ?, ?, ?, ?, 0)",
+      [expect.any(String), 'action-1', 'done', null, expect.any(String)]
+    );
+    expect(mockRunSync).toHaveBeenCalledWith(
+      "UPDATE action SET status = ?, is_done = ? WHERE id = ?",
+      ['done', 1, 'action-1']
     );
   });
 });
\ No newline at end of file
@@ -13,6 +13,7 @@ describe('Service: databaseAcces', () => {
     // Arrange
     mockDb = {
       runSync: jest.fn(),
+      getAllSync: jest.fn(),
     };
     jest.clearAllMocks();
   });
@@ -247,4 +248,137 @@ describe('Service: databaseAcces', () => {
       consoleSpy.mockRestore();
     });
   });
-});
\ No newline at end of file
+
+  describe('Action Status Functions', () => {
+    test('recordActionStatus should store the change, its photos and update the action', () => {
+      // Arrange
+      (generateUUID as jest.Mock)
+        .mockReturnValueOnce('uuid-change-1')
+        .mockReturnValueOnce('uuid-photo-1');
+
+      // Act
+      const changeId = databaseAccess.recordActionStatus(
+        'action-1',
+        'blocked',
+        mockDb,
+        'Rue fermée',
+        ['base64photo']
+      );
+
+      // Assert
+      expect(changeId).toBe('uuid-change-1');
+      expect(mockDb.runSync).toHaveBeenCalledWith(
+        'INSERT INTO action_status_change (id, action_id, status, note, changed_at, synced) VALUES (?, ?, ?, ?, ?, 0)',
+        ['uuid-change-1', 'action-1', 'blocked', 'Rue fermée', expect.any(String)]
+      );
+      expect(mockDb.runSync).toHaveBeenCalledWith(
+        'INSERT INTO action_status_picture (id, status_change_id, image) VALUES (?, ?, ?)',
+        ['uuid-photo-1', 'uuid-change-1', 'base64photo']
+      );
+      expect(mockDb.runSync).toHaveBeenCalledWith(
+        'UPDATE action SET status = ?, is_done = ? WHERE id = ?',
+        ['blocked', 0, 'action-1']
+      );
+    });
+
+    test('recordActionStatus should mark done actions as done', () => {
+      // Act
+      databaseAccess.recordActionStatus('action-1', 'done', mockDb);
+
+      // Assert
+      expect(mockDb.runSync).toHaveBeenCalledWith(
+        'UPDATE action SET status = ?, is_done = ? WHERE id = ?',
+        ['done', 1, 'action-1']
+      );
+    });
+
+    test('getPendingActionStatuses should return changes in the desktop format', () => {
+      // Arrange
+      mockDb.getAllSync
+        .mockReturnValueOnce([
+          {
+            id: 'change-1',
+            action_id: 'action-1',
+            status: 'in_progress',
+            note: 'Début de pose',
+            changed_at: '2025-06-01T08:05:00.000Z',
+          },
+        ])
+        .mockReturnValueOnce([{ image: 'photo-a' }, { image: 'photo-b' }]);
+
+      // Act
+      const statuses = databaseAccess.getPendingActionStatuses('evt-1', mockDb);
+
+      // Assert
+      expect(mockDb.getAllSync.mock.calls[0][1]).toEqual(['evt-1']);
+      expect(statuses).toEqual([
+        {
+          id: 'change-1',
+          actionId: 'action-1',
+          status: 'in_progress',
+          note: 'Début de pose',
+          changedAt: '2025-06-01T08:05:00.000Z',
+          photos: ['photo-a', 'photo-b'],
+        },
+      ]);
+    });
+
+    test('markActionStatusesSynced should flag every sent change', () => {
+      // Act
+      databaseAccess.markActionStatusesSynced(['change-1', 'change-2'], mockDb);
+
+      // Assert
+      expect(mockDb.runSync).toHaveBeenCalledTimes(2);
+      expect(mockDb.runSync).toHaveBeenCalledWith(
+        'UPDATE action_status_change SET synced = 1 WHERE id = ?',
+        ['change-2']
+      );
+    });
+  });
+
+  describe('Km Marker Functions', () => {
+    test('replaceKmMarkers should replace the event markers', () => {
+      // Arrange
+      const marker = {
+        id: 'km-1',
+        event_id: 'evt-1',
+        x: 7.75,
+        y: 48.58,
+        description: 'Km 1 - 10 km',
+        parcours_id: 'parcours-1',
+        marker_km: 1,
+        bearing: 90,
+      };
+
+      // Act
+      databaseAccess.replaceKmMarkers('evt-1', [marker], mockDb);
+
+      // Assert
+      expect(mockDb.runSync).toHaveBeenNthCalledWith(
+        1,
+        'DELETE FROM interest WHERE event_id = ? AND marker_km IS NOT NULL',
+        ['evt-1']
+      );
+      expect(mockDb.runSync).toHaveBeenNthCalledWith(
+        2,
+        'INSERT OR REPLACE INTO interest (id, event_id, x, y, description, parcours_id, marker_km, bearing) VALUES (?, ?, ?, ?, ?, ?, ?, ?)',
+        ['km-1', 'evt-1', 7.75, 48.58, 'Km 1 - 10 km', 'parcours-1', 1, 90]
+      );
+    });
+
+    test('getKmMarkers should only read markers of the event', () => {
+      // Arrange
+      mockDb.getAllSync.mockReturnValue(undefined);
+
+      // Act
+      const markers = databaseAccess.getKmMarkers('evt-1', mockDb);
+
+      // Assert
+      expect(mockDb.getAllSync).toHaveBeenCalledWith(
+        'SELECT * FROM interest WHERE event_id = ? AND marker_km IS NOT NULL ORDER BY parcours_id, marker_km',
+        ['evt-1']
+      );
+      expect(markers).toEqual([]);
+    });
+  });
+});
@@ -129,6 +129,38 @@ export type ActionType = {
   type?: string | null;
   scheduled_time?: string | null;
   is_done?: number; // SQLite BOOLEAN as INTEGER 0/1
+  status?: ActionStatus | null;
+};
+
+// Borne kilométrique d'un parcours (table interest), reçue avec le planning
+export type KmMarkerType = {
+  id: string; // UUID
+  event_id: string; // UUID reference
+  x: number; // longitude
+  y: number; // latitude
+  description?: string | null;
+  parcours_id?: string | null;
+  marker_km: number;
+  bearing?: number | null; // cap du tracé en degrés
+};
+
+// Statuts terrain, identiques à ActionStatus côté desktop
+export type ActionStatus =
+  | "planned"
+  | "en_route"
+  | "in_progress"
+  | "done"
+  | "blocked"
+  | "cancelled";
+
+// Changement de statut au format attendu par le desktop (MobileActionStatus)
+export type ActionStatusExport = {
+  id: string; // UUID du changement local
+  actionId: string;
+  status: ActionStatus;
+  note?: string | null;
+  changedAt: string; // ISO 8601
+  photos: string[]; // base64
 };
 
 export type PointDetailType = {
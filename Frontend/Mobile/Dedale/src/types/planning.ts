@@ -22,6 +22,8 @@ export type PlanningAction = {
   scheduled_time: string | null;
   is_done: boolean | null;
   equipement_name?: string;
+  depends_on?: string[];
+  blocked?: boolean;
 };
 
 export type RouteGuide = {
@@ -0,0 +1,239 @@
+// Calendrier de fermeture des rues (arrêté de circulation, information de la police)
+// Le tracé de chaque parcours est échantillonné et rapproché du réseau de rues OSM
+// (table `streets` de la base d'adresses) ; chaque portion de rue empruntée est fermée
+// avant l'arrivée du premier coureur et rouverte après le passage du dernier.
+
+use crate::db;
+use crate::geocoding::{load_streets_in_bbox, Street};
+use crate::race::{
+    cumulative_lengths, interpolate_at_chainage, load_race_parcours, locate_on_line, RaceParcours,
+};
+use crate::utils::format_datetime;
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tauri::AppHandle;
+
+/// Fermeture par défaut avant l'arrivée du premier coureur (minutes)
+pub const DEFAULT_CLOSE_BEFORE_MINUTES: i64 = 30;
+
+/// Réouverture par défaut après le passage du dernier coureur (minutes)
+pub const DEFAULT_REOPEN_AFTER_MINUTES: i64 = 15;
+
+/// Pas d'échantillonnage du tracé (m)
+const SAMPLE_STEP_METERS: f64 = 10.0;
+
+/// Distance maximale (m) entre le tracé et l'axe d'une rue pour la considérer empruntée
+const STREET_TOLERANCE_METERS: f64 = 15.0;
+
+/// Portion minimale (m) : en dessous, il s'agit d'une rue simplement traversée
+const MIN_STREET_RUN_METERS: f64 = 30.0;
+
+/// Marge (degrés, ~55 m) pour le préfiltrage des rues sur leur emprise
+const BBOX_MARGIN_DEGREES: f64 = 0.0005;
+
+/// Portion continue d'une rue empruntée par le parcours
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreetRun {
+    pub street: String,
+    pub highway: Option<String>,
+    pub from_m: f64,
+    pub to_m: f64,
+}
+
+/// Fermeture d'une portion de rue
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StreetClosure {
+    pub parcours_id: String,
+    pub parcours_name: Option<String>,
+    pub street: String,
+    pub highway: Option<String>,
+    /// Début et fin de la portion, mesurés depuis le départ (m)
+    pub from_m: f64,
+    pub to_m: f64,
+    pub closes_at: String,
+    pub first_passage: String,
+    pub last_passage: String,
+    pub reopens_at: String,
+}
+
+// ==================== Fonctions helper publiques et testables ====================
+
+/// Points du tracé tous les `step_m` mètres (distance depuis le départ, position)
+pub fn sample_line(line: &[(f64, f64)], step_m: f64) -> Vec<(f64, (f64, f64))> {
+    let lengths = cumulative_lengths(line);
+    let Some(&total) = lengths.last() else {
+        return vec![];
+    };
+    if line.len() < 2 || step_m <= 0.0 {
+        return line.first().map(|p| vec![(0.0, *p)]).unwrap_or_default();
+    }
+
+    let mut samples = Vec::new();
+    let mut chainage = 0.0;
+    loop {
+        if let Some((_, point)) = interpolate_at_chainage(line, &lengths, chainage) {
+            samples.push((chainage, point));
+        }
+
+        if chainage >= total {
+            break;
+        }
+        chainage = (chainage + step_m).min(total);
+    }
+    samples
+}
+
+fn near_bbox(line: &[(f64, f64)], point: (f64, f64), margin: f64) -> bool {
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (f64::MAX, f64::MAX, f64::MIN, f64::MIN);
+    for (x, y) in line {
+        min_x = min_x.min(*x);
+        min_y = min_y.min(*y);
+        max_x = max_x.max(*x);
+        max_y = max_y.max(*y);
+    }
+    point.0 >= min_x - margin
+        && point.0 <= max_x + margin
+        && point.1 >= min_y - margin
+        && point.1 <= max_y + margin
+}
+
+/// Portions de rues empruntées par un tracé, dans l'ordre du parcours.
+/// Chaque point échantillonné est rattaché à la rue la plus proche (si elle est assez proche) ;
+/// les portions trop courtes (intersections, rues traversées) sont écartées et les portions
+/// d'une même rue séparées par une intersection sont fusionnées.
+pub fn street_runs(line: &[(f64, f64)], streets: &[Street]) -> Vec<StreetRun> {
+    let mut runs: Vec<StreetRun> = Vec::new();
+
+    for (chainage, point) in sample_line(line, SAMPLE_STEP_METERS) {
+        let nearest = streets
+            .iter()
+            .filter(|s| near_bbox(&s.line, point, BBOX_MARGIN_DEGREES))
+            .filter_map(|s| Some((s, locate_on_line(&s.line, point)?.offset_m)))
+            .filter(|(_, offset)| *offset <= STREET_TOLERANCE_METERS)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        let Some((street, _)) = nearest else {
+            continue;
+        };
+
+        match runs.last_mut() {
+            Some(run)
+                if run.street == street.name && chainage - run.to_m <= 3.0 * SAMPLE_STEP_METERS =>
+            {
+                run.to_m = chainage;
+            }
+            _ => runs.push(StreetRun {
+                street: street.name.clone(),
+                highway: street.highway.clone(),
+                from_m: chainage,
+                to_m: chainage,
+            }),
+        }
+    }
+
+    runs.retain(|r| r.to_m - r.from_m >= MIN_STREET_RUN_METERS);
+
+    let mut merged: Vec<StreetRun> = Vec::new();
+    for run in runs {
+        match merged.last_mut() {
+            Some(last)
+                if last.street == run.street
+                    && run.from_m - last.to_m <= 2.0 * MIN_STREET_RUN_METERS =>
+            {
+                last.to_m = run.to_m;
+            }
+            _ => merged.push(run),
+        }
+    }
+    merged
+}
+
+/// Horaires de fermeture des portions de rue d'un parcours.
+/// La rue ferme `close_before_minutes` avant l'arrivée du premier coureur au début de la
+/// portion, et rouvre `reopen_after_minutes` après le passage du dernier coureur à sa fin.
+pub fn closure_schedule(
+    parcours: &RaceParcours,
+    runs: &[StreetRun],
+    close_before_minutes: i64,
+    reopen_after_minutes: i64,
+) -> Vec<StreetClosure> {
+    runs.iter()
+        .filter_map(|run| {
+            let first_passage = parcours.passage_window(run.from_m)?.first_passage;
+            let last_passage = parcours.passage_window(run.to_m)?.last_passage;
+
+            Some(StreetClosure {
+                parcours_id: parcours.id.clone(),
+                parcours_name: parcours.name.clone(),
+                street: run.street.clone(),
+                highway: run.highway.clone(),
+                from_m: run.from_m,
+                to_m: run.to_m,
+                closes_at: format_datetime(
+                    &(first_passage - Duration::minutes(close_before_minutes)),
+                ),
+                first_passage: format_datetime(&first_passage),
+                last_passage: format_datetime(&last_passage),
+                reopens_at: format_datetime(
+                    &(last_passage + Duration::minutes(reopen_after_minutes)),
+                ),
+            })
+        })
+        .collect()
+}
+
+// ==================== Chargement des données ====================
+
+/// Calendrier de fermeture de toutes les rues empruntées par les parcours d'un événement,
+/// trié par heure de fermeture
+pub async fn load_street_closures(
+    pool: &SqlitePool,
+    event_id: &str,
+    close_before_minutes: Option<i64>,
+    reopen_after_minutes: Option<i64>,
+) -> Result<Vec<StreetClosure>, String> {
+    let before = close_before_minutes.unwrap_or(DEFAULT_CLOSE_BEFORE_MINUTES);
+    let after = reopen_after_minutes.unwrap_or(DEFAULT_REOPEN_AFTER_MINUTES);
+
+    let mut closures = Vec::new();
+    for parcours in load_race_parcours(pool, event_id).await? {
+        let min_lon = parcours.line.iter().map(|p| p.0).fold(f64::MAX, f64::min);
+        let max_lon = parcours.line.iter().map(|p| p.0).fold(f64::MIN, f64::max);
+        let min_lat = parcours.line.iter().map(|p| p.1).fold(f64::MAX, f64::min);
+        let max_lat = parcours.line.iter().map(|p| p.1).fold(f64::MIN, f64::max);
+
+        let streets = load_streets_in_bbox(
+            min_lon - BBOX_MARGIN_DEGREES,
+            min_lat - BBOX_MARGIN_DEGREES,
+            max_lon + BBOX_MARGIN_DEGREES,
+            max_lat + BBOX_MARGIN_DEGREES,
+        )?;
+        let runs = street_runs(&parcours.line, &streets);
+        closures.extend(closure_schedule(&parcours, &runs, before, after));
+    }
+
+    closures.sort_by(|a, b| a.closes_at.cmp(&b.closes_at));
+    Ok(closures)
+}
+
+// ==================== Commandes Tauri ====================
+
+/// Calendrier de fermeture des rues d'un événement (marges en minutes, optionnelles)
+#[tauri::command]
+pub async fn compute_street_closures(
+    app: AppHandle,
+    event_id: String,
+    close_before_minutes: Option<i64>,
+    reopen_after_minutes: Option<i64>,
+) -> Result<Vec<StreetClosure>, String> {
+    let pool = db::get_db_pool(&app).await?;
+    let closures =
+        load_street_closures(&pool, &event_id, close_before_minutes, reopen_after_minutes).await?;
+
+    println!(
+        "[CLOSURES] 🚧 {} portion(s) de rue à fermer pour l'événement {}",
+        closures.len(),
+        event_id
+    );
+    Ok(closures)
+}
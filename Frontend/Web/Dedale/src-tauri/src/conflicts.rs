@@ -0,0 +1,160 @@
+// Cohérence du planning par équipe
+// Détecte les actions qui se chevauchent et les enchaînements impossibles compte tenu
+// du temps de trajet entre deux équipements.
+
+use crate::scheduling::{distance_between, travel_minutes};
+use crate::utils::format_datetime;
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+/// Action planifiée, telle qu'utilisée par le contrôle de cohérence
+#[derive(Debug, Clone)]
+pub struct ScheduledAction {
+    pub action_id: String,
+    pub equipement_id: String,
+    pub team_id: String,
+    pub team_name: Option<String>,
+    pub start: NaiveDateTime,
+    pub duration_minutes: i64,
+    pub position: Option<(f64, f64)>,
+}
+
+impl ScheduledAction {
+    pub fn end(&self) -> NaiveDateTime {
+        self.start + Duration::minutes(self.duration_minutes)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// Deux actions de la même équipe se chevauchent
+    Overlap,
+    /// Le temps entre deux actions ne suffit pas pour faire le trajet
+    Travel,
+}
+
+/// Conflit entre deux actions d'une même équipe
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlanningConflict {
+    pub kind: ConflictKind,
+    pub team_id: String,
+    pub team_name: Option<String>,
+    pub first_action_id: String,
+    pub first_equipement_id: String,
+    pub first_time: String,
+    pub second_action_id: String,
+    pub second_equipement_id: String,
+    pub second_time: String,
+    pub travel_distance_m: f64,
+    /// Minutes nécessaires entre la fin de la première action et le début de la seconde
+    pub required_minutes: i64,
+    /// Minutes disponibles (négatif en cas de chevauchement)
+    pub available_minutes: i64,
+    pub message: String,
+}
+
+// ==================== Fonctions helper publiques et testables ====================
+
+/// Conflit entre `first` et `second`, avec son message
+fn conflict(
+    kind: ConflictKind,
+    first: &ScheduledAction,
+    second: &ScheduledAction,
+    distance: f64,
+    required: i64,
+) -> PlanningConflict {
+    let first_end = first.end();
+    let available = (second.start - first_end).num_minutes();
+
+    let message = match kind {
+        ConflictKind::Overlap => format!(
+            "Chevauchement : l'action de {} se termine à {}, la suivante commence à {}",
+            format_datetime(&first.start),
+            format_datetime(&first_end),
+            format_datetime(&second.start)
+        ),
+        ConflictKind::Travel => format!(
+            "Trajet de {:.1} km impossible : {} min nécessaires, {} min disponibles",
+            distance / 1000.0,
+            required,
+            available
+        ),
+    };
+
+    PlanningConflict {
+        kind,
+        team_id: first.team_id.clone(),
+        team_name: first.team_name.clone(),
+        first_action_id: first.action_id.clone(),
+        first_equipement_id: first.equipement_id.clone(),
+        first_time: format_datetime(&first.start),
+        second_action_id: second.action_id.clone(),
+        second_equipement_id: second.equipement_id.clone(),
+        second_time: format_datetime(&second.start),
+        travel_distance_m: distance,
+        required_minutes: required,
+        available_minutes: available,
+        message,
+    }
+}
+
+/// Détecte les conflits de chaque équipe.
+/// Tout chevauchement est signalé ; le temps de trajet est vérifié depuis l'action qui se
+/// termine le plus tard parmi les précédentes (une longue action peut finir après la
+/// dernière commencée), là où l'équipe se trouve réellement.
+pub fn detect_conflicts(
+    actions: &[ScheduledAction],
+    travel_speed_kmh: f64,
+) -> Vec<PlanningConflict> {
+    let mut team_ids: Vec<&str> = actions.iter().map(|a| a.team_id.as_str()).collect();
+    team_ids.sort();
+    team_ids.dedup();
+
+    let distance = |a: &ScheduledAction, b: &ScheduledAction| match (a.position, b.position) {
+        (Some(a), Some(b)) => distance_between(a, b),
+        _ => 0.0,
+    };
+
+    let mut conflicts = Vec::new();
+
+    for team_id in team_ids {
+        let mut team_actions: Vec<&ScheduledAction> =
+            actions.iter().filter(|a| a.team_id == team_id).collect();
+        team_actions.sort_by_key(|a| (a.start, a.end()));
+
+        // Action terminée le plus tard parmi celles déjà parcourues
+        let mut latest: Option<&ScheduledAction> = None;
+
+        for (i, second) in team_actions.iter().enumerate() {
+            for first in &team_actions[..i] {
+                if second.start < first.end() {
+                    let d = distance(first, second);
+                    conflicts.push(conflict(
+                        ConflictKind::Overlap,
+                        first,
+                        second,
+                        d,
+                        travel_minutes(d, travel_speed_kmh),
+                    ));
+                }
+            }
+
+            if let Some(first) = latest {
+                let d = distance(first, second);
+                let required = travel_minutes(d, travel_speed_kmh);
+                if second.start >= first.end()
+                    && required > (second.start - first.end()).num_minutes()
+                {
+                    conflicts.push(conflict(ConflictKind::Travel, first, second, d, required));
+                }
+            }
+
+            if latest.is_none_or(|l| second.end() >= l.end()) {
+                latest = Some(second);
+            }
+        }
+    }
+
+    conflicts
+}
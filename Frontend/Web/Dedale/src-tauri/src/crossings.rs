@@ -0,0 +1,366 @@
+// Croisements de parcours
+// Détection des endroits où un parcours se recoupe, croise un autre parcours de l'événement
+// ou traverse une route principale (réseau OSM de la base d'adresses), pour y proposer
+// des points de sécurité.
+
+use crate::closures::street_runs;
+use crate::db;
+use crate::geocoding::{load_streets_in_bbox, Street};
+use crate::map::calculate_distance;
+use crate::race::cumulative_lengths;
+use crate::snapping::{load_snap_lines, SnapLine};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use std::collections::HashSet;
+use tauri::AppHandle;
+use uuid::Uuid;
+
+/// Routes dont la traversée nécessite un point de sécurité
+pub const MAJOR_HIGHWAYS: [&str; 5] = ["motorway", "trunk", "primary", "secondary", "tertiary"];
+
+/// En dessous de cette distance (m), deux croisements sont considérés comme identiques
+const DUPLICATE_METERS: f64 = 5.0;
+
+/// Un point existant à moins de cette distance (m) couvre déjà le croisement
+const EXISTING_POINT_METERS: f64 = 15.0;
+
+/// Marge (degrés) autour du tracé pour le chargement des routes
+const BBOX_MARGIN_DEGREES: f64 = 0.0005;
+
+/// Intersection de deux tracés : position et distance depuis le départ sur chacun
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineCrossing {
+    pub point: (f64, f64),
+    pub chainage_a: f64,
+    pub chainage_b: f64,
+}
+
+/// Croisement proposé comme point de sécurité
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Crossing {
+    /// "self" (le parcours se recoupe), "parcours" (deux parcours) ou "road" (route OSM)
+    pub kind: String,
+    pub x: f64,
+    pub y: f64,
+    pub parcours_id: String,
+    pub parcours_name: Option<String>,
+    pub chainage_m: f64,
+    /// Second passage (même parcours) ou autre parcours
+    pub other_parcours_id: Option<String>,
+    pub other_parcours_name: Option<String>,
+    pub other_chainage_m: Option<f64>,
+    /// Route traversée
+    pub street: Option<String>,
+    pub highway: Option<String>,
+}
+
+// ==================== Fonctions helper publiques et testables ====================
+
+/// Intersection de deux segments : paramètres (t, u) le long de chacun, dans [0, 1].
+/// Les paramètres sont invariants par transformation affine : on peut calculer
+/// directement en (lon, lat). Les segments parallèles sont ignorés.
+pub fn segment_intersection(
+    a1: (f64, f64),
+    a2: (f64, f64),
+    b1: (f64, f64),
+    b2: (f64, f64),
+) -> Option<(f64, f64)> {
+    let cross = |p: (f64, f64), q: (f64, f64)| p.0 * q.1 - p.1 * q.0;
+    let r = (a2.0 - a1.0, a2.1 - a1.1);
+    let s = (b2.0 - b1.0, b2.1 - b1.1);
+    let denom = cross(r, s);
+    if denom.abs() < 1e-18 {
+        return None;
+    }
+
+    let qp = (b1.0 - a1.0, b1.1 - a1.1);
+    let t = cross(qp, s) / denom;
+    let u = cross(qp, r) / denom;
+    ((0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)).then_some((t, u))
+}
+
+/// Intersections entre deux tracés (ou d'un tracé avec lui-même si `b` vaut `None`),
+/// sans doublons aux sommets partagés par deux segments
+pub fn line_crossings(a: &[(f64, f64)], b: Option<&[(f64, f64)]>) -> Vec<LineCrossing> {
+    let same_line = b.is_none();
+    let b = b.unwrap_or(a);
+    if a.len() < 2 || b.len() < 2 {
+        return vec![];
+    }
+    let lengths_a = cumulative_lengths(a);
+    let lengths_b = cumulative_lengths(b);
+    let closed = same_line && a.first() == a.last();
+
+    let mut crossings: Vec<LineCrossing> = Vec::new();
+    for i in 0..a.len() - 1 {
+        // Sur un même tracé, on ignore les segments consécutifs (sommet commun)
+        let first_j = if same_line { i + 2 } else { 0 };
+        for j in first_j..b.len() - 1 {
+            if closed && i == 0 && j == b.len() - 2 {
+                continue;
+            }
+            let Some((t, u)) = segment_intersection(a[i], a[i + 1], b[j], b[j + 1]) else {
+                continue;
+            };
+
+            let crossing = LineCrossing {
+                point: (
+                    a[i].0 + t * (a[i + 1].0 - a[i].0),
+                    a[i].1 + t * (a[i + 1].1 - a[i].1),
+                ),
+                chainage_a: lengths_a[i] + t * (lengths_a[i + 1] - lengths_a[i]),
+                chainage_b: lengths_b[j] + u * (lengths_b[j + 1] - lengths_b[j]),
+            };
+            let duplicate = crossings.iter().any(|c| {
+                (c.chainage_a - crossing.chainage_a).abs() < DUPLICATE_METERS
+                    && (c.chainage_b - crossing.chainage_b).abs() < DUPLICATE_METERS
+            });
+            if !duplicate {
+                crossings.push(crossing);
+            }
+        }
+    }
+    crossings
+}
+
+/// Croisements d'un parcours avec lui-même et avec les autres parcours de l'événement
+pub fn parcours_crossings(parcours: &[SnapLine]) -> Vec<Crossing> {
+    let mut crossings = Vec::new();
+
+    for (index, p) in parcours.iter().enumerate() {
+        for c in line_crossings(&p.line, None) {
+            crossings.push(Crossing {
+                kind: "self".to_string(),
+                x: c.point.0,
+                y: c.point.1,
+                parcours_id: p.id.clone(),
+                parcours_name: p.name.clone(),
+                chainage_m: c.chainage_a,
+                other_parcours_id: Some(p.id.clone()),
+                other_parcours_name: p.name.clone(),
+                other_chainage_m: Some(c.chainage_b),
+                street: None,
+                highway: None,
+            });
+        }
+
+        for other in &parcours[index + 1..] {
+            for c in line_crossings(&p.line, Some(&other.line)) {
+                crossings.push(Crossing {
+                    kind: "parcours".to_string(),
+                    x: c.point.0,
+                    y: c.point.1,
+                    parcours_id: p.id.clone(),
+                    parcours_name: p.name.clone(),
+                    chainage_m: c.chainage_a,
+                    other_parcours_id: Some(other.id.clone()),
+                    other_parcours_name: other.name.clone(),
+                    other_chainage_m: Some(c.chainage_b),
+                    street: None,
+                    highway: None,
+                });
+            }
+        }
+    }
+    crossings
+}
+
+/// Traversées de routes principales par un parcours.
+/// Les rues que le parcours emprunte (voir `closures::street_runs`) sont fermées à la
+/// circulation et ne sont pas comptées comme des traversées.
+pub fn road_crossings(parcours: &SnapLine, streets: &[Street]) -> Vec<Crossing> {
+    let followed: HashSet<String> = street_runs(&parcours.line, streets)
+        .into_iter()
+        .map(|run| run.street)
+        .collect();
+
+    let mut crossings: Vec<Crossing> = Vec::new();
+    for street in streets {
+        let major = street
+            .highway
+            .as_deref()
+            .is_some_and(|h| MAJOR_HIGHWAYS.contains(&h));
+        if !major || followed.contains(&street.name) {
+            continue;
+        }
+
+        for c in line_crossings(&parcours.line, Some(&street.line)) {
+            // Une même route découpée en plusieurs ways se croise au sommet commun
+            let duplicate = crossings.iter().any(|other| {
+                other.street.as_deref() == Some(street.name.as_str())
+                    && (other.chainage_m - c.chainage_a).abs() < DUPLICATE_METERS
+            });
+            if duplicate {
+                continue;
+            }
+            crossings.push(Crossing {
+                kind: "road".to_string(),
+                x: c.point.0,
+                y: c.point.1,
+                parcours_id: parcours.id.clone(),
+                parcours_name: parcours.name.clone(),
+                chainage_m: c.chainage_a,
+                other_parcours_id: None,
+                other_parcours_name: None,
+                other_chainage_m: None,
+                street: Some(street.name.clone()),
+                highway: street.highway.clone(),
+            });
+        }
+    }
+    crossings
+}
+
+/// Nom du point de sécurité proposé pour un croisement
+pub fn crossing_point_name(crossing: &Crossing) -> String {
+    let parcours = crossing.parcours_name.as_deref().unwrap_or("Parcours");
+    match crossing.kind.as_str() {
+        "road" => format!(
+            "Traversée {} – {}",
+            crossing.street.as_deref().unwrap_or("route"),
+            parcours
+        ),
+        "self" => format!("Croisement {} (double passage)", parcours),
+        _ => format!(
+            "Croisement {} / {}",
+            parcours,
+            crossing
+                .other_parcours_name
+                .as_deref()
+                .unwrap_or("Parcours")
+        ),
+    }
+}
+
+/// Commentaire du point : positions kilométriques des passages
+pub fn crossing_point_comment(crossing: &Crossing) -> String {
+    let km = |m: f64| format!("km {:.2}", m / 1000.0).replace('.', ",");
+    match (crossing.kind.as_str(), crossing.other_chainage_m) {
+        ("self", Some(other)) => format!(
+            "Passages au {} et au {}",
+            km(crossing.chainage_m),
+            km(other)
+        ),
+        ("parcours", Some(other)) => format!(
+            "{} au {}, {} au {}",
+            crossing.parcours_name.as_deref().unwrap_or("Parcours"),
+            km(crossing.chainage_m),
+            crossing
+                .other_parcours_name
+                .as_deref()
+                .unwrap_or("Parcours"),
+            km(other)
+        ),
+        _ => format!(
+            "Traversée au {}{}",
+            km(crossing.chainage_m),
+            crossing
+                .highway
+                .as_deref()
+                .map(|h| format!(" ({})", h))
+                .unwrap_or_default()
+        ),
+    }
+}
+
+// ==================== Commandes Tauri ====================
+
+/// Croisements des parcours d'un événement (entre eux, avec eux-mêmes et avec les routes)
+#[tauri::command]
+pub async fn detect_parcours_crossings(
+    app: AppHandle,
+    event_id: String,
+) -> Result<Vec<Crossing>, String> {
+    let pool = db::get_db_pool(&app).await?;
+    let parcours = load_snap_lines(&pool, &event_id).await?;
+
+    let mut crossings = parcours_crossings(&parcours);
+    for p in &parcours {
+        let min_lon = p.line.iter().map(|c| c.0).fold(f64::MAX, f64::min);
+        let max_lon = p.line.iter().map(|c| c.0).fold(f64::MIN, f64::max);
+        let min_lat = p.line.iter().map(|c| c.1).fold(f64::MAX, f64::min);
+        let max_lat = p.line.iter().map(|c| c.1).fold(f64::MIN, f64::max);
+
+        let streets = load_streets_in_bbox(
+            min_lon - BBOX_MARGIN_DEGREES,
+            min_lat - BBOX_MARGIN_DEGREES,
+            max_lon + BBOX_MARGIN_DEGREES,
+            max_lat + BBOX_MARGIN_DEGREES,
+        )?;
+        crossings.extend(road_crossings(p, &streets));
+    }
+
+    crossings.sort_by(|a, b| {
+        a.parcours_id
+            .cmp(&b.parcours_id)
+            .then(a.chainage_m.total_cmp(&b.chainage_m))
+    });
+
+    println!(
+        "[CROSSINGS] ✖️ {} croisement(s) détecté(s) pour l'événement {}",
+        crossings.len(),
+        event_id
+    );
+    Ok(crossings)
+}
+
+/// Crée les points de sécurité des croisements retenus.
+/// Un croisement déjà couvert par un point existant est ignoré. Retourne les ids créés.
+#[tauri::command]
+pub async fn create_crossing_points(
+    app: AppHandle,
+    event_id: String,
+    crossings: Vec<Crossing>,
+) -> Result<Vec<String>, String> {
+    let pool = db::get_db_pool(&app).await?;
+
+    let mut existing: Vec<(f64, f64)> = sqlx::query("SELECT x, y FROM point WHERE event_id = ?")
+        .bind(&event_id)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|row| (row.get("x"), row.get("y")))
+        .collect();
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let mut created = Vec::new();
+
+    for crossing in &crossings {
+        let covered = existing.iter().any(|(x, y)| {
+            calculate_distance(*y, *x, crossing.y, crossing.x) < EXISTING_POINT_METERS
+        });
+        if covered {
+            continue;
+        }
+
+        let id = Uuid::new_v4().to_string();
+        sqlx::query(
+            r#"INSERT INTO point (id, event_id, x, y, name, comment, type, status, parcours_id, chainage_m)
+               VALUES (?, ?, ?, ?, ?, ?, 'danger', 0, ?, ?)"#,
+        )
+        .bind(&id)
+        .bind(&event_id)
+        .bind(crossing.x)
+        .bind(crossing.y)
+        .bind(crossing_point_name(crossing))
+        .bind(crossing_point_comment(crossing))
+        .bind(&crossing.parcours_id)
+        .bind(crossing.chainage_m)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Erreur lors de la création du point de croisement : {}", e))?;
+
+        existing.push((crossing.x, crossing.y));
+        created.push(id);
+    }
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    println!(
+        "[CROSSINGS] ✅ {} point(s) de sécurité créé(s) sur {} croisement(s)",
+        created.len(),
+        crossings.len()
+    );
+    Ok(created)
+}
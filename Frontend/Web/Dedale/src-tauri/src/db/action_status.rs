@@ -0,0 +1,302 @@
+use crate::db::get_db_pool;
+use crate::progress::emit_action_progress;
+use crate::types::*;
+use crate::utils::parse_datetime;
+use chrono::{Local, NaiveDateTime};
+use sqlx::{Row, SqlitePool};
+use std::collections::HashMap;
+use tauri::AppHandle;
+use uuid::Uuid;
+
+/// Format des dates de changement de statut (à la seconde près)
+pub const STATUS_TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+// ============================================
+// FONCTIONS HELPER (testables)
+// ============================================
+
+/// Vrai si l'action peut passer du statut `from` au statut `to`.
+/// Une action annulée ne peut être que replanifiée ; une action terminée peut être
+/// rouverte (retour à planifiée ou en cours) si elle a été validée par erreur.
+pub fn can_transition(from: ActionStatus, to: ActionStatus) -> bool {
+    if from == to {
+        return false;
+    }
+
+    match from {
+        ActionStatus::Cancelled => to == ActionStatus::Planned,
+        ActionStatus::Done => matches!(to, ActionStatus::Planned | ActionStatus::InProgress),
+        _ => true,
+    }
+}
+
+/// Statut visé par la case « fait » du planning : une action terminée est rouverte,
+/// les autres sont terminées. Une action annulée doit d'abord être replanifiée.
+pub fn toggled_status(current: ActionStatus) -> Result<ActionStatus, String> {
+    match current {
+        ActionStatus::Done => Ok(ActionStatus::Planned),
+        ActionStatus::Cancelled => {
+            Err("Action annulée : replanifiez-la avant de la marquer comme terminée".to_string())
+        }
+        _ => Ok(ActionStatus::Done),
+    }
+}
+
+/// Date du changement de statut au format stocké.
+/// La date envoyée par le mobile est conservée si elle est lisible, sinon on prend `now`.
+pub fn normalize_changed_at(value: Option<&str>, now: NaiveDateTime) -> String {
+    value
+        .and_then(parse_datetime)
+        .unwrap_or(now)
+        .format(STATUS_TIMESTAMP_FORMAT)
+        .to_string()
+}
+
+// ============================================
+// CHANGEMENTS DE STATUT
+// ============================================
+
+/// Enregistre un changement de statut (historique, photos) et met à jour l'action
+pub async fn record_status_change(
+    pool: &SqlitePool,
+    action_id: &str,
+    status: ActionStatus,
+    person_id: Option<String>,
+    note: Option<String>,
+    photos: Vec<String>,
+    changed_at: String,
+) -> Result<ActionStatusChange, String> {
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let current: Option<String> = sqlx::query_scalar(
+        "SELECT COALESCE(status, CASE WHEN is_done THEN 'done' ELSE 'planned' END) FROM action WHERE id = ?",
+    )
+    .bind(action_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let current = current.ok_or_else(|| format!("Action {} introuvable", action_id))?;
+    let previous_status = ActionStatus::parse(&current);
+
+    if let Some(previous) = previous_status {
+        if !can_transition(previous, status) {
+            return Err(format!(
+                "Passage de « {} » à « {} » impossible",
+                previous.label(),
+                status.label()
+            ));
+        }
+    }
+
+    let change = ActionStatusChange {
+        id: Uuid::new_v4().to_string(),
+        action_id: action_id.to_string(),
+        previous_status,
+        status,
+        changed_at,
+        person_id,
+        person_name: None,
+        note: note.filter(|n| !n.trim().is_empty()),
+        photos,
+    };
+
+    sqlx::query(
+        "INSERT INTO action_status_change (id, action_id, previous_status, status, changed_at, person_id, note)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&change.id)
+    .bind(&change.action_id)
+    .bind(change.previous_status.map(|s| s.as_str()))
+    .bind(change.status.as_str())
+    .bind(&change.changed_at)
+    .bind(&change.person_id)
+    .bind(&change.note)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    for photo in &change.photos {
+        sqlx::query(
+            "INSERT INTO action_status_picture (status_change_id, image_data) VALUES (?, ?)",
+        )
+        .bind(&change.id)
+        .bind(photo)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    // is_done reste renseigné pour les écrans et exports qui ne connaissent que lui
+    sqlx::query("UPDATE action SET status = ?, status_changed_at = ?, is_done = ? WHERE id = ?")
+        .bind(change.status.as_str())
+        .bind(&change.changed_at)
+        .bind(change.status == ActionStatus::Done)
+        .bind(action_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    println!(
+        "[DB] 🚦 Action {} : {} -> {} ({} photo(s))",
+        action_id,
+        current,
+        change.status.as_str(),
+        change.photos.len()
+    );
+    Ok(change)
+}
+
+/// Applique les changements de statut remontés par le mobile, dans l'ordre chronologique.
+/// Les changements invalides (statut inconnu, transition refusée) sont ignorés.
+pub async fn apply_mobile_statuses(
+    pool: &SqlitePool,
+    mut updates: Vec<MobileActionStatus>,
+) -> Result<usize, String> {
+    let now = Local::now().naive_local();
+    updates.sort_by_key(|u| normalize_changed_at(u.changed_at.as_deref(), now));
+
+    let mut applied = 0;
+    for update in updates {
+        let Some(status) = ActionStatus::parse(&update.status) else {
+            println!(
+                "[DB] ⚠️ Statut inconnu '{}' pour l'action {}",
+                update.status, update.action_id
+            );
+            continue;
+        };
+
+        let changed_at = normalize_changed_at(update.changed_at.as_deref(), now);
+        match record_status_change(
+            pool,
+            &update.action_id,
+            status,
+            update.person_id,
+            update.note,
+            update.photos,
+            changed_at,
+        )
+        .await
+        {
+            Ok(_) => applied += 1,
+            Err(e) => println!("[DB] ⚠️ Statut ignoré pour {}: {}", update.action_id, e),
+        }
+    }
+
+    Ok(applied)
+}
+
+/// Change le statut d'une action depuis le desktop
+#[tauri::command]
+pub async fn set_action_status(
+    app: AppHandle,
+    action_id: String,
+    status: ActionStatus,
+    person_id: Option<String>,
+    note: Option<String>,
+    photos: Option<Vec<String>>,
+) -> Result<ActionStatusChange, String> {
+    let pool = get_db_pool(&app).await?;
+    let changed_at = normalize_changed_at(None, Local::now().naive_local());
+
+    let change = record_status_change(
+        &pool,
+        &action_id,
+        status,
+        person_id,
+        note,
+        photos.unwrap_or_default(),
+        changed_at,
+    )
+    .await?;
+    emit_action_progress(&app, &pool, &action_id).await;
+
+    Ok(change)
+}
+
+/// Historique des changements de statut d'une action, du plus ancien au plus récent
+#[tauri::command]
+pub async fn fetch_action_status_history(
+    app: AppHandle,
+    action_id: String,
+) -> Result<Vec<ActionStatusChange>, String> {
+    let pool = get_db_pool(&app).await?;
+
+    let rows = sqlx::query(
+        r#"
+        SELECT s.id, s.action_id, s.previous_status, s.status, s.changed_at, s.person_id, s.note,
+               p.firstname, p.lastname
+        FROM action_status_change s
+        LEFT JOIN person p ON s.person_id = p.id
+        WHERE s.action_id = ?
+        ORDER BY s.changed_at ASC
+    "#,
+    )
+    .bind(&action_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    // Photos de tout l'historique en une requête, regroupées par changement
+    let picture_rows = sqlx::query(
+        r#"
+        SELECT p.status_change_id, p.image_data
+        FROM action_status_picture p
+        JOIN action_status_change s ON p.status_change_id = s.id
+        WHERE s.action_id = ? AND p.image_data IS NOT NULL
+        ORDER BY p.rowid ASC
+    "#,
+    )
+    .bind(&action_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut photos_by_change: HashMap<String, Vec<String>> = HashMap::new();
+    for row in picture_rows {
+        photos_by_change
+            .entry(row.get("status_change_id"))
+            .or_default()
+            .push(row.get("image_data"));
+    }
+
+    let mut history = Vec::new();
+    for row in rows {
+        let id: String = row.get("id");
+        let photos = photos_by_change.remove(&id).unwrap_or_default();
+
+        let previous_status: Option<String> = row.get("previous_status");
+        let status: String = row.get("status");
+        let firstname: Option<String> = row.get("firstname");
+        let lastname: Option<String> = row.get("lastname");
+        let person_name = match (firstname, lastname) {
+            (None, None) => None,
+            (f, l) => Some(
+                format!("{} {}", f.unwrap_or_default(), l.unwrap_or_default())
+                    .trim()
+                    .to_string(),
+            ),
+        };
+
+        history.push(ActionStatusChange {
+            id,
+            action_id: row.get("action_id"),
+            previous_status: previous_status.as_deref().and_then(ActionStatus::parse),
+            status: ActionStatus::parse(&status).unwrap_or(ActionStatus::Planned),
+            changed_at: row.get("changed_at"),
+            person_id: row.get("person_id"),
+            person_name,
+            note: row.get("note"),
+            photos,
+        });
+    }
+
+    println!(
+        "[DB] 📜 {} changement(s) de statut pour l'action {}",
+        history.len(),
+        action_id
+    );
+    Ok(history)
+}
@@ -0,0 +1,259 @@
+use crate::db::get_db_pool;
+use crate::types::*;
+use crate::utils::format_datetime;
+use chrono::NaiveDateTime;
+use sqlx::{Row, SqlitePool};
+use std::collections::{HashMap, HashSet};
+use tauri::AppHandle;
+use uuid::Uuid;
+
+// ============================================
+// FONCTIONS HELPER (testables)
+// ============================================
+
+/// Vrai si ajouter « action_id dépend de depends_on_id » crée un cycle
+/// (depends_on_id dépend déjà, directement ou non, de action_id)
+pub fn creates_cycle(
+    dependencies: &[ActionDependency],
+    action_id: &str,
+    depends_on_id: &str,
+) -> bool {
+    if action_id == depends_on_id {
+        return true;
+    }
+
+    let mut stack = vec![depends_on_id];
+    let mut visited: HashSet<&str> = HashSet::new();
+
+    while let Some(current) = stack.pop() {
+        if current == action_id {
+            return true;
+        }
+        if !visited.insert(current) {
+            continue;
+        }
+        stack.extend(
+            dependencies
+                .iter()
+                .filter(|d| d.action_id == current)
+                .map(|d| d.depends_on_id.as_str()),
+        );
+    }
+
+    false
+}
+
+/// Vrai si la dépendance « action_id dépend de depends_on_id » est déjà enregistrée
+pub fn dependency_exists(
+    dependencies: &[ActionDependency],
+    action_id: &str,
+    depends_on_id: &str,
+) -> bool {
+    dependencies
+        .iter()
+        .any(|d| d.action_id == action_id && d.depends_on_id == depends_on_id)
+}
+
+/// Actions bloquées : au moins un prédécesseur n'est pas fait
+pub fn blocked_action_ids(
+    dependencies: &[ActionDependency],
+    done: &HashSet<String>,
+) -> HashSet<String> {
+    dependencies
+        .iter()
+        .filter(|d| !done.contains(&d.depends_on_id))
+        .map(|d| d.action_id.clone())
+        .collect()
+}
+
+/// Vérifie qu'aucune action ne commence avant la fin de ses prédécesseurs.
+/// `schedule` associe à chaque action son début et sa fin ; les actions non planifiées sont ignorées.
+pub fn check_precedence(
+    dependencies: &[ActionDependency],
+    schedule: &HashMap<String, (NaiveDateTime, NaiveDateTime)>,
+) -> Vec<DependencyViolation> {
+    dependencies
+        .iter()
+        .filter_map(|d| {
+            let (start, _) = schedule.get(&d.action_id)?;
+            let (_, predecessor_end) = schedule.get(&d.depends_on_id)?;
+            if start >= predecessor_end {
+                return None;
+            }
+
+            Some(DependencyViolation {
+                action_id: d.action_id.clone(),
+                depends_on_id: d.depends_on_id.clone(),
+                action_time: format_datetime(start),
+                depends_on_end: format_datetime(predecessor_end),
+                message: format!(
+                    "L'action commence à {} alors que l'action dont elle dépend se termine à {}",
+                    format_datetime(start),
+                    format_datetime(predecessor_end)
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Dépendances entre actions d'un événement
+pub async fn fetch_event_dependencies(
+    pool: &SqlitePool,
+    event_id: &str,
+) -> Result<Vec<ActionDependency>, String> {
+    let rows = sqlx::query(
+        r#"
+        SELECT d.id, d.action_id, d.depends_on_id
+        FROM action_dependency d
+        JOIN action a ON d.action_id = a.id
+        JOIN equipement e ON a.equipement_id = e.id
+        WHERE e.event_id = ?
+    "#,
+    )
+    .bind(event_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| ActionDependency {
+            id: row.get("id"),
+            action_id: row.get("action_id"),
+            depends_on_id: row.get("depends_on_id"),
+        })
+        .collect())
+}
+
+/// Dépendances dont l'action dépendante est affectée à une équipe
+pub async fn fetch_team_dependencies(
+    pool: &SqlitePool,
+    team_id: &str,
+) -> Result<Vec<ActionDependency>, String> {
+    let rows = sqlx::query(
+        r#"
+        SELECT d.id, d.action_id, d.depends_on_id
+        FROM action_dependency d
+        JOIN action a ON d.action_id = a.id
+        WHERE a.team_id = ?
+    "#,
+    )
+    .bind(team_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| ActionDependency {
+            id: row.get("id"),
+            action_id: row.get("action_id"),
+            depends_on_id: row.get("depends_on_id"),
+        })
+        .collect())
+}
+
+// ============================================
+// DÉPENDANCES ENTRE ACTIONS
+// ============================================
+
+#[tauri::command]
+pub async fn fetch_action_dependencies(
+    app: AppHandle,
+    event_id: String,
+) -> Result<Vec<ActionDependency>, String> {
+    let pool = get_db_pool(&app).await?;
+    let dependencies = fetch_event_dependencies(&pool, &event_id).await?;
+
+    println!(
+        "[DB] 🔗 {} dépendance(s) pour l'événement {}",
+        dependencies.len(),
+        event_id
+    );
+    Ok(dependencies)
+}
+
+/// Ajoute une dépendance : `action_id` ne peut commencer qu'après `depends_on_id`
+#[tauri::command]
+pub async fn add_action_dependency(
+    app: AppHandle,
+    action_id: String,
+    depends_on_id: String,
+) -> Result<ActionDependency, String> {
+    let pool = get_db_pool(&app).await?;
+
+    let event_query =
+        "SELECT e.event_id FROM action a JOIN equipement e ON a.equipement_id = e.id WHERE a.id = ?";
+
+    let event_id: Option<String> = sqlx::query_scalar(event_query)
+        .bind(&action_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let event_id = event_id.ok_or_else(|| format!("Action {} introuvable", action_id))?;
+
+    let depends_on_event_id: Option<String> = sqlx::query_scalar(event_query)
+        .bind(&depends_on_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let depends_on_event_id =
+        depends_on_event_id.ok_or_else(|| format!("Action {} introuvable", depends_on_id))?;
+    if depends_on_event_id != event_id {
+        return Err(format!(
+            "L'action {} n'appartient pas au même événement",
+            depends_on_id
+        ));
+    }
+
+    let dependencies = fetch_event_dependencies(&pool, &event_id).await?;
+
+    if dependency_exists(&dependencies, &action_id, &depends_on_id) {
+        return Err("Cette dépendance existe déjà".to_string());
+    }
+    if creates_cycle(&dependencies, &action_id, &depends_on_id) {
+        return Err("Cette dépendance créerait un cycle entre les actions".to_string());
+    }
+
+    let dependency = ActionDependency {
+        id: Uuid::new_v4().to_string(),
+        action_id,
+        depends_on_id,
+    };
+
+    sqlx::query("INSERT INTO action_dependency (id, action_id, depends_on_id) VALUES (?, ?, ?)")
+        .bind(&dependency.id)
+        .bind(&dependency.action_id)
+        .bind(&dependency.depends_on_id)
+        .execute(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    println!(
+        "[DB] ✅ L'action {} dépend maintenant de {}",
+        dependency.action_id, dependency.depends_on_id
+    );
+    Ok(dependency)
+}
+
+#[tauri::command]
+pub async fn remove_action_dependency(
+    app: AppHandle,
+    action_id: String,
+    depends_on_id: String,
+) -> Result<(), String> {
+    let pool = get_db_pool(&app).await?;
+
+    sqlx::query("DELETE FROM action_dependency WHERE action_id = ? AND depends_on_id = ?")
+        .bind(&action_id)
+        .bind(&depends_on_id)
+        .execute(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    println!(
+        "[DB] 🗑️ Dépendance {} -> {} supprimée",
+        action_id, depends_on_id
+    );
+    Ok(())
+}
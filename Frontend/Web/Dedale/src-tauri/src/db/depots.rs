@@ -0,0 +1,353 @@
+use crate::db::get_db_pool;
+use crate::types::*;
+use sqlx::{Row, SqlitePool};
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+use uuid::Uuid;
+
+// ============================================
+// FONCTIONS HELPER (testables)
+// ============================================
+
+/// Calcule le nombre maximal d'unités sorties simultanément.
+/// Chaque intervalle est (sortie, retour, quantité) ; un retour absent signifie
+/// que le matériel ne revient pas pendant l'événement.
+pub fn compute_peak_usage(intervals: &[(String, Option<String>, i32)]) -> i32 {
+    // (horodatage, ordre, delta) : à horodatage égal, les retours passent avant les sorties
+    let mut deltas: Vec<(&str, u8, i32)> = Vec::new();
+    for (departure, return_time, quantity) in intervals {
+        deltas.push((departure.as_str(), 1, *quantity));
+        if let Some(back) = return_time {
+            deltas.push((back.as_str(), 0, -*quantity));
+        }
+    }
+    deltas.sort();
+
+    let mut current = 0;
+    let mut peak = 0;
+    for (_, _, delta) in deltas {
+        current += delta;
+        peak = peak.max(current);
+    }
+    peak
+}
+
+/// Regroupe les mouvements par dépôt et calcule le bilan stock / besoin par type.
+/// Les équipements sans dépôt sont regroupés dans un rapport sans dépôt, placé en dernier.
+pub fn build_depot_report(depots: &[Depot], movements: Vec<DepotMovement>) -> Vec<DepotReport> {
+    let mut by_depot: BTreeMap<Option<String>, Vec<DepotMovement>> = BTreeMap::new();
+    for movement in movements {
+        // Un dépôt supprimé entre-temps est traité comme « sans dépôt »
+        let key = movement
+            .depot_id
+            .clone()
+            .filter(|id| depots.iter().any(|d| &d.id == id));
+        by_depot.entry(key).or_default().push(movement);
+    }
+
+    let mut reports = Vec::new();
+
+    for depot in depots {
+        if let Some(mut departures) = by_depot.remove(&Some(depot.id.clone())) {
+            departures.sort_by(|a, b| a.departure_time.cmp(&b.departure_time));
+            let balances = compute_balances(Some(depot), &departures);
+            reports.push(DepotReport {
+                depot: Some(depot.clone()),
+                departures,
+                balances,
+            });
+        }
+    }
+
+    if let Some(mut departures) = by_depot.remove(&None) {
+        departures.sort_by(|a, b| a.departure_time.cmp(&b.departure_time));
+        let balances = compute_balances(None, &departures);
+        reports.push(DepotReport {
+            depot: None,
+            departures,
+            balances,
+        });
+    }
+
+    reports
+}
+
+fn compute_balances(depot: Option<&Depot>, departures: &[DepotMovement]) -> Vec<DepotTypeBalance> {
+    let mut by_type: BTreeMap<Option<String>, Vec<&DepotMovement>> = BTreeMap::new();
+    for movement in departures {
+        by_type
+            .entry(movement.type_id.clone())
+            .or_default()
+            .push(movement);
+    }
+
+    by_type
+        .into_iter()
+        .map(|(type_id, movements)| {
+            let stock = depot
+                .and_then(|d| {
+                    d.stock
+                        .iter()
+                        .find(|s| Some(&s.type_id) == type_id.as_ref())
+                })
+                .map(|s| s.quantity)
+                .unwrap_or(0);
+
+            let intervals: Vec<(String, Option<String>, i32)> = movements
+                .iter()
+                .filter_map(|m| {
+                    m.departure_time
+                        .clone()
+                        .map(|t| (t, m.return_time.clone(), m.quantity))
+                })
+                .collect();
+
+            let total_out = movements.iter().map(|m| m.quantity).sum();
+            let peak_out = compute_peak_usage(&intervals);
+
+            DepotTypeBalance {
+                type_name: movements.first().and_then(|m| m.type_name.clone()),
+                type_id,
+                stock,
+                total_out,
+                peak_out,
+                missing: (peak_out - stock).max(0),
+            }
+        })
+        .collect()
+}
+
+async fn fetch_all_depots(pool: &SqlitePool) -> Result<Vec<Depot>, String> {
+    let rows = sqlx::query("SELECT id, name, address, x, y FROM depot ORDER BY name ASC")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let stock_rows = sqlx::query(
+        "SELECT s.depot_id, s.type_id, s.quantity, t.name as type_name
+         FROM depot_stock s
+         LEFT JOIN type t ON s.type_id = t.id",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let stock: Vec<DepotStock> = stock_rows
+        .into_iter()
+        .map(|row| DepotStock {
+            depot_id: row.get("depot_id"),
+            type_id: row.get("type_id"),
+            type_name: row.get("type_name"),
+            quantity: row.get("quantity"),
+        })
+        .collect();
+
+    let depots = rows
+        .into_iter()
+        .map(|row| {
+            let id: String = row.get("id");
+            Depot {
+                stock: stock.iter().filter(|s| s.depot_id == id).cloned().collect(),
+                id,
+                name: row.get("name"),
+                address: row.get("address"),
+                x: row.get("x"),
+                y: row.get("y"),
+            }
+        })
+        .collect();
+
+    Ok(depots)
+}
+
+// ============================================
+// DÉPÔTS
+// ============================================
+
+#[tauri::command]
+pub async fn fetch_depots(app: AppHandle) -> Result<Vec<Depot>, String> {
+    let pool = get_db_pool(&app).await?;
+    let depots = fetch_all_depots(&pool).await?;
+
+    println!("[DB] 🏭 {} dépôt(s) récupéré(s)", depots.len());
+    Ok(depots)
+}
+
+#[tauri::command]
+pub async fn create_depot(
+    app: AppHandle,
+    name: String,
+    address: Option<String>,
+    x: f64,
+    y: f64,
+) -> Result<Depot, String> {
+    let pool = get_db_pool(&app).await?;
+    let uuid = Uuid::new_v4().to_string();
+
+    sqlx::query("INSERT INTO depot (id, name, address, x, y) VALUES (?, ?, ?, ?, ?)")
+        .bind(&uuid)
+        .bind(&name)
+        .bind(&address)
+        .bind(x)
+        .bind(y)
+        .execute(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    println!("[DB] ✅ Dépôt '{}' créé", name);
+
+    Ok(Depot {
+        id: uuid,
+        name: Some(name),
+        address,
+        x,
+        y,
+        stock: vec![],
+    })
+}
+
+#[tauri::command]
+pub async fn update_depot(
+    app: AppHandle,
+    depot_id: String,
+    name: String,
+    address: Option<String>,
+    x: f64,
+    y: f64,
+) -> Result<(), String> {
+    let pool = get_db_pool(&app).await?;
+
+    sqlx::query("UPDATE depot SET name = ?, address = ?, x = ?, y = ? WHERE id = ?")
+        .bind(&name)
+        .bind(&address)
+        .bind(x)
+        .bind(y)
+        .bind(&depot_id)
+        .execute(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    println!("[DB] ✅ Dépôt {} mis à jour", depot_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_depot(app: AppHandle, depot_id: String) -> Result<(), String> {
+    let pool = get_db_pool(&app).await?;
+
+    // Le stock est supprimé par ON DELETE CASCADE, les équipements repassent à depot_id NULL
+    sqlx::query("DELETE FROM depot WHERE id = ?")
+        .bind(&depot_id)
+        .execute(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    println!("[DB] 🗑️ Dépôt {} supprimé", depot_id);
+    Ok(())
+}
+
+/// Définit la quantité disponible d'un type d'équipement dans un dépôt
+#[tauri::command]
+pub async fn set_depot_stock(
+    app: AppHandle,
+    depot_id: String,
+    type_id: String,
+    quantity: i32,
+) -> Result<(), String> {
+    let pool = get_db_pool(&app).await?;
+
+    sqlx::query(
+        "INSERT INTO depot_stock (id, depot_id, type_id, quantity) VALUES (?, ?, ?, ?)
+         ON CONFLICT(depot_id, type_id) DO UPDATE SET quantity = excluded.quantity",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&depot_id)
+    .bind(&type_id)
+    .bind(quantity.max(0))
+    .execute(&pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    println!(
+        "[DB] ✅ Stock du dépôt {} pour le type {} : {}",
+        depot_id, type_id, quantity
+    );
+    Ok(())
+}
+
+/// Rattache un équipement à son dépôt d'origine (None pour le détacher)
+#[tauri::command]
+pub async fn set_equipement_depot(
+    app: AppHandle,
+    equipement_id: String,
+    depot_id: Option<String>,
+) -> Result<(), String> {
+    let pool = get_db_pool(&app).await?;
+
+    sqlx::query("UPDATE equipement SET depot_id = ? WHERE id = ?")
+        .bind(&depot_id)
+        .bind(&equipement_id)
+        .execute(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    println!(
+        "[DB] ✅ Équipement {} rattaché au dépôt {:?}",
+        equipement_id, depot_id
+    );
+    Ok(())
+}
+
+/// Rapport par dépôt de ce qui sort (pose) et revient (dépose) pour un événement
+#[tauri::command]
+pub async fn fetch_depot_report(
+    app: AppHandle,
+    event_id: String,
+) -> Result<Vec<DepotReport>, String> {
+    let pool = get_db_pool(&app).await?;
+    let depots = fetch_all_depots(&pool).await?;
+
+    let rows = sqlx::query(
+        r#"
+        SELECT
+            e.id, e.depot_id, e.type_id, e.quantity, e.length_per_unit, e.description,
+            e.date_pose, e.date_depose,
+            t.name as type_name,
+            (SELECT tm.name FROM action a JOIN team tm ON tm.id = a.team_id
+             WHERE a.equipement_id = e.id AND a.type = 'pose') as team_name
+        FROM equipement e
+        LEFT JOIN type t ON e.type_id = t.id
+        WHERE e.event_id = ?
+        ORDER BY e.date_pose ASC
+    "#,
+    )
+    .bind(&event_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let movements: Vec<DepotMovement> = rows
+        .into_iter()
+        .map(|row| DepotMovement {
+            equipement_id: row.get("id"),
+            depot_id: row.get("depot_id"),
+            type_id: row.get("type_id"),
+            type_name: row.get("type_name"),
+            description: row.get("description"),
+            quantity: row.get::<Option<i32>, _>("quantity").unwrap_or(0),
+            length_per_unit: row.get::<Option<i32>, _>("length_per_unit").unwrap_or(0),
+            departure_time: row.get("date_pose"),
+            return_time: row.get("date_depose"),
+            team_name: row.get("team_name"),
+        })
+        .collect();
+
+    let reports = build_depot_report(&depots, movements);
+
+    println!(
+        "[DB] 🏭 Rapport dépôts pour l'événement {} : {} dépôt(s)",
+        event_id,
+        reports.len()
+    );
+    Ok(reports)
+}
@@ -1,6 +1,11 @@
+use crate::db::dependencies::blocked_action_ids;
 use crate::db::get_db_pool;
+use crate::db::planning::compute_team_itinerary;
+use crate::markers::load_km_markers;
+use crate::race::{load_race_parcours, passage_time_on_parcours};
 use crate::types::*;
 use sqlx::Row;
+use std::collections::HashSet;
 use tauri::AppHandle;
 use uuid::Uuid;
 
@@ -113,14 +118,15 @@ pub async fn create_equipement(
     date_pose: String,
     date_depose: String,
     coordinates: Vec<(f64, f64)>, // Liste de (x, y) représentant la ligne
+    depot_id: Option<String>,
 ) -> Result<EquipementComplet, String> {
     let pool = get_db_pool(&app).await?;
     let equipement_id = Uuid::new_v4().to_string();
 
     // 1. Créer l'équipement
     sqlx::query(
-        "INSERT INTO equipement (id, event_id, type_id, quantity, length_per_unit, description, date_pose, date_depose) 
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+        "INSERT INTO equipement (id, event_id, type_id, quantity, length_per_unit, description, date_pose, date_depose, depot_id) 
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
     )
     .bind(&equipement_id)
     .bind(&event_id)
@@ -130,6 +136,7 @@ pub async fn create_equipement(
     .bind(&description)
     .bind(&date_pose)
     .bind(&date_depose)
+    .bind(&depot_id)
     .execute(&pool)
     .await
     .map_err(|e| e.to_string())?;
@@ -184,7 +191,10 @@ pub async fn create_equipement(
         hour_pose: None,
         date_depose: Some(date_depose.clone()),
         hour_depose: None,
+        depot_id,
         coordinates: coords,
+        parcours_id: None,
+        chainage_m: None,
     })
 }
 
@@ -197,11 +207,13 @@ pub async fn fetch_equipements_for_event(
 
     // Récupérer tous les équipements de l'événement avec le nom du type
     let rows = sqlx::query(
-        "SELECT e.id, e.type_id, e.quantity, e.length_per_unit, e.description, e.date_pose, e.date_depose,
+        "SELECT e.id, e.type_id, e.quantity, e.length_per_unit, e.description, e.date_pose, e.date_depose, e.depot_id,
+                e.parcours_id, e.chainage_m,
                 t.name as type_name, t.description as type_description
          FROM equipement e
          LEFT JOIN type t ON e.type_id = t.id
-         WHERE e.event_id = ?",
+         WHERE e.event_id = ?
+         ORDER BY e.chainage_m IS NULL, e.parcours_id, e.chainage_m, e.rowid",
     )
     .bind(&event_id)
     .fetch_all(&pool)
@@ -247,7 +259,10 @@ pub async fn fetch_equipements_for_event(
             hour_pose: None,
             date_depose: row.get("date_depose"),
             hour_depose: None,
+            depot_id: row.get("depot_id"),
             coordinates,
+            parcours_id: row.get("parcours_id"),
+            chainage_m: row.get("chainage_m"),
         });
     }
 
@@ -312,7 +327,9 @@ pub async fn fetch_actions(app: AppHandle, event_id: String) -> Result<Vec<Actio
     let pool = get_db_pool(&app).await?;
 
     let rows = sqlx::query(
-        "SELECT a.id, a.team_id, a.equipement_id, a.type, a.scheduled_time, a.is_done
+        "SELECT a.id, a.team_id, a.equipement_id, a.type, a.scheduled_time, a.is_done,
+                COALESCE(a.status, CASE WHEN a.is_done THEN 'done' ELSE 'planned' END) as status,
+                a.status_changed_at
          FROM action a
          JOIN equipement e ON a.equipement_id = e.id
          WHERE e.event_id = ?
@@ -332,6 +349,10 @@ pub async fn fetch_actions(app: AppHandle, event_id: String) -> Result<Vec<Actio
             r#type: row.get("type"),
             scheduled_time: row.get("scheduled_time"),
             is_done: row.get("is_done"),
+            status: row.get("status"),
+            status_changed_at: row.get("status_changed_at"),
+            depends_on: vec![],
+            blocked: false,
         })
         .collect();
 
@@ -380,9 +401,10 @@ pub async fn add_action(
         _ => date_depose, // date_depose par défaut
     };
 
-    // Utiliser INSERT OR REPLACE pour mettre à jour si l'action existe déjà
+    // Mise à jour en place si l'action existe déjà (un REPLACE supprimerait ses dépendances)
     sqlx::query(
-        "INSERT OR REPLACE INTO action (id, team_id, equipement_id, type, scheduled_time, is_done) VALUES (?, ?, ?, ?, ?, ?)",
+        "INSERT INTO action (id, team_id, equipement_id, type, scheduled_time, is_done) VALUES (?, ?, ?, ?, ?, ?)
+         ON CONFLICT(id) DO UPDATE SET team_id = excluded.team_id, scheduled_time = excluded.scheduled_time, is_done = excluded.is_done",
     )
     .bind(&action_id)
     .bind(&team_id)
@@ -512,9 +534,11 @@ pub async fn send_planning(team_id: String, app: AppHandle) -> Result<Planning,
         team_name, event_id
     );
 
-    let actions = sqlx::query_as::<_, Action>(
+    let mut actions = sqlx::query_as::<_, Action>(
         r#"
-        SELECT id, team_id, equipement_id, type as type, scheduled_time, is_done 
+        SELECT id, team_id, equipement_id, type as type, scheduled_time, is_done,
+               COALESCE(status, CASE WHEN is_done THEN 'done' ELSE 'planned' END) as status,
+               status_changed_at
         FROM action 
         WHERE team_id = ?
         "#,
@@ -532,6 +556,8 @@ pub async fn send_planning(team_id: String, app: AppHandle) -> Result<Planning,
         );
     }
 
+    let km_markers = load_km_markers(&pool, &event_id).await?;
+
     if actions.is_empty() {
         println!("[DB] ⚠️ Aucune action trouvée pour team_id: {}", team_id);
         return Ok(Planning {
@@ -539,6 +565,9 @@ pub async fn send_planning(team_id: String, app: AppHandle) -> Result<Planning,
             actions: vec![],
             equipements: vec![],
             coordonees: vec![],
+            itinerary: vec![],
+            passages: vec![],
+            km_markers,
         });
     }
 
@@ -594,11 +623,73 @@ pub async fn send_planning(team_id: String, app: AppHandle) -> Result<Planning,
     println!("[DB] 🚚 Équipements trouvés: {}", final_equipements.len());
     println!("[DB] 📍 Coordonnées totales: {}", coords.len());
 
+    // Dépendances : une action reste bloquée tant que ses prédécesseurs ne sont pas faits
+    let dependency_rows = sqlx::query(
+        r#"
+        SELECT d.action_id, d.depends_on_id, COALESCE(p.is_done, 0) as is_done
+        FROM action_dependency d
+        JOIN action a ON d.action_id = a.id
+        JOIN action p ON d.depends_on_id = p.id
+        WHERE a.team_id = ?
+        "#,
+    )
+    .bind(&team_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| format!("Erreur dépendances: {}", e))?;
+
+    let dependencies: Vec<ActionDependency> = dependency_rows
+        .iter()
+        .map(|row| ActionDependency {
+            id: String::new(),
+            action_id: row.get("action_id"),
+            depends_on_id: row.get("depends_on_id"),
+        })
+        .collect();
+    let done: HashSet<String> = dependency_rows
+        .iter()
+        .filter(|row| row.get::<bool, _>("is_done"))
+        .map(|row| row.get("depends_on_id"))
+        .collect();
+    let blocked = blocked_action_ids(&dependencies, &done);
+
+    for action in actions.iter_mut() {
+        action.depends_on = dependencies
+            .iter()
+            .filter(|d| d.action_id == action.id)
+            .map(|d| d.depends_on_id.clone())
+            .collect();
+        action.blocked = blocked.contains(&action.id);
+    }
+
+    // Les actions sont envoyées dans l'ordre de la tournée optimisée
+    let itinerary = compute_team_itinerary(&pool, &team_id, None, None, None).await?;
+    actions.sort_by_key(|a| {
+        itinerary
+            .stops
+            .iter()
+            .position(|s| s.action_id == a.id)
+            .unwrap_or(usize::MAX)
+    });
+
+    // Heures de passage de la course sur les équipements de l'équipe
+    let parcours = load_race_parcours(&pool, &event_id).await?;
+    let passages = final_equipements
+        .iter()
+        .filter_map(|eq| {
+            let points: Vec<(f64, f64)> = eq.coordinates.iter().map(|c| (c.x, c.y)).collect();
+            passage_time_on_parcours(&parcours, "equipement", Some(eq.id.clone()), &points)
+        })
+        .collect();
+
     let result = Planning {
         team,
         actions,
         equipements: final_equipements,
         coordonees: coords,
+        itinerary: itinerary.stops,
+        passages,
+        km_markers,
     };
 
     println!(
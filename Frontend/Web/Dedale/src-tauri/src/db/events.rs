@@ -210,7 +210,7 @@ pub async fn duplicate_event(
 
     // 2. Dupliquer les zones
     let zones = sqlx::query(
-        "SELECT id, name, color, description, geometry_json FROM zone WHERE event_id = ?",
+        "SELECT id, name, color, description, geometry_json, restricted FROM zone WHERE event_id = ?",
     )
     .bind(&source_event_id)
     .fetch_all(&pool)
@@ -223,14 +223,16 @@ pub async fn duplicate_event(
         let color: Option<String> = zone.get("color");
         let description: Option<String> = zone.get("description");
         let geometry_json: Option<String> = zone.get("geometry_json");
+        let restricted: bool = zone.get("restricted");
 
-        sqlx::query("INSERT INTO zone (id, event_id, name, color, description, geometry_json) VALUES (?, ?, ?, ?, ?, ?)")
+        sqlx::query("INSERT INTO zone (id, event_id, name, color, description, geometry_json, restricted) VALUES (?, ?, ?, ?, ?, ?, ?)")
             .bind(&new_zone_id)
             .bind(&new_event_id)
             .bind(&name)
             .bind(&color)
             .bind(&description)
             .bind(&geometry_json)
+            .bind(restricted)
             .execute(&pool)
             .await
             .map_err(|e| format!("Failed to duplicate zone: {}", e))?;
@@ -265,6 +267,34 @@ pub async fn duplicate_event(
             .execute(&pool)
             .await
             .map_err(|e| format!("Failed to duplicate parcours: {}", e))?;
+
+        // Vagues de départ du parcours
+        let source_parcours_id: String = p.get("id");
+        let waves = sqlx::query(
+            "SELECT name, start_time, speed_low, speed_high FROM wave WHERE parcours_id = ?",
+        )
+        .bind(&source_parcours_id)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| format!("Failed to fetch waves: {}", e))?;
+
+        for w in waves {
+            let name: Option<String> = w.get("name");
+            let start_time: Option<i64> = w.get("start_time");
+            let speed_low: Option<f64> = w.get("speed_low");
+            let speed_high: Option<f64> = w.get("speed_high");
+
+            sqlx::query("INSERT INTO wave (id, parcours_id, name, start_time, speed_low, speed_high) VALUES (?, ?, ?, ?, ?, ?)")
+                .bind(Uuid::new_v4().to_string())
+                .bind(&new_parcours_id)
+                .bind(&name)
+                .bind(start_time)
+                .bind(speed_low)
+                .bind(speed_high)
+                .execute(&pool)
+                .await
+                .map_err(|e| format!("Failed to duplicate wave: {}", e))?;
+        }
     }
     println!("[DB] ✅ Parcours dupliqués");
 
@@ -359,7 +389,7 @@ pub async fn duplicate_event(
     println!("[DB] ✅ Équipes dupliquées");
 
     // 6. Dupliquer les équipements
-    let equipements = sqlx::query("SELECT id, type_id, length_per_unit, quantity, description, date_pose, date_depose FROM equipement WHERE event_id = ?")
+    let equipements = sqlx::query("SELECT id, type_id, length_per_unit, quantity, description, date_pose, date_depose, depot_id FROM equipement WHERE event_id = ?")
         .bind(&source_event_id)
         .fetch_all(&pool)
         .await
@@ -374,8 +404,9 @@ pub async fn duplicate_event(
         let description: Option<String> = equip.get("description");
         let date_pose: Option<String> = equip.get("date_pose");
         let date_depose: Option<String> = equip.get("date_depose");
+        let depot_id: Option<String> = equip.get("depot_id");
 
-        let _ = sqlx::query("INSERT INTO equipement (id, event_id, type_id, length_per_unit, quantity, description, date_pose, date_depose) VALUES (?, ?, ?, ?, ?, ?, ?, ?)")
+        let _ = sqlx::query("INSERT INTO equipement (id, event_id, type_id, length_per_unit, quantity, description, date_pose, date_depose, depot_id) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)")
             .bind(&new_equip_id)
             .bind(&new_event_id)
             .bind(&type_id)
@@ -384,6 +415,7 @@ pub async fn duplicate_event(
             .bind(&description)
             .bind(&date_pose)
             .bind(&date_depose)
+            .bind(&depot_id)
             .execute(&pool)
             .await; // Ignorer les erreurs
 
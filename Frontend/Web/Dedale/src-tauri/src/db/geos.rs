@@ -1,4 +1,5 @@
 use crate::db::get_db_pool;
+use crate::snapping::{refresh_parcours_chainages, refresh_point_chainage};
 use crate::types::*;
 use sqlx::Row;
 use tauri::AppHandle;
@@ -95,7 +96,7 @@ pub async fn fetch_geometries_for_event(
 pub async fn fetch_zones_for_event(app: AppHandle, event_id: String) -> Result<Vec<Zone>, String> {
     let pool = get_db_pool(&app).await?;
     let rows = sqlx::query(
-        "SELECT id, event_id, name, color, description, geometry_json FROM zone WHERE event_id = ?",
+        "SELECT id, event_id, name, color, description, geometry_json, restricted FROM zone WHERE event_id = ?",
     )
     .bind(&event_id)
     .fetch_all(&pool)
@@ -111,6 +112,7 @@ pub async fn fetch_zones_for_event(app: AppHandle, event_id: String) -> Result<V
             color: row.get("color"),
             description: row.get("description"),
             geometry_json: row.get("geometry_json"),
+            restricted: row.get("restricted"),
         })
         .collect();
 
@@ -165,11 +167,13 @@ pub async fn create_zone(
     name: String,
     color: String,
     description: Option<String>,
+    restricted: Option<bool>,
 ) -> Result<Zone, String> {
     let pool = get_db_pool(&app).await?;
     let uuid = Uuid::new_v4().to_string();
+    let restricted = restricted.unwrap_or(false);
     let _result = sqlx::query(
-        "INSERT INTO zone (id, event_id, geometry_json, name, color, description) VALUES (?, ?, ?, ?, ?, ?)",
+        "INSERT INTO zone (id, event_id, geometry_json, name, color, description, restricted) VALUES (?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(&uuid)
     .bind(&event_id)
@@ -177,6 +181,7 @@ pub async fn create_zone(
     .bind(&name)
     .bind(&color)
     .bind(&description)
+    .bind(restricted)
     .execute(&pool)
     .await
     .map_err(|e| e.to_string())?;
@@ -188,6 +193,7 @@ pub async fn create_zone(
         color: Some(color),
         description,
         geometry_json: Some(geom),
+        restricted,
     })
 }
 #[tauri::command]
@@ -332,6 +338,7 @@ pub async fn update_geometry(
                     .execute(&pool)
                     .await
                     .map_err(|e| e.to_string())?;
+                refresh_point_chainage(&pool, &geometry_id).await?;
             }
         }
 
@@ -361,6 +368,7 @@ pub async fn update_geometry(
             .execute(&pool)
             .await
             .map_err(|e| e.to_string())?;
+        refresh_parcours_chainages(&pool, &event_id, &geometry_id).await?;
 
         println!("[DB] ✏️ Parcours {} mis à jour", geometry_id);
         return Ok(Geometry {
@@ -410,24 +418,28 @@ pub async fn update_zone(
     name: String,
     color: String,
     description: Option<String>,
+    restricted: Option<bool>,
 ) -> Result<Zone, String> {
     let pool = get_db_pool(&app).await?;
 
-    let row = sqlx::query("SELECT event_id FROM zone WHERE id = ?")
+    let row = sqlx::query("SELECT event_id, restricted FROM zone WHERE id = ?")
         .bind(&geometry_id)
         .fetch_one(&pool)
         .await
         .map_err(|e| format!("Géométrie non trouvée: {}", e))?;
 
     let event_id: String = row.get("event_id");
+    // Sans valeur transmise, la zone garde son statut
+    let restricted = restricted.unwrap_or_else(|| row.get("restricted"));
 
     sqlx::query(
-        "UPDATE zone SET geometry_json = ?, name = ?, color = ?, description = ? WHERE id = ?",
+        "UPDATE zone SET geometry_json = ?, name = ?, color = ?, description = ?, restricted = ? WHERE id = ?",
     )
     .bind(&geom)
     .bind(&name)
     .bind(&color)
     .bind(&description)
+    .bind(restricted)
     .bind(&geometry_id)
     .execute(&pool)
     .await
@@ -442,6 +454,7 @@ pub async fn update_zone(
         color: Some(color),
         description,
         geometry_json: Some(geom),
+        restricted,
     })
 }
 
@@ -480,6 +493,7 @@ pub async fn update_parcours(
         .execute(&pool)
         .await
         .map_err(|e| e.to_string())?;
+    refresh_parcours_chainages(&pool, &event_id, &geometry_id).await?;
 
     println!("[DB] Parcours {} mis à jour", geometry_id);
 
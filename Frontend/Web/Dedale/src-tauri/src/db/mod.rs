@@ -5,6 +5,9 @@ use std::fs;
 use std::str::FromStr;
 use tauri::{AppHandle, Manager};
 
+pub mod action_status;
+pub mod dependencies;
+pub mod depots;
 pub mod equipements;
 pub mod events;
 pub mod geos;
@@ -12,6 +15,10 @@ pub mod persons;
 pub mod planning;
 pub mod points;
 pub mod teams;
+pub mod waves;
+pub use action_status::*;
+pub use dependencies::*;
+pub use depots::*;
 pub use equipements::*;
 pub use events::*;
 pub use geos::*;
@@ -19,6 +26,7 @@ pub use persons::*;
 pub use planning::*;
 pub use points::*;
 pub use teams::*;
+pub use waves::*;
 // Réexporter les types depuis le module types
 pub use crate::types::*;
 
@@ -100,6 +108,22 @@ pub async fn get_db_pool(app: &AppHandle) -> Result<SqlitePool, String> {
     .await
     .map_err(|e| format!("Error creating parcours: {}", e))?;
 
+    // Vagues de départ d'un parcours (élite, masse, relais...)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS wave (
+            id CHAR(36) PRIMARY KEY,
+            parcours_id CHAR(36) NOT NULL,
+            name TEXT,
+            start_time DATETIME,
+            speed_low REAL,
+            speed_high REAL,
+            FOREIGN KEY (parcours_id) REFERENCES parcours (id) ON DELETE CASCADE
+        )",
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| format!("Error creating wave: {}", e))?;
+
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS zone (
             id CHAR(36) PRIMARY KEY,
@@ -172,6 +196,35 @@ pub async fn get_db_pool(app: &AppHandle) -> Result<SqlitePool, String> {
     .await
     .map_err(|e| format!("Error creating type: {}", e))?;
 
+    // --- DÉPÔTS (origine du matériel) ---
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS depot (
+            id CHAR(36) PRIMARY KEY,
+            name TEXT,
+            address TEXT,
+            x REAL NOT NULL,
+            y REAL NOT NULL
+        )",
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| format!("Error creating depot: {}", e))?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS depot_stock (
+            id CHAR(36) PRIMARY KEY,
+            depot_id CHAR(36) NOT NULL,
+            type_id CHAR(36) NOT NULL,
+            quantity INTEGER NOT NULL DEFAULT 0,
+            FOREIGN KEY (depot_id) REFERENCES depot (id) ON DELETE CASCADE,
+            FOREIGN KEY (type_id) REFERENCES type (id) ON DELETE CASCADE,
+            UNIQUE(depot_id, type_id)
+        )",
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| format!("Error creating depot_stock: {}", e))?;
+
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS equipement (
             id CHAR(36) PRIMARY KEY,
@@ -182,8 +235,10 @@ pub async fn get_db_pool(app: &AppHandle) -> Result<SqlitePool, String> {
             description TEXT,
             date_pose DATETIME,
             date_depose DATETIME,
+            depot_id CHAR(36),
             FOREIGN KEY (event_id) REFERENCES event (id) ON DELETE CASCADE,
-            FOREIGN KEY (type_id) REFERENCES type (id)
+            FOREIGN KEY (type_id) REFERENCES type (id),
+            FOREIGN KEY (depot_id) REFERENCES depot (id) ON DELETE SET NULL
         )",
     )
     .execute(&pool)
@@ -268,9 +323,118 @@ pub async fn get_db_pool(app: &AppHandle) -> Result<SqlitePool, String> {
         .execute(&pool)
         .await; // Ignore l'erreur si la colonne existe déjà
 
+    let _ = sqlx::query("ALTER TABLE zone ADD COLUMN restricted BOOLEAN NOT NULL DEFAULT 0")
+        .execute(&pool)
+        .await; // Ignore l'erreur si la colonne existe déjà
+
     let _ = sqlx::query("ALTER TABLE equipement ADD COLUMN description TEXT")
         .execute(&pool)
         .await; // Ignore l'erreur si la colonne existe déjà
+
+    let _ = sqlx::query(
+        "ALTER TABLE equipement ADD COLUMN depot_id CHAR(36) REFERENCES depot (id) ON DELETE SET NULL",
+    )
+    .execute(&pool)
+    .await; // Ignore l'erreur si la colonne existe déjà
+
+    let _ = sqlx::query(
+        "ALTER TABLE interest ADD COLUMN parcours_id CHAR(36) REFERENCES parcours (id) ON DELETE CASCADE",
+    )
+    .execute(&pool)
+    .await; // Ignore l'erreur si la colonne existe déjà
+
+    let _ = sqlx::query("ALTER TABLE interest ADD COLUMN marker_km REAL")
+        .execute(&pool)
+        .await; // Ignore l'erreur si la colonne existe déjà
+
+    let _ = sqlx::query("ALTER TABLE interest ADD COLUMN bearing REAL")
+        .execute(&pool)
+        .await; // Ignore l'erreur si la colonne existe déjà
+
+    // Accrochage au parcours : position kilométrique des points et équipements
+    for table in ["point", "equipement"] {
+        let _ = sqlx::query(&format!(
+            "ALTER TABLE {} ADD COLUMN parcours_id CHAR(36) REFERENCES parcours (id) ON DELETE SET NULL",
+            table
+        ))
+        .execute(&pool)
+        .await; // Ignore l'erreur si la colonne existe déjà
+
+        let _ = sqlx::query(&format!("ALTER TABLE {} ADD COLUMN chainage_m REAL", table))
+            .execute(&pool)
+            .await; // Ignore l'erreur si la colonne existe déjà
+    }
+
+    let _ = sqlx::query("ALTER TABLE action ADD COLUMN status TEXT")
+        .execute(&pool)
+        .await; // Ignore l'erreur si la colonne existe déjà
+
+    let _ = sqlx::query("ALTER TABLE action ADD COLUMN status_changed_at TEXT")
+        .execute(&pool)
+        .await; // Ignore l'erreur si la colonne existe déjà
+
+    // Révision des actions (SEQUENCE / LAST-MODIFIED de l'export iCalendar)
+    let _ = sqlx::query("ALTER TABLE action ADD COLUMN updated_at TEXT")
+        .execute(&pool)
+        .await; // Ignore l'erreur si la colonne existe déjà
+
+    let _ = sqlx::query("ALTER TABLE action ADD COLUMN sequence INTEGER NOT NULL DEFAULT 0")
+        .execute(&pool)
+        .await; // Ignore l'erreur si la colonne existe déjà
+
+    sqlx::query(
+        "UPDATE action SET updated_at = status_changed_at
+         WHERE updated_at IS NULL AND status_changed_at IS NOT NULL",
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| format!("Error migrating action updated_at: {}", e))?;
+
+    // Toute modification effective d'une action (quel que soit l'écran ou la synchro mobile)
+    // incrémente sa révision
+    sqlx::query(
+        "CREATE TRIGGER IF NOT EXISTS trg_action_revision
+         AFTER UPDATE OF team_id, equipement_id, type, scheduled_time, is_done, status ON action
+         WHEN OLD.team_id IS NOT NEW.team_id
+           OR OLD.equipement_id IS NOT NEW.equipement_id
+           OR OLD.type IS NOT NEW.type
+           OR OLD.scheduled_time IS NOT NEW.scheduled_time
+           OR OLD.is_done IS NOT NEW.is_done
+           OR OLD.status IS NOT NEW.status
+         BEGIN
+           UPDATE action
+           SET sequence = COALESCE(OLD.sequence, 0) + 1,
+               updated_at = strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')
+           WHERE id = NEW.id;
+         END",
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| format!("Error creating action revision trigger: {}", e))?;
+
+    // Dernier contenu exporté de chaque action en iCalendar : une durée estimée recalculée
+    // change le VEVENT sans modifier l'action, sa révision est suivie ici
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS ical_export_state (
+            action_id CHAR(36) PRIMARY KEY,
+            content TEXT NOT NULL,
+            revision INTEGER NOT NULL DEFAULT 0,
+            modified_at TEXT,
+            FOREIGN KEY (action_id) REFERENCES action (id) ON DELETE CASCADE
+        )",
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| format!("Error creating ical_export_state table: {}", e))?;
+
+    // Les actions existantes reprennent leur statut depuis is_done
+    sqlx::query(
+        "UPDATE action SET status = CASE WHEN is_done THEN 'done' ELSE 'planned' END
+         WHERE status IS NULL",
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| format!("Error migrating action status: {}", e))?;
     sqlx::query(
         "CREATE UNIQUE INDEX IF NOT EXISTS idx_unique_action_per_equipement 
          ON action (equipement_id, type);",
@@ -279,6 +443,68 @@ pub async fn get_db_pool(app: &AppHandle) -> Result<SqlitePool, String> {
     .await
     .map_err(|e| format!("Error creating unique action index: {}", e))?;
 
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS action_dependency (
+            id CHAR(36) PRIMARY KEY,
+            action_id CHAR(36) NOT NULL,
+            depends_on_id CHAR(36) NOT NULL,
+            FOREIGN KEY (action_id) REFERENCES action (id) ON DELETE CASCADE,
+            FOREIGN KEY (depends_on_id) REFERENCES action (id) ON DELETE CASCADE,
+            UNIQUE(action_id, depends_on_id)
+        )",
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| format!("Error creating action_dependency: {}", e))?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS action_status_change (
+            id CHAR(36) PRIMARY KEY,
+            action_id CHAR(36) NOT NULL,
+            previous_status TEXT,
+            status TEXT NOT NULL,
+            changed_at TEXT NOT NULL,
+            person_id CHAR(36),
+            note TEXT,
+            FOREIGN KEY (action_id) REFERENCES action (id) ON DELETE CASCADE,
+            FOREIGN KEY (person_id) REFERENCES person (id) ON DELETE SET NULL
+        )",
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| format!("Error creating action_status_change: {}", e))?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS action_status_picture (
+            id INTEGER PRIMARY KEY,
+            status_change_id CHAR(36) NOT NULL,
+            image_data TEXT,
+            FOREIGN KEY (status_change_id) REFERENCES action_status_change (id) ON DELETE CASCADE
+        )",
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| format!("Error creating action_status_picture: {}", e))?;
+
+    // --- CADENCES (durée estimée des actions par type d'équipement) ---
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS productivity_rate (
+            type_id CHAR(36) NOT NULL,
+            action_type TEXT NOT NULL,
+            base_minutes REAL NOT NULL DEFAULT 0,
+            minutes_per_unit REAL NOT NULL DEFAULT 0,
+            minutes_per_metre REAL NOT NULL DEFAULT 0,
+            reference_team_size INTEGER NOT NULL DEFAULT 2,
+            calibration_factor REAL NOT NULL DEFAULT 1,
+            sample_count INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (type_id, action_type),
+            FOREIGN KEY (type_id) REFERENCES type (id) ON DELETE CASCADE
+        )",
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| format!("Error creating productivity_rate: {}", e))?;
+
     println!("[DB] Toutes les tables ont été synchronisées avec le diagramme ER.");
 
     Ok(pool)
@@ -1,11 +1,30 @@
-use sqlx::Row;
+use chrono::{Duration, Local, NaiveDateTime};
+use sqlx::{Row, Sqlite, SqlitePool, Transaction};
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::Write;
 use tauri::AppHandle;
-use tauri::Manager;
+use uuid::Uuid;
 
+use crate::conflicts::{detect_conflicts, PlanningConflict, ScheduledAction};
+use crate::db::action_status::{normalize_changed_at, record_status_change, toggled_status};
+use crate::db::dependencies::{
+    check_precedence, fetch_event_dependencies, fetch_team_dependencies,
+};
 use crate::db::get_db_pool;
+use crate::durations::{
+    duration_of, estimate_minutes, load_action_durations, load_productivity_rates, load_team_sizes,
+};
+use crate::itinerary::{build_itinerary, ItineraryInput, TeamItinerary};
+use crate::progress::emit_action_progress;
+use crate::race::{
+    check_race_window, load_equipement_points, load_race_parcours, RaceViolation,
+    TeamRaceViolations, PARCOURS_TOLERANCE_METERS,
+};
+use crate::scheduling::{
+    solve_planning, PlanningProposal, ProposedAction, SolverOptions, SolverTask, SolverTeam,
+    DEFAULT_TRAVEL_SPEED_KMH,
+};
+use crate::types::{ActionStatus, DependencyViolation};
+use crate::utils;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(crate = "serde")]
@@ -25,6 +44,8 @@ pub struct PlanningAction {
     pub action_type: String,
     pub scheduled_time: String,
     pub is_done: bool,
+    pub status: String,
+    pub status_changed_at: Option<String>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
@@ -90,7 +111,9 @@ pub async fn fetch_teams_with_actions_for_event(
             a.equipement_id,
             a.type,
             a.scheduled_time,
-            a.is_done
+            a.is_done,
+            COALESCE(a.status, CASE WHEN a.is_done THEN 'done' ELSE 'planned' END) as status,
+            a.status_changed_at
         FROM team t
         JOIN team te ON te.id = t.id
         JOIN event e ON e.id = te.event_id
@@ -133,6 +156,8 @@ pub async fn fetch_teams_with_actions_for_event(
                 action_type: row.get("type"),
                 scheduled_time: row.get("scheduled_time"),
                 is_done: row.get("is_done"),
+                status: row.get("status"),
+                status_changed_at: row.get("status_changed_at"),
             });
         }
     }
@@ -159,7 +184,9 @@ pub async fn fetch_actions_for_team(
             a.equipement_id,
             a.type,
             a.scheduled_time,
-            a.is_done
+            a.is_done,
+            COALESCE(a.status, CASE WHEN a.is_done THEN 'done' ELSE 'planned' END) as status,
+            a.status_changed_at
         FROM action a
         WHERE a.team_id = ?
         ORDER BY a.is_done ASC, a.scheduled_time ASC
@@ -180,6 +207,8 @@ pub async fn fetch_actions_for_team(
             action_type: row.get("type"),
             scheduled_time: row.get("scheduled_time"),
             is_done: row.get("is_done"),
+            status: row.get("status"),
+            status_changed_at: row.get("status_changed_at"),
         })
         .collect();
 
@@ -201,7 +230,9 @@ pub async fn fetch_actions_for_equipement(
             a.equipement_id,
             a.type,
             COALESCE(a.scheduled_time, '') as scheduled_time,
-            COALESCE(a.is_done, 0) as is_done
+            COALESCE(a.is_done, 0) as is_done,
+            COALESCE(a.status, CASE WHEN a.is_done THEN 'done' ELSE 'planned' END) as status,
+            a.status_changed_at
         FROM action a
         WHERE a.equipement_id = ?
         ORDER BY a.type ASC
@@ -222,230 +253,735 @@ pub async fn fetch_actions_for_equipement(
             action_type: row.get("type"),
             scheduled_time: row.get("scheduled_time"),
             is_done: row.get("is_done"),
+            status: row.get("status"),
+            status_changed_at: row.get("status_changed_at"),
         })
         .collect();
 
     Ok(actions)
 }
 
-/// Met à jour le statut d'une action
+/// Bascule une action entre « planifiée » et « terminée » (case à cocher du planning).
+/// Une action annulée est refusée avec un message explicite (voir `toggled_status`).
 #[tauri::command]
 pub async fn update_action_status(app: AppHandle, action_id: String) -> Result<(), String> {
     let pool = get_db_pool(&app).await?;
 
-    // Basculer le statut is_done
-    let query = r#"
-        UPDATE action
-        SET is_done = NOT is_done
-        WHERE id = ?
-    "#;
+    let current: Option<String> = sqlx::query_scalar(
+        "SELECT COALESCE(status, CASE WHEN is_done THEN 'done' ELSE 'planned' END) FROM action WHERE id = ?",
+    )
+    .bind(&action_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| e.to_string())?;
 
-    sqlx::query(query)
-        .bind(&action_id)
-        .execute(&pool)
-        .await
-        .map_err(|e| e.to_string())?;
+    let current = current.ok_or_else(|| format!("Action {} introuvable", action_id))?;
+    let status = toggled_status(ActionStatus::parse(&current).unwrap_or(ActionStatus::Planned))?;
+
+    let changed_at = normalize_changed_at(None, Local::now().naive_local());
+    record_status_change(&pool, &action_id, status, None, None, vec![], changed_at).await?;
+    emit_action_progress(&app, &pool, &action_id).await;
 
     Ok(())
 }
 
-/// Exporte le planning en Excel (CSV)
+/// Génère une proposition de planning (pose et dépose) pour un événement.
+/// Rien n'est écrit en base : la proposition doit être validée via `apply_planning_proposal`.
 #[tauri::command]
-pub async fn export_planning_excel(
+pub async fn generate_planning_proposal(
     app: AppHandle,
-    _db_url: String,
-    excel_path_str: String,
-    event_id: Option<String>,
-) -> Result<(), String> {
+    event_id: String,
+    options: Option<SolverOptions>,
+) -> Result<PlanningProposal, String> {
     let pool = get_db_pool(&app).await?;
+    let options = options.unwrap_or_default();
+    let mut warnings = Vec::new();
 
-    // Récupérer les équipes et actions
-    let teams_query = if let Some(ref eid) = event_id {
-        format!(
-            r#"
-            SELECT DISTINCT t.id, t.name
-            FROM team t
-            JOIN team te ON te.team_id = t.id
-            WHERE te.event_id = '{}'
-            ORDER BY t.name ASC
-        "#,
-            eid
-        )
-    } else {
-        r#"
-            SELECT DISTINCT t.id, t.name
-            FROM team t
-            ORDER BY t.name ASC
-        "#
-        .to_string()
-    };
-
-    let team_rows = sqlx::query_as::<_, (String, String)>(&teams_query)
+    let team_rows = sqlx::query("SELECT id, name FROM team WHERE event_id = ? ORDER BY name ASC")
+        .bind(&event_id)
         .fetch_all(&pool)
         .await
         .map_err(|e| e.to_string())?;
+    let team_sizes = load_team_sizes(&pool, &event_id).await?;
 
-    // Créer un fichier CSV
-    let mut file = File::create(&excel_path_str).map_err(|e| e.to_string())?;
+    let teams: Vec<SolverTeam> = team_rows
+        .into_iter()
+        .map(|row| {
+            let id: String = row.get("id");
+            SolverTeam {
+                size: team_sizes.get(&id).copied().unwrap_or(0),
+                id,
+                name: row.get("name"),
+            }
+        })
+        .collect();
 
-    // En-tête
-    writeln!(file, "Team,Action Type,Equipment ID,Scheduled Time,Status")
-        .map_err(|e| e.to_string())?;
+    if teams.is_empty() {
+        return Err("Aucune équipe n'est affectée à cet événement".to_string());
+    }
 
-    for (_team_id, team_name) in team_rows {
-        let actions_query = r#"
-            SELECT 
-                type,
-                equipement_id,
-                scheduled_time,
-                is_done
-            FROM action
-            WHERE team_id = ?
-            ORDER BY scheduled_time ASC
-        "#;
-
-        let action_rows = sqlx::query_as::<_, (String, String, String, bool)>(actions_query)
-            .bind(&_team_id)
-            .fetch_all(&pool)
-            .await
-            .map_err(|e| e.to_string())?;
+    let equipement_rows = sqlx::query(
+        r#"
+        SELECT
+            e.id, e.date_pose, e.date_depose, e.type_id,
+            COALESCE(e.quantity, 0) as quantity,
+            COALESCE(e.quantity, 0) * COALESCE(e.length_per_unit, 0) as length_m,
+            d.x as depot_x, d.y as depot_y,
+            (SELECT c.x FROM equipement_coordinate c WHERE c.equipement_id = e.id
+             ORDER BY c.order_index ASC LIMIT 1) as x,
+            (SELECT c.y FROM equipement_coordinate c WHERE c.equipement_id = e.id
+             ORDER BY c.order_index ASC LIMIT 1) as y
+        FROM equipement e
+        LEFT JOIN depot d ON d.id = e.depot_id
+        WHERE e.event_id = ?
+    "#,
+    )
+    .bind(&event_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    // Actions existantes : on réutilise leur ID et on ne replanifie pas celles terminées
+    // ou annulées
+    let existing_rows = sqlx::query(
+        r#"
+        SELECT a.id, a.equipement_id, a.type,
+               COALESCE(a.status, CASE WHEN a.is_done THEN 'done' ELSE 'planned' END) as status
+        FROM action a
+        JOIN equipement e ON a.equipement_id = e.id
+        WHERE e.event_id = ?
+    "#,
+    )
+    .bind(&event_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    // Les déposes peuvent être enregistrées en "retrait" : la clé utilise la nature de l'action.
+    // Si plusieurs actions ont la même nature, une action terminée ou annulée l'emporte.
+    let mut existing: HashMap<(String, String), (String, bool)> = HashMap::new();
+    for row in existing_rows {
+        let action_type: String = row.get("type");
+        let key = (
+            row.get::<String, _>("equipement_id"),
+            utils::action_kind(&action_type).to_string(),
+        );
+        let status: String = row.get("status");
+        let closed = ActionStatus::parse(&status).is_some_and(|s| s.is_closed());
+        let value: (String, bool) = (row.get("id"), closed);
+        match existing.get(&key) {
+            Some((_, true)) => {}
+            _ => {
+                existing.insert(key, value);
+            }
+        }
+    }
 
-        for (action_type, equipement_id, scheduled_time, is_done) in action_rows {
-            let status = if is_done { "Done" } else { "Pending" };
-            writeln!(
-                file,
-                "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"",
-                team_name, action_type, equipement_id, scheduled_time, status
-            )
-            .map_err(|e| e.to_string())?;
+    let rates = load_productivity_rates(&pool).await?;
+
+    let mut tasks = Vec::new();
+    for row in equipement_rows {
+        let equipement_id: String = row.get("id");
+        let type_id: Option<String> = row.get("type_id");
+        let quantity = row.get::<i64, _>("quantity") as f64;
+        let length_m = row.get::<i64, _>("length_m") as f64;
+        let x: Option<f64> = row.get("x");
+        let y: Option<f64> = row.get("y");
+        let depot_x: Option<f64> = row.get("depot_x");
+        let depot_y: Option<f64> = row.get("depot_y");
+
+        for (action_type, column) in [("pose", "date_pose"), ("depose", "date_depose")] {
+            let existing_action = existing.get(&(equipement_id.clone(), action_type.to_string()));
+            if let Some((_, true)) = existing_action {
+                continue;
+            }
+
+            let date: Option<String> = row.get(column);
+            let Some(reference_time) = date.as_deref().and_then(utils::parse_datetime) else {
+                warnings.push(format!(
+                    "Équipement {} : {} sans date, action non planifiée",
+                    equipement_id, column
+                ));
+                continue;
+            };
+
+            // Durée pour l'équipe de référence ; le solveur l'adapte à l'équipe retenue
+            let rate = type_id
+                .as_ref()
+                .and_then(|t| rates.get(&(t.clone(), action_type.to_string())));
+            let (duration_minutes, reference_team_size) = match rate {
+                Some(rate) => (
+                    estimate_minutes(Some(rate), quantity, length_m, rate.reference_team_size),
+                    rate.reference_team_size,
+                ),
+                None => (options.action_duration_minutes, 0),
+            };
+
+            tasks.push(SolverTask {
+                action_id: existing_action.map(|(id, _)| id.clone()),
+                equipement_id: equipement_id.clone(),
+                action_type: action_type.to_string(),
+                reference_time,
+                position: x.zip(y),
+                origin: depot_x.zip(depot_y),
+                duration_minutes,
+                reference_team_size,
+            });
         }
     }
 
-    Ok(())
+    let (actions, team_loads) = solve_planning(&tasks, &teams, &options);
+
+    let violations = proposal_dependency_violations(&pool, &event_id, &actions).await?;
+    warnings.extend(violations.into_iter().map(|v| v.message));
+
+    let late_count = actions.iter().filter(|a| a.is_late).count();
+    if late_count > 0 {
+        warnings.push(format!(
+            "{} action(s) ne tiennent pas dans leur fenêtre horaire",
+            late_count
+        ));
+    }
+
+    println!(
+        "[DB] 🧮 Proposition de planning pour l'événement {} : {} action(s), {} équipe(s)",
+        event_id,
+        actions.len(),
+        team_loads.len()
+    );
+
+    Ok(PlanningProposal {
+        event_id,
+        actions,
+        team_loads,
+        warnings,
+    })
 }
 
-/// Crée un PDF du planning
+/// Enregistre une proposition de planning (éventuellement modifiée) dans la table action
 #[tauri::command]
-pub async fn create_planning_pdf(app: AppHandle, event_id: Option<String>) -> Result<(), String> {
+pub async fn apply_planning_proposal(
+    app: AppHandle,
+    proposal: PlanningProposal,
+) -> Result<usize, String> {
     let pool = get_db_pool(&app).await?;
 
-    // Récupérer les équipes et actions
-    let teams_query = if let Some(ref eid) = event_id {
-        format!(
-            r#"
-            SELECT DISTINCT t.id, t.name
-            FROM team t
-            JOIN team te ON te.id = t.id
-            WHERE te.event_id = '{}'
-            ORDER BY t.name ASC
-        "#,
-            eid
-        )
-    } else {
-        r#"
-            SELECT DISTINCT t.id, t.name
-            FROM team t
-            ORDER BY t.name ASC
-        "#
-        .to_string()
-    };
-
-    let team_rows = sqlx::query_as::<_, (String, String)>(&teams_query)
+    let team_ids: Vec<String> = sqlx::query_scalar("SELECT id FROM team WHERE event_id = ?")
+        .bind(&proposal.event_id)
         .fetch_all(&pool)
         .await
         .map_err(|e| e.to_string())?;
-
-    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
-
-    let pdf_path = app_data.join("planning.pdf");
-
-    // Créer un document PDF simple
-    let mut content = String::new();
-    content.push_str("%PDF-1.4\n");
-    content.push_str("1 0 obj\n");
-    content.push_str("<<\n");
-    content.push_str("/Type /Catalog\n");
-    content.push_str("/Pages 2 0 R\n");
-    content.push_str(">>\n");
-    content.push_str("endobj\n");
-    content.push_str("2 0 obj\n");
-    content.push_str("<<\n");
-    content.push_str("/Type /Pages\n");
-    content.push_str("/Kids [3 0 R]\n");
-    content.push_str("/Count 1\n");
-    content.push_str(">>\n");
-    content.push_str("endobj\n");
-    content.push_str("3 0 obj\n");
-    content.push_str("<<\n");
-    content.push_str("/Type /Page\n");
-    content.push_str("/Parent 2 0 R\n");
-    content.push_str("/MediaBox [0 0 612 792]\n");
-    content.push_str("/Contents 4 0 R\n");
-    content.push_str(">>\n");
-    content.push_str("endobj\n");
-    content.push_str("4 0 obj\n");
-    content.push_str("<<\n");
-    content.push_str("/Length 500\n");
-    content.push_str(">>\n");
-    content.push_str("stream\n");
-    content.push_str("BT\n");
-    content.push_str("/F1 24 Tf\n");
-    content.push_str("50 750 Td\n");
-    content.push_str("(Planning) Tj\n");
-    content.push_str("0 -30 Td\n");
-
-    #[allow(unused_variables, unused_assignments)]
-    for (_team_id, team_name) in team_rows {
-        content.push_str(&format!("({}) Tj\n", team_name));
-        content.push_str("0 -15 Td\n");
-        let actions_query = r#"
-            SELECT 
-                type,
-                equipement_id,
-                scheduled_time,
-                is_done
-            FROM action
-            WHERE team_id = ?
-            ORDER BY scheduled_time ASC
-        "#;
-
-        let action_rows = sqlx::query_as::<_, (String, String, String, bool)>(actions_query)
-            .bind(&_team_id)
+    let equipement_ids: Vec<String> =
+        sqlx::query_scalar("SELECT id FROM equipement WHERE event_id = ?")
+            .bind(&proposal.event_id)
             .fetch_all(&pool)
             .await
             .map_err(|e| e.to_string())?;
 
-        for (action_type, _equipement_id, _scheduled_time, is_done) in action_rows {
-            let status = if is_done { "[Done]" } else { "[Pending]" };
-            content.push_str(&format!("  - {} {}) Tj\n", action_type, status));
-            content.push_str("0 -10 Td\n");
+    // Validation complète avant toute écriture
+    for action in &proposal.actions {
+        if !equipement_ids.contains(&action.equipement_id) {
+            return Err(format!(
+                "L'équipement {} n'appartient pas à l'événement",
+                action.equipement_id
+            ));
+        }
+        if !team_ids.contains(&action.team_id) {
+            return Err(format!(
+                "L'équipe {} n'appartient pas à l'événement",
+                action.team_id
+            ));
         }
+        if utils::parse_datetime(&action.scheduled_time).is_none() {
+            return Err(format!(
+                "Horaire invalide pour l'équipement {} : {}",
+                action.equipement_id, action.scheduled_time
+            ));
+        }
+    }
 
-        content.push_str("0 -10 Td\n");
+    let violations =
+        proposal_dependency_violations(&pool, &proposal.event_id, &proposal.actions).await?;
+    if let Some(violation) = violations.first() {
+        return Err(format!(
+            "Dépendance non respectée ({} au total) : {}",
+            violations.len(),
+            violation.message
+        ));
     }
 
-    content.push_str("ET\n");
-    content.push_str("endstream\n");
-    content.push_str("endobj\n");
-    content.push_str("xref\n");
-    content.push_str("0 5\n");
-    content.push_str("0000000000 65535 f\n");
-    content.push_str("0000000009 00000 n\n");
-    content.push_str("0000000058 00000 n\n");
-    content.push_str("0000000115 00000 n\n");
-    content.push_str("0000000203 00000 n\n");
-    content.push_str("trailer\n");
-    content.push_str("<<\n");
-    content.push_str("/Size 5\n");
-    content.push_str("/Root 1 0 R\n");
-    content.push_str(">>\n");
-    content.push_str("startxref\n");
-    content.push_str("850\n");
-    content.push_str("%%EOF\n");
-
-    let mut file = File::create(&pdf_path).map_err(|e| e.to_string())?;
-    file.write_all(content.as_bytes())
+    let mut tx: Transaction<Sqlite> = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let mut skipped_closed = 0;
+    for action in &proposal.actions {
+        let kind = utils::action_kind(&action.action_type);
+
+        // Action visée : celle de la proposition, sinon l'action de même nature de
+        // l'équipement (dépose enregistrée en "depose" ou "retrait"), une action close d'abord
+        let existing = match &action.action_id {
+            Some(action_id) => sqlx::query(
+                "SELECT id, equipement_id, type,
+                        COALESCE(status, CASE WHEN is_done THEN 'done' ELSE 'planned' END) as status
+                 FROM action WHERE id = ?",
+            )
+            .bind(action_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?,
+            None => sqlx::query(&format!(
+                "SELECT id, equipement_id, type,
+                        COALESCE(status, CASE WHEN is_done THEN 'done' ELSE 'planned' END) as status
+                 FROM action WHERE equipement_id = ? AND {} = ?
+                 ORDER BY COALESCE(status, CASE WHEN is_done THEN 'done' ELSE 'planned' END)
+                          IN ('done', 'cancelled') DESC, rowid ASC
+                 LIMIT 1",
+                utils::ACTION_KIND_SQL
+            ))
+            .bind(&action.equipement_id)
+            .bind(kind)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?,
+        };
+
+        match existing {
+            Some(row) => {
+                let action_id: String = row.get("id");
+                let equipement_id: String = row.get("equipement_id");
+                let action_type: String = row.get("type");
+                if equipement_id != action.equipement_id || utils::action_kind(&action_type) != kind
+                {
+                    return Err(format!(
+                        "L'action {} ne correspond pas à la {} de l'équipement {}",
+                        action_id, kind, action.equipement_id
+                    ));
+                }
+
+                // Une action terminée ou annulée n'est ni replanifiée ni dupliquée
+                let status: String = row.get("status");
+                if ActionStatus::parse(&status).is_some_and(|s| s.is_closed()) {
+                    skipped_closed += 1;
+                    continue;
+                }
+
+                sqlx::query("UPDATE action SET team_id = ?, scheduled_time = ? WHERE id = ?")
+                    .bind(&action.team_id)
+                    .bind(&action.scheduled_time)
+                    .bind(&action_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+            None => {
+                if let Some(action_id) = &action.action_id {
+                    return Err(format!(
+                        "Action {} introuvable, relancez la proposition",
+                        action_id
+                    ));
+                }
+
+                sqlx::query(
+                    "INSERT INTO action (id, team_id, equipement_id, type, scheduled_time, is_done) VALUES (?, ?, ?, ?, ?, ?)",
+                )
+                .bind(Uuid::new_v4().to_string())
+                .bind(&action.team_id)
+                .bind(&action.equipement_id)
+                .bind(utils::stored_action_type(kind))
+                .bind(&action.scheduled_time)
+                .bind(false)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+            }
+        }
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    let applied = proposal.actions.len() - skipped_closed;
+    println!(
+        "[DB] ✅ Proposition appliquée : {} action(s) pour l'événement {} ({} terminée(s) ou annulée(s) ignorée(s))",
+        applied,
+        proposal.event_id,
+        skipped_closed
+    );
+
+    Ok(applied)
+}
+
+/// Calcule la tournée d'une équipe (actions d'un événement, ou toutes si event_id est None).
+/// Sans point de départ fourni, la tournée part du dépôt le plus utilisé par ses équipements.
+/// La tournée suit les créneaux planifiés (voir `build_itinerary`) en respectant les dépendances.
+pub async fn compute_team_itinerary(
+    pool: &SqlitePool,
+    team_id: &str,
+    event_id: Option<&str>,
+    start: Option<(f64, f64)>,
+    start_time: Option<NaiveDateTime>,
+) -> Result<TeamItinerary, String> {
+    let team_name: Option<String> = sqlx::query_scalar("SELECT name FROM team WHERE id = ?")
+        .bind(team_id)
+        .fetch_optional(pool)
+        .await
         .map_err(|e| e.to_string())?;
 
-    Ok(())
+    let rows = sqlx::query(
+        r#"
+        SELECT
+            a.id, a.equipement_id, a.type, a.scheduled_time,
+            d.x as depot_x, d.y as depot_y,
+            (SELECT c.x FROM equipement_coordinate c WHERE c.equipement_id = e.id
+             ORDER BY c.order_index ASC LIMIT 1) as x,
+            (SELECT c.y FROM equipement_coordinate c WHERE c.equipement_id = e.id
+             ORDER BY c.order_index ASC LIMIT 1) as y
+        FROM action a
+        JOIN equipement e ON a.equipement_id = e.id
+        LEFT JOIN depot d ON d.id = e.depot_id
+        WHERE a.team_id = ? AND (? IS NULL OR e.event_id = ?)
+        ORDER BY a.scheduled_time ASC
+    "#,
+    )
+    .bind(team_id)
+    .bind(event_id)
+    .bind(event_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let durations = load_action_durations(pool, event_id, Some(team_id)).await?;
+    let mut depot_counts: Vec<((f64, f64), usize)> = Vec::new();
+    let mut inputs = Vec::new();
+
+    for row in rows {
+        let depot_x: Option<f64> = row.get("depot_x");
+        let depot_y: Option<f64> = row.get("depot_y");
+        if let Some(depot) = depot_x.zip(depot_y) {
+            match depot_counts.iter_mut().find(|(p, _)| *p == depot) {
+                Some((_, count)) => *count += 1,
+                None => depot_counts.push((depot, 1)),
+            }
+        }
+
+        let x: Option<f64> = row.get("x");
+        let y: Option<f64> = row.get("y");
+        let action_id: String = row.get("id");
+        inputs.push(ItineraryInput {
+            duration_minutes: duration_of(&durations, &action_id),
+            action_id,
+            equipement_id: row.get("equipement_id"),
+            action_type: row.get("type"),
+            position: x.zip(y),
+            scheduled_time: row.get("scheduled_time"),
+        });
+    }
+
+    let start = start.or_else(|| {
+        depot_counts
+            .iter()
+            .max_by_key(|(_, count)| *count)
+            .map(|(p, _)| *p)
+    });
+    let start_time = start_time.or_else(|| {
+        inputs
+            .iter()
+            .filter_map(|i| i.scheduled_time.as_deref().and_then(utils::parse_datetime))
+            .min()
+    });
+
+    let dependencies = fetch_team_dependencies(pool, team_id).await?;
+    let stops = build_itinerary(
+        start,
+        start_time,
+        inputs,
+        &dependencies,
+        DEFAULT_TRAVEL_SPEED_KMH,
+    );
+
+    Ok(TeamItinerary {
+        team_id: team_id.to_string(),
+        team_name,
+        start_x: start.map(|p| p.0),
+        start_y: start.map(|p| p.1),
+        start_time: start_time.as_ref().map(utils::format_datetime),
+        total_distance_m: stops.last().map_or(0.0, |s| s.cumulative_distance_m),
+        stops,
+    })
+}
+
+/// Ordonne les actions d'une équipe en tournée optimisée.
+/// Le départ est un point choisi (start_x/start_y), sinon un dépôt, sinon le dépôt par défaut.
+#[tauri::command]
+pub async fn fetch_team_itinerary(
+    app: AppHandle,
+    team_id: String,
+    event_id: Option<String>,
+    depot_id: Option<String>,
+    start_x: Option<f64>,
+    start_y: Option<f64>,
+    start_time: Option<String>,
+) -> Result<TeamItinerary, String> {
+    let pool = get_db_pool(&app).await?;
+
+    let mut start = start_x.zip(start_y);
+    if start.is_none() {
+        if let Some(depot_id) = &depot_id {
+            start = sqlx::query_as::<_, (f64, f64)>("SELECT x, y FROM depot WHERE id = ?")
+                .bind(depot_id)
+                .fetch_optional(&pool)
+                .await
+                .map_err(|e| e.to_string())?;
+
+            if start.is_none() {
+                return Err(format!("Dépôt {} introuvable", depot_id));
+            }
+        }
+    }
+
+    let start_time = match start_time.as_deref() {
+        Some(value) => Some(
+            utils::parse_datetime(value)
+                .ok_or_else(|| format!("Heure de départ invalide : {}", value))?,
+        ),
+        None => None,
+    };
+
+    let itinerary =
+        compute_team_itinerary(&pool, &team_id, event_id.as_deref(), start, start_time).await?;
+
+    println!(
+        "[DB] 🧭 Tournée de l'équipe {} : {} étape(s), {:.0} m",
+        team_id,
+        itinerary.stops.len(),
+        itinerary.total_distance_m
+    );
+
+    Ok(itinerary)
+}
+
+/// Vérifie le planning d'un événement par rapport au passage de la course.
+/// Retourne les violations regroupées par équipe (les équipes sans violation sont omises).
+#[tauri::command]
+pub async fn validate_planning_against_race(
+    app: AppHandle,
+    event_id: String,
+) -> Result<Vec<TeamRaceViolations>, String> {
+    let pool = get_db_pool(&app).await?;
+    let durations = load_action_durations(&pool, Some(&event_id), None).await?;
+
+    let parcours = load_race_parcours(&pool, &event_id).await?;
+    let coordinates = load_equipement_points(&pool, &event_id).await?;
+
+    let action_rows = sqlx::query(
+        r#"
+        SELECT a.id, a.team_id, t.name as team_name, a.equipement_id, a.type, a.scheduled_time
+        FROM action a
+        JOIN equipement e ON a.equipement_id = e.id
+        LEFT JOIN team t ON a.team_id = t.id
+        WHERE e.event_id = ?
+        ORDER BY t.name ASC, a.scheduled_time ASC
+    "#,
+    )
+    .bind(&event_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut by_team: Vec<TeamRaceViolations> = Vec::new();
+
+    for row in action_rows {
+        let scheduled_time: Option<String> = row.get("scheduled_time");
+        let Some(scheduled) = scheduled_time.as_deref().and_then(utils::parse_datetime) else {
+            continue;
+        };
+        let equipement_id: String = row.get("equipement_id");
+        let action_type: Option<String> = row.get("type");
+        let action_type = action_type.unwrap_or_default();
+        let points = coordinates
+            .get(&equipement_id)
+            .map(Vec::as_slice)
+            .unwrap_or(&[]);
+
+        for p in &parcours {
+            let Some((chainage, window)) = p.occupancy_window(points, PARCOURS_TOLERANCE_METERS)
+            else {
+                continue;
+            };
+
+            let action_id: String = row.get("id");
+            let Some(message) = check_race_window(
+                &action_type,
+                scheduled,
+                duration_of(&durations, &action_id),
+                &window,
+            ) else {
+                continue;
+            };
+
+            let team_id: String = row.get("team_id");
+            let violation = RaceViolation {
+                action_id,
+                equipement_id: equipement_id.clone(),
+                action_type: action_type.clone(),
+                scheduled_time: utils::format_datetime(&scheduled),
+                parcours_id: p.id.clone(),
+                parcours_name: p.name.clone(),
+                chainage_m: chainage,
+                first_passage: utils::format_datetime(&window.first_passage),
+                last_passage: utils::format_datetime(&window.last_passage),
+                message,
+            };
+
+            match by_team.iter_mut().find(|t| t.team_id == team_id) {
+                Some(team) => team.violations.push(violation),
+                None => by_team.push(TeamRaceViolations {
+                    team_id,
+                    team_name: row.get("team_name"),
+                    violations: vec![violation],
+                }),
+            }
+        }
+    }
+
+    println!(
+        "[DB] 🏃 Validation course pour l'événement {} : {} violation(s) sur {} équipe(s)",
+        event_id,
+        by_team.iter().map(|t| t.violations.len()).sum::<usize>(),
+        by_team.len()
+    );
+
+    Ok(by_team)
+}
+
+/// Charge les actions planifiées (avec horaire valide) d'un événement pour le contrôle de cohérence
+pub async fn fetch_scheduled_actions(
+    pool: &SqlitePool,
+    event_id: &str,
+) -> Result<Vec<ScheduledAction>, String> {
+    let rows = sqlx::query(
+        r#"
+        SELECT
+            a.id, a.equipement_id, a.type, a.team_id, a.scheduled_time,
+            t.name as team_name,
+            (SELECT c.x FROM equipement_coordinate c WHERE c.equipement_id = e.id
+             ORDER BY c.order_index ASC LIMIT 1) as x,
+            (SELECT c.y FROM equipement_coordinate c WHERE c.equipement_id = e.id
+             ORDER BY c.order_index ASC LIMIT 1) as y
+        FROM action a
+        JOIN equipement e ON a.equipement_id = e.id
+        LEFT JOIN team t ON a.team_id = t.id
+        WHERE e.event_id = ?
+    "#,
+    )
+    .bind(event_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let durations = load_action_durations(pool, Some(event_id), None).await?;
+    let actions = rows
+        .into_iter()
+        .filter_map(|row| {
+            let scheduled_time: Option<String> = row.get("scheduled_time");
+            let start = scheduled_time.as_deref().and_then(utils::parse_datetime)?;
+            let x: Option<f64> = row.get("x");
+            let y: Option<f64> = row.get("y");
+
+            let action_id: String = row.get("id");
+            Some(ScheduledAction {
+                duration_minutes: duration_of(&durations, &action_id),
+                action_id,
+                equipement_id: row.get("equipement_id"),
+                team_id: row.get("team_id"),
+                team_name: row.get("team_name"),
+                start,
+                position: x.zip(y),
+            })
+        })
+        .collect();
+
+    Ok(actions)
+}
+
+/// Détecte les chevauchements et les trajets impossibles dans le planning d'un événement
+#[tauri::command]
+pub async fn check_planning_conflicts(
+    app: AppHandle,
+    event_id: String,
+) -> Result<Vec<PlanningConflict>, String> {
+    let pool = get_db_pool(&app).await?;
+    let actions = fetch_scheduled_actions(&pool, &event_id).await?;
+    let conflicts = detect_conflicts(&actions, DEFAULT_TRAVEL_SPEED_KMH);
+
+    println!(
+        "[DB] ⚠️ {} conflit(s) de planning pour l'événement {}",
+        conflicts.len(),
+        event_id
+    );
+
+    Ok(conflicts)
+}
+
+/// Dépendances non respectées si la proposition est appliquée
+/// (les actions hors proposition gardent leur horaire actuel)
+async fn proposal_dependency_violations(
+    pool: &SqlitePool,
+    event_id: &str,
+    proposed: &[ProposedAction],
+) -> Result<Vec<DependencyViolation>, String> {
+    let dependencies = fetch_event_dependencies(pool, event_id).await?;
+    if dependencies.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut schedule = scheduled_actions_map(fetch_scheduled_actions(pool, event_id).await?);
+    for action in proposed {
+        let (Some(action_id), Some(start)) = (
+            action.action_id.as_ref(),
+            utils::parse_datetime(&action.scheduled_time),
+        ) else {
+            continue;
+        };
+        schedule.insert(
+            action_id.clone(),
+            (start, start + Duration::minutes(action.duration_minutes)),
+        );
+    }
+
+    Ok(check_precedence(&dependencies, &schedule))
+}
+
+fn scheduled_actions_map(
+    actions: Vec<ScheduledAction>,
+) -> HashMap<String, (NaiveDateTime, NaiveDateTime)> {
+    actions
+        .into_iter()
+        .map(|a| {
+            let end = a.end();
+            (a.action_id, (a.start, end))
+        })
+        .collect()
+}
+
+/// Vérifie les dépendances entre actions avec le planning actuel d'un événement
+#[tauri::command]
+pub async fn validate_action_dependencies(
+    app: AppHandle,
+    event_id: String,
+) -> Result<Vec<DependencyViolation>, String> {
+    let pool = get_db_pool(&app).await?;
+    let dependencies = fetch_event_dependencies(&pool, &event_id).await?;
+    let schedule = scheduled_actions_map(fetch_scheduled_actions(&pool, &event_id).await?);
+    let violations = check_precedence(&dependencies, &schedule);
+
+    println!(
+        "[DB] 🔗 {} dépendance(s) non respectée(s) pour l'événement {}",
+        violations.len(),
+        event_id
+    );
+    Ok(violations)
 }
@@ -1,4 +1,5 @@
 use crate::db::get_db_pool;
+use crate::snapping::refresh_point_chainage;
 use crate::types::*;
 use sqlx::{Row, SqlitePool};
 use sqlx::{Sqlite, Transaction};
@@ -17,7 +18,9 @@ pub async fn fetch_points(
     let rows = if let Some(eid) = event_id {
         println!("[DB] Récupération des points pour event_id: {}", eid);
         sqlx::query(
-            "SELECT id, x, y, name, event_id, comment, status, type FROM point WHERE event_id = ?",
+            "SELECT id, x, y, name, event_id, comment, status, type, parcours_id, chainage_m
+             FROM point WHERE event_id = ?
+             ORDER BY chainage_m IS NULL, parcours_id, chainage_m, rowid",
         )
         .bind(eid)
         .fetch_all(&pool)
@@ -25,10 +28,14 @@ pub async fn fetch_points(
         .map_err(|e| e.to_string())?
     } else {
         println!("[DB] Récupération de tous les points");
-        sqlx::query("SELECT id, x, y, name, event_id, comment, status, type FROM point")
-            .fetch_all(&pool)
-            .await
-            .map_err(|e| e.to_string())?
+        sqlx::query(
+            "SELECT id, x, y, name, event_id, comment, status, type, parcours_id, chainage_m
+             FROM point
+             ORDER BY chainage_m IS NULL, parcours_id, chainage_m, rowid",
+        )
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| e.to_string())?
     };
 
     let mut points_with_details: Vec<PointWithDetails> = Vec::new();
@@ -61,6 +68,8 @@ pub async fn fetch_points(
             comment: row.get("comment"),
             r#type: row.get("type"),
             pictures,
+            parcours_id: row.get("parcours_id"),
+            chainage_m: row.get("chainage_m"),
         });
     }
 
@@ -85,6 +94,9 @@ pub async fn update_point(app: AppHandle, point: Point) -> Result<Point, String>
         .await
         .map_err(|e| e.to_string())?;
 
+    // Le point a pu être déplacé : sa position kilométrique n'est plus forcément valable
+    refresh_point_chainage(&pool, &point.id).await?;
+
     Ok(point)
 }
 #[tauri::command]
@@ -137,6 +149,9 @@ pub async fn fetch_equipement_details(
             e.hour_pose,
             e.date_depose,
             e.hour_depose,
+            e.depot_id,
+            e.parcours_id,
+            e.chainage_m,
             t.name AS type_name,
             t.description AS type_description
         FROM equipement e
@@ -171,7 +186,10 @@ pub async fn fetch_equipement_details(
                 hour_pose: row.get("hour_pose"),
                 date_depose: row.get("date_depose"),
                 hour_depose: row.get("hour_depose"),
+                depot_id: row.get("depot_id"),
                 coordinates,
+                parcours_id: row.get("parcours_id"),
+                chainage_m: row.get("chainage_m"),
             };
 
             Ok(Some(equipement))
@@ -263,10 +281,11 @@ pub async fn retrieve_data_by_event(
         println!("[DB] Récupération des points pour l'event_id: {}", eid);
         sqlx::query(
             r#"
-            SELECT DISTINCT p.id, p.x, p.y, p.name, p.comment, p.type, p.status, p.event_id
+            SELECT DISTINCT p.id, p.x, p.y, p.name, p.comment, p.type, p.status, p.event_id,
+                   p.parcours_id, p.chainage_m
             FROM point p
             WHERE p.event_id = ?
-            ORDER BY p.id
+            ORDER BY p.chainage_m IS NULL, p.parcours_id, p.chainage_m, p.id
         "#,
         )
         .bind(eid)
@@ -277,9 +296,10 @@ pub async fn retrieve_data_by_event(
         println!("[DB]  Récupération de tous les points");
         sqlx::query(
             r#"
-            SELECT p.id, p.x, p.y, p.name, p.comment, p.type, p.status, p.event_id
+            SELECT p.id, p.x, p.y, p.name, p.comment, p.type, p.status, p.event_id,
+                   p.parcours_id, p.chainage_m
             FROM point p
-            ORDER BY p.id
+            ORDER BY p.chainage_m IS NULL, p.parcours_id, p.chainage_m, p.id
         "#,
         )
         .fetch_all(&pool)
@@ -317,6 +337,8 @@ pub async fn retrieve_data_by_event(
             event_id: row.get("event_id"),
             r#type: row.get("type"),
             pictures,
+            parcours_id: row.get("parcours_id"),
+            chainage_m: row.get("chainage_m"),
         });
     }
 
@@ -485,17 +507,19 @@ pub async fn fetch_interest_points(
             "[DB] Récupération des points d'intérêt pour event_id: {}",
             eid
         );
-        sqlx::query("SELECT id, x, y, description, event_id FROM interest WHERE event_id = ?")
+        sqlx::query("SELECT id, x, y, description, event_id, parcours_id, marker_km, bearing FROM interest WHERE event_id = ?")
             .bind(eid)
             .fetch_all(&pool)
             .await
             .map_err(|e| e.to_string())?
     } else {
         println!("[DB] Récupération de tous les points d'intérêt");
-        sqlx::query("SELECT id, x, y, description, event_id FROM interest")
-            .fetch_all(&pool)
-            .await
-            .map_err(|e| e.to_string())?
+        sqlx::query(
+            "SELECT id, x, y, description, event_id, parcours_id, marker_km, bearing FROM interest",
+        )
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| e.to_string())?
     };
 
     let interests: Vec<Interest> = rows
@@ -506,6 +530,9 @@ pub async fn fetch_interest_points(
             y: row.get("y"),
             description: row.get("description"),
             event_id: row.get("event_id"),
+            parcours_id: row.get("parcours_id"),
+            marker_km: row.get("marker_km"),
+            bearing: row.get("bearing"),
         })
         .collect();
 
@@ -283,6 +283,7 @@ pub async fn fetch_team_actions(
             e.date_pose,
             e.date_depose,
             e.event_id,
+            e.depot_id,
             t.name as type_name,
             t.description as type_description
         FROM action a
@@ -317,7 +318,10 @@ pub async fn fetch_team_actions(
             hour_pose: None,
             date_depose: row.get("date_depose"),
             hour_depose: None,
+            depot_id: row.get("depot_id"),
             coordinates,
+            parcours_id: None,
+            chainage_m: None,
         };
 
         // 2. Wrap it in the new Action-specific struct
@@ -0,0 +1,172 @@
+use crate::db::get_db_pool;
+use crate::types::Wave;
+use sqlx::Row;
+use tauri::AppHandle;
+use uuid::Uuid;
+
+// ============================================
+// FONCTIONS HELPER (testables)
+// ============================================
+
+/// Vérifie les vitesses d'une vague (km/h) : strictement positives, et la vitesse
+/// du dernier coureur (`speed_low`) ne dépasse pas celle du premier (`speed_high`)
+pub fn validate_wave_speeds(speed_low: Option<f64>, speed_high: Option<f64>) -> Result<(), String> {
+    for (label, speed) in [("basse", speed_low), ("haute", speed_high)] {
+        if let Some(speed) = speed {
+            if !speed.is_finite() || speed <= 0.0 {
+                return Err(format!(
+                    "La vitesse {} doit être strictement positive (reçu {})",
+                    label, speed
+                ));
+            }
+        }
+    }
+
+    if let (Some(low), Some(high)) = (speed_low, speed_high) {
+        if low > high {
+            return Err(format!(
+                "La vitesse basse ({} km/h) dépasse la vitesse haute ({} km/h)",
+                low, high
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================
+// VAGUES DE DÉPART
+// ============================================
+
+/// Vagues de départ d'un parcours, dans l'ordre des départs
+#[tauri::command]
+pub async fn fetch_waves_for_parcours(
+    app: AppHandle,
+    parcours_id: String,
+) -> Result<Vec<Wave>, String> {
+    let pool = get_db_pool(&app).await?;
+
+    let rows = sqlx::query(
+        "SELECT id, parcours_id, name, start_time, speed_low, speed_high
+         FROM wave WHERE parcours_id = ? ORDER BY start_time",
+    )
+    .bind(&parcours_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let waves: Vec<Wave> = rows
+        .into_iter()
+        .map(|row| Wave {
+            id: row.get("id"),
+            parcours_id: row.get("parcours_id"),
+            name: row.get("name"),
+            start_time: row.get("start_time"),
+            speed_low: row.get("speed_low"),
+            speed_high: row.get("speed_high"),
+        })
+        .collect();
+
+    println!(
+        "[DB] 🏁 {} vague(s) récupérée(s) pour le parcours {}",
+        waves.len(),
+        parcours_id
+    );
+    Ok(waves)
+}
+
+#[tauri::command]
+pub async fn create_wave(
+    app: AppHandle,
+    parcours_id: String,
+    name: Option<String>,
+    start_time: Option<i64>,
+    speed_low: Option<f64>,
+    speed_high: Option<f64>,
+) -> Result<Wave, String> {
+    validate_wave_speeds(speed_low, speed_high)?;
+    let pool = get_db_pool(&app).await?;
+    let uuid = Uuid::new_v4().to_string();
+
+    sqlx::query("INSERT INTO wave (id, parcours_id, name, start_time, speed_low, speed_high) VALUES (?, ?, ?, ?, ?, ?)")
+        .bind(&uuid)
+        .bind(&parcours_id)
+        .bind(&name)
+        .bind(start_time)
+        .bind(speed_low)
+        .bind(speed_high)
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("Erreur lors de la création de la vague : {}", e))?;
+
+    println!(
+        "[DB] ✅ Vague {} créée pour le parcours {}",
+        uuid, parcours_id
+    );
+
+    Ok(Wave {
+        id: uuid,
+        parcours_id,
+        name,
+        start_time,
+        speed_low,
+        speed_high,
+    })
+}
+
+#[tauri::command]
+pub async fn update_wave(
+    app: AppHandle,
+    wave_id: String,
+    name: Option<String>,
+    start_time: Option<i64>,
+    speed_low: Option<f64>,
+    speed_high: Option<f64>,
+) -> Result<Wave, String> {
+    validate_wave_speeds(speed_low, speed_high)?;
+    let pool = get_db_pool(&app).await?;
+
+    let parcours_id: String = sqlx::query_scalar("SELECT parcours_id FROM wave WHERE id = ?")
+        .bind(&wave_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Vague {} non trouvée", wave_id))?;
+
+    sqlx::query(
+        "UPDATE wave SET name = ?, start_time = ?, speed_low = ?, speed_high = ? WHERE id = ?",
+    )
+    .bind(&name)
+    .bind(start_time)
+    .bind(speed_low)
+    .bind(speed_high)
+    .bind(&wave_id)
+    .execute(&pool)
+    .await
+    .map_err(|e| format!("Erreur lors de la mise à jour de la vague : {}", e))?;
+
+    println!("[DB] ✅ Vague {} mise à jour", wave_id);
+
+    Ok(Wave {
+        id: wave_id,
+        parcours_id,
+        name,
+        start_time,
+        speed_low,
+        speed_high,
+    })
+}
+
+#[tauri::command]
+pub async fn delete_wave(app: AppHandle, wave_id: String) -> Result<(), String> {
+    let pool = get_db_pool(&app).await?;
+
+    sqlx::query("DELETE FROM wave WHERE id = ?")
+        .bind(&wave_id)
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("Erreur lors de la suppression de la vague : {}", e))?;
+
+    println!("[DB] ✅ Vague {} supprimée", wave_id);
+    Ok(())
+}
@@ -0,0 +1,474 @@
+// Estimation de la durée des actions
+// Chaque type d'équipement a des cadences de pose et de dépose (temps fixe, par unité,
+// par mètre) pour une équipe de référence ; la taille réelle de l'équipe module la durée.
+// Un facteur de calibration, recalculé à partir des durées réelles remontées par le
+// mobile (passage « en cours » -> « terminée »), corrige les cadences saisies.
+
+use crate::db;
+use crate::scheduling::DEFAULT_ACTION_DURATION_MINUTES;
+use crate::types::ActionStatus;
+use crate::utils;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+/// Rendement décroissant : doubler l'équipe divise la durée par 2^0.8 ≈ 1,74
+pub const TEAM_SIZE_EXPONENT: f64 = 0.8;
+
+/// Taille d'équipe pour laquelle les cadences sont saisies par défaut
+pub const DEFAULT_REFERENCE_TEAM_SIZE: i64 = 2;
+
+/// Nombre d'échantillons « virtuels » donnant du poids aux cadences saisies :
+/// avec 5 durées réelles, la calibration ne corrige que la moitié de l'écart observé
+const CALIBRATION_PRIOR_SAMPLES: f64 = 5.0;
+
+/// Bornes du facteur de calibration (protège contre une saisie mobile aberrante)
+const MIN_CALIBRATION_FACTOR: f64 = 0.25;
+const MAX_CALIBRATION_FACTOR: f64 = 4.0;
+
+/// Cadence d'un type d'équipement pour une action (pose ou dépose)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProductivityRate {
+    pub type_id: String,
+    pub action_type: String,
+    /// Temps fixe par action (chargement, balisage...)
+    pub base_minutes: f64,
+    pub minutes_per_unit: f64,
+    pub minutes_per_metre: f64,
+    pub reference_team_size: i64,
+    /// Recalculé par `recalibrate_productivity_rates`
+    #[serde(default = "default_calibration_factor")]
+    pub calibration_factor: f64,
+    #[serde(default)]
+    pub sample_count: i64,
+}
+
+fn default_calibration_factor() -> f64 {
+    1.0
+}
+
+/// Durée réelle d'une action terminée, avec ce qui a servi à l'estimer
+#[derive(Debug, Clone)]
+pub struct CompletionSample {
+    pub quantity: f64,
+    pub length_m: f64,
+    pub team_size: i64,
+    pub actual_minutes: f64,
+}
+
+// ==================== Fonctions helper publiques et testables ====================
+
+/// Facteur appliqué à la durée de référence selon la taille réelle de l'équipe.
+/// Taille inconnue (0) : pas de modulation.
+pub fn team_size_factor(reference_team_size: i64, team_size: i64) -> f64 {
+    if reference_team_size <= 0 || team_size <= 0 {
+        return 1.0;
+    }
+    (reference_team_size as f64 / team_size as f64).powf(TEAM_SIZE_EXPONENT)
+}
+
+/// Durée en minutes pour l'équipe de référence, hors calibration
+pub fn reference_minutes(rate: &ProductivityRate, quantity: f64, length_m: f64) -> f64 {
+    rate.base_minutes + rate.minutes_per_unit * quantity + rate.minutes_per_metre * length_m
+}
+
+/// Durée estimée (minutes, arrondie au supérieur, au moins 1).
+/// Sans cadence pour le type, on garde la durée par défaut.
+pub fn estimate_minutes(
+    rate: Option<&ProductivityRate>,
+    quantity: f64,
+    length_m: f64,
+    team_size: i64,
+) -> i64 {
+    let Some(rate) = rate else {
+        return DEFAULT_ACTION_DURATION_MINUTES;
+    };
+
+    let minutes = reference_minutes(rate, quantity, length_m)
+        * rate.calibration_factor
+        * team_size_factor(rate.reference_team_size, team_size);
+    (minutes.ceil() as i64).max(1)
+}
+
+/// Durée réelle d'une action à partir de son historique de statuts (trié par date) :
+/// du dernier passage « en cours » au passage « terminée » qui le suit
+pub fn actual_duration_minutes(history: &[(ActionStatus, NaiveDateTime)]) -> Option<f64> {
+    let (done_index, (_, done_at)) = history
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(_, (status, _))| *status == ActionStatus::Done)?;
+
+    let (_, started_at) = history[..done_index]
+        .iter()
+        .rev()
+        .find(|(status, _)| *status == ActionStatus::InProgress)?;
+
+    let minutes = (*done_at - *started_at).num_seconds() as f64 / 60.0;
+    (minutes > 0.0).then_some(minutes)
+}
+
+/// Facteur de calibration : médiane des rapports durée réelle / durée estimée,
+/// atténuée tant que les échantillons sont peu nombreux
+pub fn calibration_factor(rate: &ProductivityRate, samples: &[CompletionSample]) -> f64 {
+    let mut ratios: Vec<f64> = samples
+        .iter()
+        .filter_map(|s| {
+            let estimated = reference_minutes(rate, s.quantity, s.length_m)
+                * team_size_factor(rate.reference_team_size, s.team_size);
+            (estimated > 0.0 && s.actual_minutes > 0.0).then(|| s.actual_minutes / estimated)
+        })
+        .collect();
+
+    if ratios.is_empty() {
+        return 1.0;
+    }
+
+    ratios.sort_by(f64::total_cmp);
+    let middle = ratios.len() / 2;
+    let median = if ratios.len().is_multiple_of(2) {
+        (ratios[middle - 1] + ratios[middle]) / 2.0
+    } else {
+        ratios[middle]
+    };
+
+    let weight = ratios.len() as f64 / (ratios.len() as f64 + CALIBRATION_PRIOR_SAMPLES);
+    median
+        .powf(weight)
+        .clamp(MIN_CALIBRATION_FACTOR, MAX_CALIBRATION_FACTOR)
+}
+
+/// Type d'action d'une cadence : "pose" ou "depose".
+/// "retrait" (type des déposes saisies dans l'interface web) est ramené à "depose".
+pub fn rate_action_type(action_type: &str) -> Option<&'static str> {
+    match action_type {
+        "pose" => Some("pose"),
+        "depose" | "retrait" => Some("depose"),
+        _ => None,
+    }
+}
+
+/// Clé de cadence d'une action enregistrée (tout type autre que "pose" est une dépose)
+pub fn rate_key(type_id: String, action_type: &str) -> (String, String) {
+    (type_id, utils::action_kind(action_type).to_string())
+}
+
+/// Durée d'une action d'après les estimations chargées, défaut sinon
+pub fn duration_of(durations: &HashMap<String, i64>, action_id: &str) -> i64 {
+    durations
+        .get(action_id)
+        .copied()
+        .unwrap_or(DEFAULT_ACTION_DURATION_MINUTES)
+}
+
+// ==================== Chargement des données ====================
+
+/// Cadences configurées, par (type_id, action_type)
+pub async fn load_productivity_rates(
+    pool: &SqlitePool,
+) -> Result<HashMap<(String, String), ProductivityRate>, String> {
+    let rows = sqlx::query(
+        r#"
+        SELECT type_id, action_type, base_minutes, minutes_per_unit, minutes_per_metre,
+               reference_team_size, calibration_factor, sample_count
+        FROM productivity_rate
+    "#,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let rate = ProductivityRate {
+                type_id: row.get("type_id"),
+                action_type: row.get("action_type"),
+                base_minutes: row.get("base_minutes"),
+                minutes_per_unit: row.get("minutes_per_unit"),
+                minutes_per_metre: row.get("minutes_per_metre"),
+                reference_team_size: row.get("reference_team_size"),
+                calibration_factor: row.get("calibration_factor"),
+                sample_count: row.get("sample_count"),
+            };
+            ((rate.type_id.clone(), rate.action_type.clone()), rate)
+        })
+        .collect())
+}
+
+/// Durée estimée de chaque action (par ID), filtrée par événement et/ou équipe
+pub async fn load_action_durations(
+    pool: &SqlitePool,
+    event_id: Option<&str>,
+    team_id: Option<&str>,
+) -> Result<HashMap<String, i64>, String> {
+    let rates = load_productivity_rates(pool).await?;
+
+    let rows = sqlx::query(
+        r#"
+        SELECT
+            a.id, a.type, e.type_id,
+            COALESCE(e.quantity, 0) as quantity,
+            COALESCE(e.quantity, 0) * COALESCE(e.length_per_unit, 0) as length_m,
+            (SELECT COUNT(*) FROM member m WHERE m.team_id = a.team_id) as team_size
+        FROM action a
+        JOIN equipement e ON a.equipement_id = e.id
+        WHERE (? IS NULL OR e.event_id = ?) AND (? IS NULL OR a.team_id = ?)
+    "#,
+    )
+    .bind(event_id)
+    .bind(event_id)
+    .bind(team_id)
+    .bind(team_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let type_id: Option<String> = row.get("type_id");
+            let action_type: Option<String> = row.get("type");
+            let rate = type_id
+                .zip(action_type)
+                .and_then(|(type_id, action_type)| rates.get(&rate_key(type_id, &action_type)));
+            let minutes = estimate_minutes(
+                rate,
+                row.get::<i64, _>("quantity") as f64,
+                row.get::<i64, _>("length_m") as f64,
+                row.get("team_size"),
+            );
+            (row.get("id"), minutes)
+        })
+        .collect())
+}
+
+/// Nombre de membres de chaque équipe d'un événement
+pub async fn load_team_sizes(
+    pool: &SqlitePool,
+    event_id: &str,
+) -> Result<HashMap<String, i64>, String> {
+    let rows = sqlx::query(
+        r#"
+        SELECT t.id, COUNT(m.id) as team_size
+        FROM team t
+        LEFT JOIN member m ON m.team_id = t.id
+        WHERE t.event_id = ?
+        GROUP BY t.id
+    "#,
+    )
+    .bind(event_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.get("id"), row.get("team_size")))
+        .collect())
+}
+
+/// Recalcule le facteur de calibration de chaque cadence à partir de toutes les actions
+/// terminées. Le calcul repart des cadences saisies : il peut être relancé à chaque
+/// synchronisation mobile sans compter deux fois les mêmes durées.
+pub async fn recalibrate_rates(pool: &SqlitePool) -> Result<Vec<ProductivityRate>, String> {
+    let rates = load_productivity_rates(pool).await?;
+
+    let action_rows = sqlx::query(
+        r#"
+        SELECT
+            a.id, a.type, e.type_id,
+            COALESCE(e.quantity, 0) as quantity,
+            COALESCE(e.quantity, 0) * COALESCE(e.length_per_unit, 0) as length_m,
+            (SELECT COUNT(*) FROM member m WHERE m.team_id = a.team_id) as team_size
+        FROM action a
+        JOIN equipement e ON a.equipement_id = e.id
+        WHERE a.status = 'done' AND e.type_id IS NOT NULL
+    "#,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let history_rows = sqlx::query(
+        r#"
+        SELECT s.action_id, s.status, s.changed_at
+        FROM action_status_change s
+        JOIN action a ON s.action_id = a.id
+        WHERE a.status = 'done'
+        ORDER BY s.action_id, s.changed_at ASC
+    "#,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut histories: HashMap<String, Vec<(ActionStatus, NaiveDateTime)>> = HashMap::new();
+    for row in history_rows {
+        let status: String = row.get("status");
+        let changed_at: String = row.get("changed_at");
+        if let (Some(status), Some(changed_at)) = (
+            ActionStatus::parse(&status),
+            utils::parse_datetime(&changed_at),
+        ) {
+            histories
+                .entry(row.get("action_id"))
+                .or_default()
+                .push((status, changed_at));
+        }
+    }
+
+    let mut samples: HashMap<(String, String), Vec<CompletionSample>> = HashMap::new();
+    for row in action_rows {
+        let action_id: String = row.get("id");
+        let Some(actual_minutes) = histories
+            .get(&action_id)
+            .and_then(|h| actual_duration_minutes(h))
+        else {
+            continue;
+        };
+
+        let action_type: Option<String> = row.get("type");
+        samples
+            .entry(rate_key(
+                row.get("type_id"),
+                &action_type.unwrap_or_default(),
+            ))
+            .or_default()
+            .push(CompletionSample {
+                quantity: row.get::<i64, _>("quantity") as f64,
+                length_m: row.get::<i64, _>("length_m") as f64,
+                team_size: row.get("team_size"),
+                actual_minutes,
+            });
+    }
+
+    let mut updated = Vec::new();
+    for (key, mut rate) in rates {
+        let rate_samples = samples.get(&key).map(Vec::as_slice).unwrap_or(&[]);
+        rate.calibration_factor = calibration_factor(&rate, rate_samples);
+        rate.sample_count = rate_samples.len() as i64;
+
+        sqlx::query(
+            "UPDATE productivity_rate SET calibration_factor = ?, sample_count = ? WHERE type_id = ? AND action_type = ?",
+        )
+        .bind(rate.calibration_factor)
+        .bind(rate.sample_count)
+        .bind(&rate.type_id)
+        .bind(&rate.action_type)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        updated.push(rate);
+    }
+
+    updated.sort_by(|a, b| (&a.type_id, &a.action_type).cmp(&(&b.type_id, &b.action_type)));
+    Ok(updated)
+}
+
+// ==================== Commandes Tauri ====================
+
+#[tauri::command]
+pub async fn fetch_productivity_rates(app: AppHandle) -> Result<Vec<ProductivityRate>, String> {
+    let pool = db::get_db_pool(&app).await?;
+    let mut rates: Vec<ProductivityRate> = load_productivity_rates(&pool)
+        .await?
+        .into_values()
+        .collect();
+    rates.sort_by(|a, b| (&a.type_id, &a.action_type).cmp(&(&b.type_id, &b.action_type)));
+    Ok(rates)
+}
+
+/// Crée ou met à jour la cadence d'un type pour une action (pose / depose ou retrait).
+/// La calibration existante est conservée.
+#[tauri::command]
+pub async fn save_productivity_rate(
+    app: AppHandle,
+    mut rate: ProductivityRate,
+) -> Result<(), String> {
+    rate.action_type = rate_action_type(&rate.action_type)
+        .ok_or_else(|| format!("Type d'action inconnu : {}", rate.action_type))?
+        .to_string();
+    if rate.base_minutes < 0.0 || rate.minutes_per_unit < 0.0 || rate.minutes_per_metre < 0.0 {
+        return Err("Les cadences ne peuvent pas être négatives".to_string());
+    }
+
+    let pool = db::get_db_pool(&app).await?;
+    sqlx::query(
+        r#"
+        INSERT INTO productivity_rate
+            (type_id, action_type, base_minutes, minutes_per_unit, minutes_per_metre, reference_team_size)
+        VALUES (?, ?, ?, ?, ?, ?)
+        ON CONFLICT(type_id, action_type) DO UPDATE SET
+            base_minutes = excluded.base_minutes,
+            minutes_per_unit = excluded.minutes_per_unit,
+            minutes_per_metre = excluded.minutes_per_metre,
+            reference_team_size = excluded.reference_team_size
+    "#,
+    )
+    .bind(&rate.type_id)
+    .bind(&rate.action_type)
+    .bind(rate.base_minutes)
+    .bind(rate.minutes_per_unit)
+    .bind(rate.minutes_per_metre)
+    .bind(if rate.reference_team_size > 0 {
+        rate.reference_team_size
+    } else {
+        DEFAULT_REFERENCE_TEAM_SIZE
+    })
+    .execute(&pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    println!(
+        "[DB] ⏱️ Cadence enregistrée pour le type {} ({})",
+        rate.type_id, rate.action_type
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_productivity_rate(
+    app: AppHandle,
+    type_id: String,
+    action_type: String,
+) -> Result<(), String> {
+    let action_type = rate_action_type(&action_type).unwrap_or(action_type.as_str());
+    let pool = db::get_db_pool(&app).await?;
+    sqlx::query("DELETE FROM productivity_rate WHERE type_id = ? AND action_type = ?")
+        .bind(&type_id)
+        .bind(action_type)
+        .execute(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Durée estimée (minutes) de chaque action d'un événement
+#[tauri::command]
+pub async fn fetch_action_durations(
+    app: AppHandle,
+    event_id: String,
+) -> Result<HashMap<String, i64>, String> {
+    let pool = db::get_db_pool(&app).await?;
+    load_action_durations(&pool, Some(&event_id), None).await
+}
+
+/// Recalibre les cadences à partir des durées réelles remontées par le mobile
+#[tauri::command]
+pub async fn recalibrate_productivity_rates(
+    app: AppHandle,
+) -> Result<Vec<ProductivityRate>, String> {
+    let pool = db::get_db_pool(&app).await?;
+    let rates = recalibrate_rates(&pool).await?;
+
+    println!(
+        "[DB] 🎯 {} cadence(s) recalibrée(s) sur {} durée(s) réelle(s)",
+        rates.len(),
+        rates.iter().map(|r| r.sample_count).sum::<i64>()
+    );
+    Ok(rates)
+}
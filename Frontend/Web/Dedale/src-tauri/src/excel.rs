@@ -1,4 +1,6 @@
+use crate::closures;
 use crate::db::{self};
+use crate::race::{load_event_passage_times, passages_by_target};
 use crate::types::PointWithDetails;
 use crate::utils;
 use rust_xlsxwriter::{Color, Format, Workbook};
@@ -24,6 +26,15 @@ pub async fn export_points_excel(app: AppHandle, event_id: Option<String>) -> Re
         "Tous les événements".to_string()
     };
 
+    // Passage de la course au niveau de chaque point (uniquement pour un événement donné)
+    let passages = match &event_id {
+        Some(eid) => {
+            let pool = db::get_db_pool(&app).await?;
+            passages_by_target(load_event_passage_times(&pool, eid).await?)
+        }
+        None => Default::default(),
+    };
+
     let mut workbook = Workbook::new();
     let worksheet = workbook.add_worksheet();
 
@@ -42,6 +53,9 @@ pub async fn export_points_excel(app: AppHandle, event_id: Option<String>) -> Re
         "Largeur",
         "Longueur",
         "Événement",
+        "Km parcours",
+        "Premier coureur",
+        "Dernier coureur",
     ];
 
     for (col, header) in headers.iter().enumerate() {
@@ -90,6 +104,17 @@ pub async fn export_points_excel(app: AppHandle, event_id: Option<String>) -> Re
         worksheet
             .write_string(current_row, 8, &row_event_name)
             .map_err(|e| e.to_string())?;
+        if let Some(passage) = passages.get(&("point".to_string(), p.id.clone())) {
+            worksheet
+                .write_number(current_row, 9, (passage.chainage_m / 10.0).round() / 100.0)
+                .map_err(|e| e.to_string())?;
+            worksheet
+                .write_string(current_row, 10, &passage.first_passage)
+                .map_err(|e| e.to_string())?;
+            worksheet
+                .write_string(current_row, 11, &passage.last_passage)
+                .map_err(|e| e.to_string())?;
+        }
 
         current_row += 1;
     }
@@ -106,3 +131,222 @@ pub async fn export_points_excel(app: AppHandle, event_id: Option<String>) -> Re
 
     Ok(())
 }
+
+/// Exporte le bon de chargement par dépôt (ce qui sort de chaque dépôt, et quand)
+#[tauri::command]
+pub async fn export_depot_report_excel(app: AppHandle, event_id: String) -> Result<(), String> {
+    let reports = db::fetch_depot_report(app.clone(), event_id.clone()).await?;
+    println!("📊 Export Excel dépôts : {} dépôt(s)", reports.len());
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    let header_format = Format::new()
+        .set_bold()
+        .set_background_color(Color::RGB(0xE0E0E0))
+        .set_border(rust_xlsxwriter::FormatBorder::Thin);
+    let depot_format = Format::new().set_bold().set_font_size(13);
+    let missing_format = Format::new().set_font_color(Color::Red).set_bold();
+
+    let headers = [
+        "Sortie",
+        "Retour",
+        "Type",
+        "Description",
+        "Quantité",
+        "Longueur/unité",
+        "Équipe (pose)",
+    ];
+
+    for col in 0..headers.len() {
+        worksheet
+            .set_column_width(col as u16, 20)
+            .map_err(|e| e.to_string())?;
+    }
+
+    let mut current_row: u32 = 0;
+
+    for report in &reports {
+        let depot_label = match &report.depot {
+            Some(depot) => format!(
+                "{} ({})",
+                depot.name.as_deref().unwrap_or("Dépôt"),
+                depot.address.as_deref().unwrap_or("adresse inconnue")
+            ),
+            None => "Sans dépôt".to_string(),
+        };
+
+        worksheet
+            .write_string_with_format(current_row, 0, &depot_label, &depot_format)
+            .map_err(|e| e.to_string())?;
+        current_row += 1;
+
+        for (col, header) in headers.iter().enumerate() {
+            worksheet
+                .write_string_with_format(current_row, col as u16, *header, &header_format)
+                .map_err(|e| e.to_string())?;
+        }
+        current_row += 1;
+
+        for m in &report.departures {
+            worksheet
+                .write_string(current_row, 0, m.departure_time.as_deref().unwrap_or(""))
+                .map_err(|e| e.to_string())?;
+            worksheet
+                .write_string(current_row, 1, m.return_time.as_deref().unwrap_or(""))
+                .map_err(|e| e.to_string())?;
+            worksheet
+                .write_string(current_row, 2, m.type_name.as_deref().unwrap_or(""))
+                .map_err(|e| e.to_string())?;
+            worksheet
+                .write_string(current_row, 3, m.description.as_deref().unwrap_or(""))
+                .map_err(|e| e.to_string())?;
+            worksheet
+                .write_number(current_row, 4, m.quantity as f64)
+                .map_err(|e| e.to_string())?;
+            worksheet
+                .write_number(current_row, 5, m.length_per_unit as f64)
+                .map_err(|e| e.to_string())?;
+            worksheet
+                .write_string(current_row, 6, m.team_name.as_deref().unwrap_or(""))
+                .map_err(|e| e.to_string())?;
+            current_row += 1;
+        }
+
+        // Bilan stock / besoin par type
+        for balance in &report.balances {
+            let label = format!(
+                "{} : {} sortie(s), pic {} / stock {}",
+                balance.type_name.as_deref().unwrap_or("Type inconnu"),
+                balance.total_out,
+                balance.peak_out,
+                balance.stock
+            );
+            worksheet
+                .write_string(current_row, 2, &label)
+                .map_err(|e| e.to_string())?;
+            if balance.missing > 0 {
+                worksheet
+                    .write_string_with_format(
+                        current_row,
+                        4,
+                        format!("Manque {}", balance.missing),
+                        &missing_format,
+                    )
+                    .map_err(|e| e.to_string())?;
+            }
+            current_row += 1;
+        }
+
+        current_row += 1;
+    }
+
+    let (dir_path, file_name) =
+        utils::create_file_name("chargement_depots".to_string(), "xlsx".to_string());
+
+    if let Some(file_path) = utils::show_save_dialog(&file_name, &dir_path, "xlsx".to_string()) {
+        println!("💾 Saving workbook... {}", file_path.display());
+        workbook.save(file_path).map_err(|e| e.to_string())?;
+        println!("✅ Excel saved successfully!");
+    } else {
+        println!("Save cancelled by user");
+    }
+
+    Ok(())
+}
+
+/// Exporte le calendrier de fermeture des rues (une ligne par portion de rue)
+#[tauri::command]
+pub async fn export_street_closures_excel(
+    app: AppHandle,
+    event_id: String,
+    close_before_minutes: Option<i64>,
+    reopen_after_minutes: Option<i64>,
+) -> Result<(), String> {
+    let pool = db::get_db_pool(&app).await?;
+    let closures = closures::load_street_closures(
+        &pool,
+        &event_id,
+        close_before_minutes,
+        reopen_after_minutes,
+    )
+    .await?;
+    println!(
+        "📊 Export Excel fermetures : {} portion(s) de rue",
+        closures.len()
+    );
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    let header_format = Format::new()
+        .set_bold()
+        .set_background_color(Color::RGB(0xE0E0E0))
+        .set_border(rust_xlsxwriter::FormatBorder::Thin);
+
+    let headers = [
+        "Parcours",
+        "Rue",
+        "Type de voie",
+        "Du km",
+        "Au km",
+        "Fermeture",
+        "Premier coureur",
+        "Dernier coureur",
+        "Réouverture",
+    ];
+
+    for (col, header) in headers.iter().enumerate() {
+        worksheet
+            .write_string_with_format(0, col as u16, *header, &header_format)
+            .map_err(|e| e.to_string())?;
+        worksheet
+            .set_column_width(col as u16, if col == 1 { 30 } else { 18 })
+            .map_err(|e| e.to_string())?;
+    }
+
+    for (index, closure) in closures.iter().enumerate() {
+        let row = index as u32 + 1;
+        worksheet
+            .write_string(row, 0, closure.parcours_name.as_deref().unwrap_or(""))
+            .map_err(|e| e.to_string())?;
+        worksheet
+            .write_string(row, 1, &closure.street)
+            .map_err(|e| e.to_string())?;
+        worksheet
+            .write_string(row, 2, closure.highway.as_deref().unwrap_or(""))
+            .map_err(|e| e.to_string())?;
+        worksheet
+            .write_number(row, 3, (closure.from_m / 10.0).round() / 100.0)
+            .map_err(|e| e.to_string())?;
+        worksheet
+            .write_number(row, 4, (closure.to_m / 10.0).round() / 100.0)
+            .map_err(|e| e.to_string())?;
+        for (col, value) in [
+            &closure.closes_at,
+            &closure.first_passage,
+            &closure.last_passage,
+            &closure.reopens_at,
+        ]
+        .iter()
+        .enumerate()
+        {
+            worksheet
+                .write_string(row, col as u16 + 5, *value)
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    let (dir_path, file_name) =
+        utils::create_file_name("fermeture_rues".to_string(), "xlsx".to_string());
+
+    if let Some(file_path) = utils::show_save_dialog(&file_name, &dir_path, "xlsx".to_string()) {
+        println!("💾 Saving workbook... {}", file_path.display());
+        workbook.save(file_path).map_err(|e| e.to_string())?;
+        println!("✅ Excel saved successfully!");
+    } else {
+        println!("Save cancelled by user");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,433 @@
+// Diagramme de Gantt du planning : une ligne par équipe, une barre par action,
+// fenêtres de passage de la course en surimpression.
+// Le SVG produit ici est exporté tel quel ou intégré au PDF via Typst.
+
+use crate::db;
+use crate::durations::{duration_of, load_action_durations};
+use crate::race::{cumulative_lengths, load_race_parcours};
+use crate::types::ActionStatus;
+use crate::utils::{self, escape_xml, format_datetime};
+use chrono::{Duration, NaiveDateTime};
+use sqlx::{Row, SqlitePool};
+use std::fmt::Write;
+use std::fs;
+use tauri::AppHandle;
+
+const SVG_WIDTH: f64 = 1400.0;
+const LABEL_WIDTH: f64 = 180.0;
+const HEADER_HEIGHT: f64 = 60.0;
+const LANE_HEIGHT: f64 = 34.0;
+const LEGEND_HEIGHT: f64 = 40.0;
+const LEGEND_ENTRY_WIDTH: f64 = 130.0;
+const MARGIN: f64 = 10.0;
+
+/// Pas possibles de la graduation horaire (minutes)
+const TICK_STEPS_MINUTES: [i64; 8] = [15, 30, 60, 120, 180, 360, 720, 1440];
+const MAX_TICKS: i64 = 14;
+
+/// Barre d'une action
+#[derive(Debug, Clone)]
+pub struct GanttBar {
+    pub action_id: String,
+    pub label: String,
+    pub action_type: String,
+    pub status: ActionStatus,
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+/// Ligne d'une équipe
+#[derive(Debug, Clone)]
+pub struct GanttLane {
+    pub team_id: String,
+    pub team_name: String,
+    pub bars: Vec<GanttBar>,
+}
+
+/// Fenêtre de passage de la course (départ du premier coureur, arrivée du dernier)
+#[derive(Debug, Clone)]
+pub struct GanttRaceWindow {
+    pub label: String,
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct GanttChart {
+    pub event_name: String,
+    pub lanes: Vec<GanttLane>,
+    pub race_windows: Vec<GanttRaceWindow>,
+}
+
+// ==================== Fonctions helper publiques et testables ====================
+
+/// Bornes temporelles du diagramme (barres et fenêtres de course), avec une marge
+pub fn chart_bounds(chart: &GanttChart) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    let times = chart
+        .lanes
+        .iter()
+        .flat_map(|l| l.bars.iter().flat_map(|b| [b.start, b.end]))
+        .chain(chart.race_windows.iter().flat_map(|w| [w.start, w.end]));
+
+    let (min, max) = times.fold(None, |acc: Option<(NaiveDateTime, NaiveDateTime)>, t| {
+        Some(match acc {
+            Some((min, max)) => (min.min(t), max.max(t)),
+            None => (t, t),
+        })
+    })?;
+
+    let margin = Duration::minutes(30);
+    Some((min - margin, max + margin))
+}
+
+/// Pas de graduation (minutes) pour ne pas dépasser MAX_TICKS graduations
+pub fn tick_step_minutes(span_minutes: i64) -> i64 {
+    TICK_STEPS_MINUTES
+        .iter()
+        .copied()
+        .find(|step| span_minutes / step <= MAX_TICKS)
+        .unwrap_or(1440)
+}
+
+/// Fenêtre de passage d'un parcours entier : du départ jusqu'au passage du dernier coureur
+/// à l'arrivée (vitesse basse, ou vitesse haute à défaut)
+pub fn race_window(
+    start: NaiveDateTime,
+    length_m: f64,
+    speed_low_kmh: Option<f64>,
+    speed_high_kmh: Option<f64>,
+) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    let speed = speed_low_kmh
+        .filter(|s| *s > 0.0)
+        .or(speed_high_kmh.filter(|s| *s > 0.0))?;
+    let seconds = length_m / (speed * 1000.0 / 3600.0);
+    Some((start, start + Duration::seconds(seconds.round() as i64)))
+}
+
+/// Couleur d'une barre : vert pour une pose, rouge pour une dépose, de plus en plus foncé
+/// de « planifiée » à « terminée ». Les actions bloquées et annulées ont leur propre couleur.
+pub fn bar_color(action_type: &str, status: ActionStatus) -> &'static str {
+    let is_pose = utils::action_kind(action_type) == "pose";
+    match (status, is_pose) {
+        (ActionStatus::Blocked, _) => "#ffb74d",
+        (ActionStatus::Cancelled, _) => "#e0e0e0",
+        (ActionStatus::Done, true) => "#2e7d32",
+        (ActionStatus::EnRoute | ActionStatus::InProgress, true) => "#66bb6a",
+        (ActionStatus::Planned, true) => "#a5d6a7",
+        (ActionStatus::Done, false) => "#c62828",
+        (ActionStatus::EnRoute | ActionStatus::InProgress, false) => "#e57373",
+        (ActionStatus::Planned, false) => "#ef9a9a",
+    }
+}
+
+/// Génère le diagramme au format SVG
+pub fn render_gantt_svg(chart: &GanttChart) -> String {
+    let height = HEADER_HEIGHT + LANE_HEIGHT * chart.lanes.len().max(1) as f64 + LEGEND_HEIGHT;
+    let plot_left = LABEL_WIDTH;
+    let plot_width = SVG_WIDTH - LABEL_WIDTH - MARGIN;
+    let lanes_bottom = HEADER_HEIGHT + LANE_HEIGHT * chart.lanes.len().max(1) as f64;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="Liberation Sans, Arial, sans-serif">"#,
+        w = SVG_WIDTH,
+        h = height
+    )
+    .unwrap();
+    writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#).unwrap();
+    writeln!(
+        svg,
+        r#"<text x="{}" y="22" font-size="18" font-weight="bold">{}</text>"#,
+        MARGIN,
+        escape_xml(&format!("Planning - {}", chart.event_name))
+    )
+    .unwrap();
+
+    let Some((start, end)) = chart_bounds(chart) else {
+        writeln!(
+            svg,
+            r#"<text x="{}" y="{}" font-size="14" fill="gray">Aucune action planifiée</text>"#,
+            plot_left,
+            HEADER_HEIGHT + LANE_HEIGHT / 2.0
+        )
+        .unwrap();
+        svg.push_str("</svg>\n");
+        return svg;
+    };
+
+    let span = (end - start).num_minutes().max(1);
+    let x_of =
+        |t: NaiveDateTime| plot_left + (t - start).num_minutes() as f64 / span as f64 * plot_width;
+
+    // Fond alterné des lignes et noms d'équipes
+    for (index, lane) in chart.lanes.iter().enumerate() {
+        let y = HEADER_HEIGHT + LANE_HEIGHT * index as f64;
+        if index % 2 == 0 {
+            writeln!(
+                svg,
+                r##"<rect x="0" y="{}" width="{}" height="{}" fill="#f5f5f5"/>"##,
+                y, SVG_WIDTH, LANE_HEIGHT
+            )
+            .unwrap();
+        }
+        writeln!(
+            svg,
+            r#"<text x="{}" y="{}" font-size="13" dominant-baseline="middle">{}</text>"#,
+            MARGIN,
+            y + LANE_HEIGHT / 2.0,
+            escape_xml(&lane.team_name)
+        )
+        .unwrap();
+    }
+
+    // Graduation horaire
+    let step = tick_step_minutes(span);
+    let multi_day = start.date() != end.date();
+    let first_tick = start.and_utc().timestamp() / 60;
+    let mut tick_minutes = (first_tick + step - 1) / step * step;
+    while tick_minutes * 60 <= end.and_utc().timestamp() {
+        let Some(tick) = chrono::DateTime::from_timestamp(tick_minutes * 60, 0) else {
+            break;
+        };
+        let tick = tick.naive_utc();
+        let x = x_of(tick);
+        let label = if multi_day {
+            tick.format("%d/%m %H:%M").to_string()
+        } else {
+            tick.format("%H:%M").to_string()
+        };
+        writeln!(
+            svg,
+            r##"<line x1="{x:.1}" y1="{}" x2="{x:.1}" y2="{}" stroke="#cccccc" stroke-width="1"/>"##,
+            HEADER_HEIGHT - 6.0,
+            lanes_bottom,
+            x = x
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{}" font-size="11" text-anchor="middle">{}</text>"#,
+            x,
+            HEADER_HEIGHT - 10.0,
+            label
+        )
+        .unwrap();
+        tick_minutes += step;
+    }
+
+    // Fenêtres de course en surimpression
+    for window in &chart.race_windows {
+        let x1 = x_of(window.start);
+        let x2 = x_of(window.end);
+        writeln!(
+            svg,
+            r##"<rect x="{:.1}" y="{}" width="{:.1}" height="{}" fill="#ff9800" fill-opacity="0.15" stroke="#ff9800" stroke-dasharray="4 3"><title>{}</title></rect>"##,
+            x1,
+            HEADER_HEIGHT,
+            (x2 - x1).max(1.0),
+            lanes_bottom - HEADER_HEIGHT,
+            escape_xml(&format!(
+                "{} : {} → {}",
+                window.label,
+                format_datetime(&window.start),
+                format_datetime(&window.end)
+            ))
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r##"<text x="{:.1}" y="{}" font-size="10" fill="#e65100">{}</text>"##,
+            x1 + 3.0,
+            HEADER_HEIGHT + 10.0,
+            escape_xml(&window.label)
+        )
+        .unwrap();
+    }
+
+    // Barres des actions
+    for (index, lane) in chart.lanes.iter().enumerate() {
+        let y = HEADER_HEIGHT + LANE_HEIGHT * index as f64 + 6.0;
+        for bar in &lane.bars {
+            let x1 = x_of(bar.start);
+            let width = (x_of(bar.end) - x1).max(2.0);
+            writeln!(
+                svg,
+                r##"<rect id="action-{}" x="{:.1}" y="{}" width="{:.1}" height="{}" rx="2" fill="{}" stroke="#555555" stroke-width="0.5"><title>{}</title></rect>"##,
+                escape_xml(&bar.action_id),
+                x1,
+                y,
+                width,
+                LANE_HEIGHT - 12.0,
+                bar_color(&bar.action_type, bar.status),
+                escape_xml(&format!(
+                    "{} - {} ({})",
+                    format_datetime(&bar.start),
+                    bar.label,
+                    bar.status.label()
+                ))
+            )
+            .unwrap();
+        }
+    }
+
+    // Légende
+    let legend_y = lanes_bottom + 14.0;
+    let entries = [
+        ("pose", ActionStatus::Done, "Pose faite"),
+        ("pose", ActionStatus::InProgress, "Pose en cours"),
+        ("pose", ActionStatus::Planned, "Pose à faire"),
+        ("depose", ActionStatus::Done, "Dépose faite"),
+        ("depose", ActionStatus::InProgress, "Dépose en cours"),
+        ("depose", ActionStatus::Planned, "Dépose à faire"),
+        ("pose", ActionStatus::Blocked, "Bloquée"),
+        ("pose", ActionStatus::Cancelled, "Annulée"),
+    ];
+    for (i, (action_type, status, label)) in entries.iter().enumerate() {
+        let x = plot_left + i as f64 * LEGEND_ENTRY_WIDTH;
+        writeln!(
+            svg,
+            r##"<rect x="{}" y="{}" width="14" height="14" fill="{}" stroke="#555555" stroke-width="0.5"/><text x="{}" y="{}" font-size="12">{}</text>"##,
+            x,
+            legend_y,
+            bar_color(action_type, *status),
+            x + 20.0,
+            legend_y + 11.0,
+            label
+        )
+        .unwrap();
+    }
+    let x = plot_left + entries.len() as f64 * LEGEND_ENTRY_WIDTH;
+    writeln!(
+        svg,
+        r##"<rect x="{}" y="{}" width="14" height="14" fill="#ff9800" fill-opacity="0.15" stroke="#ff9800" stroke-dasharray="4 3"/><text x="{}" y="{}" font-size="12">Passage de la course</text>"##,
+        x,
+        legend_y,
+        x + 20.0,
+        legend_y + 11.0
+    )
+    .unwrap();
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+// ==================== Chargement des données ====================
+
+/// Construit le diagramme d'un événement à partir des équipes, actions et parcours
+pub async fn load_gantt_chart(pool: &SqlitePool, event_id: &str) -> Result<GanttChart, String> {
+    let event_name: Option<String> = sqlx::query_scalar("SELECT name FROM event WHERE id = ?")
+        .bind(event_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let team_rows = sqlx::query("SELECT id, name FROM team WHERE event_id = ? ORDER BY name ASC")
+        .bind(event_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut lanes: Vec<GanttLane> = team_rows
+        .into_iter()
+        .map(|row| GanttLane {
+            team_id: row.get("id"),
+            team_name: row.get::<Option<String>, _>("name").unwrap_or_default(),
+            bars: vec![],
+        })
+        .collect();
+
+    let action_rows = sqlx::query(
+        r#"
+        SELECT
+            a.id, a.team_id, a.type, a.scheduled_time,
+            COALESCE(a.status, CASE WHEN a.is_done THEN 'done' ELSE 'planned' END) as status,
+            e.description, t.name as type_name
+        FROM action a
+        JOIN equipement e ON a.equipement_id = e.id
+        LEFT JOIN type t ON e.type_id = t.id
+        WHERE e.event_id = ?
+        ORDER BY a.scheduled_time ASC
+    "#,
+    )
+    .bind(event_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let durations = load_action_durations(pool, Some(event_id), None).await?;
+
+    for row in action_rows {
+        let scheduled_time: Option<String> = row.get("scheduled_time");
+        let Some(start) = scheduled_time.as_deref().and_then(utils::parse_datetime) else {
+            continue;
+        };
+        let team_id: String = row.get("team_id");
+        let Some(lane) = lanes.iter_mut().find(|l| l.team_id == team_id) else {
+            continue;
+        };
+
+        let description: Option<String> = row.get("description");
+        let type_name: Option<String> = row.get("type_name");
+        let action_id: String = row.get("id");
+        let duration = duration_of(&durations, &action_id);
+        let status: String = row.get("status");
+        lane.bars.push(GanttBar {
+            action_id,
+            label: description
+                .filter(|d| !d.is_empty())
+                .or(type_name)
+                .unwrap_or_else(|| "Équipement".to_string()),
+            action_type: row.get::<Option<String>, _>("type").unwrap_or_default(),
+            status: ActionStatus::parse(&status).unwrap_or(ActionStatus::Planned),
+            start,
+            end: start + Duration::minutes(duration),
+        });
+    }
+
+    // Une fenêtre par parcours, couvrant toutes ses vagues de départ
+    let race_windows = load_race_parcours(pool, event_id)
+        .await?
+        .into_iter()
+        .filter_map(|p| {
+            let length = cumulative_lengths(&p.line).last().copied().unwrap_or(0.0);
+            let (start, end) = p
+                .waves
+                .iter()
+                .filter_map(|w| race_window(w.start, length, w.speed_low, w.speed_high))
+                .reduce(|(s1, e1), (s2, e2)| (s1.min(s2), e1.max(e2)))?;
+
+            Some(GanttRaceWindow {
+                label: p.name.unwrap_or_else(|| "Parcours".to_string()),
+                start,
+                end,
+            })
+        })
+        .collect();
+
+    Ok(GanttChart {
+        event_name: event_name.unwrap_or_else(|| "Événement".to_string()),
+        lanes,
+        race_windows,
+    })
+}
+
+/// Exporte le diagramme de Gantt d'un événement au format SVG
+#[tauri::command]
+pub async fn export_gantt_svg(app: AppHandle, event_id: String) -> Result<(), String> {
+    let pool = db::get_db_pool(&app).await?;
+    let chart = load_gantt_chart(&pool, &event_id).await?;
+    let svg = render_gantt_svg(&chart);
+
+    let (dir_path, file_name) =
+        utils::create_file_name(format!("Gantt_{}", chart.event_name), "svg".to_string());
+    if let Some(save_path) = utils::show_save_dialog(&file_name, &dir_path, "svg".to_string()) {
+        fs::write(save_path, svg).map_err(|e| e.to_string())?;
+    }
+
+    println!(
+        "[GANTT] 📊 Diagramme exporté en SVG : {} équipe(s)",
+        chart.lanes.len()
+    );
+    Ok(())
+}
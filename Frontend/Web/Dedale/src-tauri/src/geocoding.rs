@@ -1,6 +1,7 @@
 // Module de géocodage local pour Tauri
 // Recherche d'adresses dans la base SQLite embarquée
 
+use crate::map::parse_linestring_wkt;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -11,6 +12,15 @@ pub struct SearchResult {
     pub display_name: String,
 }
 
+/// Tronçon de rue extrait d'OpenStreetMap (voir tools/extract_addresses.rs)
+#[derive(Debug, Clone)]
+pub struct Street {
+    pub name: String,
+    pub highway: Option<String>,
+    /// (lon, lat) dans l'ordre du way
+    pub line: Vec<(f64, f64)>,
+}
+
 /// Obtient le chemin vers la base de données d'adresses
 fn get_addresses_db_path() -> Result<PathBuf, String> {
     // Obtenir le répertoire de l'exécutable
@@ -175,6 +185,52 @@ pub async fn reverse_geocode(lat: f64, lon: f64) -> Result<Option<String>, Strin
     Ok(nearest_addr)
 }
 
+/// Tronçons de rue dont l'emprise recoupe la zone (lon/lat min et max)
+pub fn load_streets_in_bbox(
+    min_lon: f64,
+    min_lat: f64,
+    max_lon: f64,
+    max_lat: f64,
+) -> Result<Vec<Street>, String> {
+    let db_path = get_addresses_db_path()?;
+    let conn =
+        rusqlite::Connection::open_with_flags(&db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT name, highway, geometry
+             FROM streets
+             WHERE max_lon >= ?1 AND min_lon <= ?3 AND max_lat >= ?2 AND min_lat <= ?4",
+        )
+        .map_err(|e| {
+            format!(
+                "Réseau de rues absent de la base d'adresses (relancer extract_addresses) : {}",
+                e
+            )
+        })?;
+
+    let streets: Vec<Street> = stmt
+        .query_map([min_lon, min_lat, max_lon, max_lat], |row| {
+            let geometry: String = row.get(2)?;
+            Ok(Street {
+                name: row.get(0)?,
+                highway: row.get(1)?,
+                line: parse_linestring_wkt(&geometry),
+            })
+        })
+        .map_err(|e| format!("Erreur d'exécution de la requête: {}", e))?
+        .filter_map(|r| r.ok())
+        .filter(|s| s.line.len() >= 2)
+        .collect();
+
+    println!(
+        "[geocoding] 🛣️ {} tronçon(s) de rue dans la zone",
+        streets.len()
+    );
+    Ok(streets)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,709 @@
+// Échange GeoJSON avec le SIG de la ville
+// L'export écrit une FeatureCollection de toute la géométrie d'un événement (parcours, zones,
+// points, points d'intérêt, équipements) avec l'ensemble des colonnes en propriétés ; la
+// propriété « layer » indique la table d'origine. L'import relit une FeatureCollection,
+// valide chaque géométrie puis crée les lignes correspondantes en une transaction.
+// Les coordonnées peuvent être échangées en WGS84, Lambert-93 ou CC48 (voir projection.rs).
+
+use crate::db;
+use crate::map::{
+    close_ring, format_linestring_wkt, format_polygon_wkt, is_valid_coordinate,
+    parse_linestring_wkt, parse_polygon_wkt,
+};
+use crate::projection::Crs;
+use crate::race::load_equipement_points;
+use crate::utils;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Column, Row, SqlitePool, TypeInfo, ValueRef};
+use std::fs;
+use tauri::AppHandle;
+use uuid::Uuid;
+
+/// Couches (tables) échangées, dans l'ordre d'export
+pub const GEO_LAYERS: [&str; 5] = ["parcours", "zone", "point", "interest", "equipement"];
+
+/// Géométrie d'une entité, coordonnées (lon, lat)
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeoGeometry {
+    Point((f64, f64)),
+    LineString(Vec<(f64, f64)>),
+    /// Anneau extérieur uniquement
+    Polygon(Vec<(f64, f64)>),
+}
+
+impl GeoGeometry {
+    /// Applique une transformation à chaque sommet
+    pub fn map_points(&self, f: impl Fn((f64, f64)) -> (f64, f64)) -> GeoGeometry {
+        match self {
+            GeoGeometry::Point(point) => GeoGeometry::Point(f(*point)),
+            GeoGeometry::LineString(line) => {
+                GeoGeometry::LineString(line.iter().map(|p| f(*p)).collect())
+            }
+            GeoGeometry::Polygon(ring) => {
+                GeoGeometry::Polygon(ring.iter().map(|p| f(*p)).collect())
+            }
+        }
+    }
+}
+
+/// Entité géographique d'un événement
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoFeature {
+    /// Table d'origine : "parcours", "zone", "point", "interest" ou "equipement"
+    pub layer: String,
+    pub id: String,
+    pub name: Option<String>,
+    pub geometry: GeoGeometry,
+    /// Colonnes de la table (hors géométrie)
+    pub properties: Map<String, Value>,
+}
+
+/// Bilan d'un import GeoJSON
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct GeoJsonImportSummary {
+    pub parcours: usize,
+    pub zones: usize,
+    pub points: usize,
+    pub interests: usize,
+    pub equipements: usize,
+}
+
+// ==================== Fonctions helper publiques et testables ====================
+
+fn position(point: &(f64, f64)) -> Value {
+    json!([point.0, point.1])
+}
+
+/// Géométrie GeoJSON (les polygones sont refermés)
+pub fn geometry_to_json(geometry: &GeoGeometry) -> Value {
+    match geometry {
+        GeoGeometry::Point(point) => json!({ "type": "Point", "coordinates": position(point) }),
+        GeoGeometry::LineString(line) => json!({
+            "type": "LineString",
+            "coordinates": line.iter().map(position).collect::<Vec<_>>(),
+        }),
+        GeoGeometry::Polygon(ring) => json!({
+            "type": "Polygon",
+            "coordinates": [close_ring(ring).iter().map(position).collect::<Vec<_>>()],
+        }),
+    }
+}
+
+fn parse_position(value: &Value, crs: Crs) -> Result<(f64, f64), String> {
+    let coords = value
+        .as_array()
+        .filter(|c| c.len() >= 2)
+        .ok_or("position invalide")?;
+    let (x, y) = match (coords[0].as_f64(), coords[1].as_f64()) {
+        (Some(x), Some(y)) => (x, y),
+        _ => return Err("position invalide".to_string()),
+    };
+    let (lon, lat) = crs.unproject((x, y));
+    if is_valid_coordinate(lat, lon) {
+        Ok((lon, lat))
+    } else {
+        Err(format!("coordonnées hors limites ({}, {})", x, y))
+    }
+}
+
+fn parse_positions(value: &Value, crs: Crs) -> Result<Vec<(f64, f64)>, String> {
+    value
+        .as_array()
+        .ok_or("liste de positions attendue")?
+        .iter()
+        .map(|position| parse_position(position, crs))
+        .collect()
+}
+
+/// Lit une géométrie GeoJSON (Point, LineString ou Polygon) exprimée dans `crs`
+/// et la ramène en WGS84
+pub fn geometry_from_json(value: &Value, crs: Crs) -> Result<GeoGeometry, String> {
+    let kind = value
+        .get("type")
+        .and_then(Value::as_str)
+        .ok_or("géométrie sans type")?;
+    let coordinates = value
+        .get("coordinates")
+        .ok_or("géométrie sans coordonnées")?;
+
+    match kind {
+        "Point" => Ok(GeoGeometry::Point(parse_position(coordinates, crs)?)),
+        "LineString" => {
+            let line = parse_positions(coordinates, crs)?;
+            if line.len() < 2 {
+                return Err("une ligne doit avoir au moins 2 positions".to_string());
+            }
+            Ok(GeoGeometry::LineString(line))
+        }
+        "Polygon" => {
+            let outer = coordinates
+                .as_array()
+                .and_then(|rings| rings.first())
+                .ok_or("polygone sans anneau")?;
+            let ring = close_ring(&parse_positions(outer, crs)?);
+            if ring.len() < 4 {
+                return Err("un polygone doit avoir au moins 3 sommets".to_string());
+            }
+            Ok(GeoGeometry::Polygon(ring))
+        }
+        other => Err(format!("type de géométrie non pris en charge : {}", other)),
+    }
+}
+
+/// Vérifie qu'une géométrie convient à la couche
+pub fn layer_accepts(layer: &str, geometry: &GeoGeometry) -> bool {
+    matches!(
+        (layer, geometry),
+        ("parcours", GeoGeometry::LineString(_))
+            | ("zone", GeoGeometry::Polygon(_))
+            | ("point", GeoGeometry::Point(_))
+            | ("interest", GeoGeometry::Point(_))
+            | ("equipement", GeoGeometry::LineString(_))
+            | ("equipement", GeoGeometry::Point(_))
+    )
+}
+
+/// Couche par défaut d'une géométrie sans propriété « layer »
+pub fn default_layer(geometry: &GeoGeometry) -> &'static str {
+    match geometry {
+        GeoGeometry::Point(_) => "point",
+        GeoGeometry::LineString(_) => "parcours",
+        GeoGeometry::Polygon(_) => "zone",
+    }
+}
+
+/// Feature GeoJSON d'une entité
+pub fn feature_to_json(feature: &GeoFeature) -> Value {
+    let mut properties = feature.properties.clone();
+    properties.insert("layer".to_string(), json!(feature.layer));
+    properties.insert("id".to_string(), json!(feature.id));
+    if let Some(name) = &feature.name {
+        properties.entry("name").or_insert_with(|| json!(name));
+    }
+
+    json!({
+        "type": "Feature",
+        "id": feature.id,
+        "geometry": geometry_to_json(&feature.geometry),
+        "properties": properties,
+    })
+}
+
+/// FeatureCollection d'un événement, coordonnées projetées dans `crs`.
+/// Un système projeté est déclaré par le membre « crs » (nom URN OGC).
+pub fn feature_collection(name: &str, features: &[GeoFeature], crs: Crs) -> Value {
+    let features: Vec<Value> = features
+        .iter()
+        .map(|feature| {
+            let mut projected = feature.clone();
+            projected.geometry = feature.geometry.map_points(|point| crs.project(point));
+            feature_to_json(&projected)
+        })
+        .collect();
+
+    let mut collection = json!({
+        "type": "FeatureCollection",
+        "name": name,
+        "features": features,
+    });
+    if crs.is_projected() {
+        collection["crs"] = json!({ "type": "name", "properties": { "name": crs.urn() } });
+    }
+    collection
+}
+
+/// Système déclaré par le membre « crs » d'une FeatureCollection (absent : WGS84)
+pub fn declared_crs(root: &Value) -> Result<Crs, String> {
+    match root
+        .get("crs")
+        .and_then(|crs| crs.get("properties"))
+        .and_then(|properties| properties.get("name"))
+        .and_then(Value::as_str)
+    {
+        Some(name) => {
+            Crs::from_code(name).ok_or_else(|| format!("Système de coordonnées inconnu : {}", name))
+        }
+        None => Ok(Crs::Wgs84),
+    }
+}
+
+/// Relit une FeatureCollection. Les entités invalides sont listées dans le second élément
+/// (« Entité n : raison ») ; les identifiants d'origine sont conservés mais ne sont pas réutilisés.
+/// Sans système imposé, celui déclaré par le fichier s'applique.
+pub fn parse_feature_collection(
+    text: &str,
+    crs: Option<Crs>,
+) -> Result<(Vec<GeoFeature>, Vec<String>), String> {
+    let root: Value =
+        serde_json::from_str(text).map_err(|e| format!("GeoJSON illisible : {}", e))?;
+    if root.get("type").and_then(Value::as_str) != Some("FeatureCollection") {
+        return Err("Le fichier n'est pas une FeatureCollection GeoJSON".to_string());
+    }
+    let crs = match crs {
+        Some(crs) => crs,
+        None => declared_crs(&root)?,
+    };
+    let items = root
+        .get("features")
+        .and_then(Value::as_array)
+        .ok_or("FeatureCollection sans « features »")?;
+
+    let mut features = Vec::new();
+    let mut errors = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        let geometry = match item.get("geometry").map(|g| geometry_from_json(g, crs)) {
+            Some(Ok(geometry)) => geometry,
+            Some(Err(e)) => {
+                errors.push(format!("Entité {} : {}", index + 1, e));
+                continue;
+            }
+            None => {
+                errors.push(format!("Entité {} : géométrie absente", index + 1));
+                continue;
+            }
+        };
+
+        let properties = item
+            .get("properties")
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        let layer =
+            prop_str(&properties, "layer").unwrap_or_else(|| default_layer(&geometry).to_string());
+
+        if !GEO_LAYERS.contains(&layer.as_str()) {
+            errors.push(format!(
+                "Entité {} : couche inconnue « {} »",
+                index + 1,
+                layer
+            ));
+            continue;
+        }
+        if !layer_accepts(&layer, &geometry) {
+            errors.push(format!(
+                "Entité {} : géométrie incompatible avec la couche « {} »",
+                index + 1,
+                layer
+            ));
+            continue;
+        }
+
+        features.push(GeoFeature {
+            id: prop_str(&properties, "id").unwrap_or_default(),
+            name: prop_str(&properties, "name"),
+            layer,
+            geometry,
+            properties,
+        });
+    }
+    Ok((features, errors))
+}
+
+/// Propriété texte (les nombres sont convertis)
+pub fn prop_str(properties: &Map<String, Value>, key: &str) -> Option<String> {
+    match properties.get(key)? {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Propriété numérique
+pub fn prop_f64(properties: &Map<String, Value>, key: &str) -> Option<f64> {
+    match properties.get(key)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Propriété entière (les décimaux sont arrondis)
+pub fn prop_i64(properties: &Map<String, Value>, key: &str) -> Option<i64> {
+    prop_f64(properties, key).map(|v| v.round() as i64)
+}
+
+/// Propriété booléenne (true/false ou 0/1)
+pub fn prop_bool(properties: &Map<String, Value>, key: &str) -> Option<bool> {
+    match properties.get(key)? {
+        Value::Bool(b) => Some(*b),
+        Value::Number(n) => n.as_f64().map(|v| v != 0.0),
+        _ => None,
+    }
+}
+
+/// Type d'équipement d'une entité importée : par ID, sinon par nom (insensible à la casse)
+pub fn resolve_type_id(
+    properties: &Map<String, Value>,
+    types: &[(String, Option<String>)],
+) -> Option<String> {
+    if let Some(id) = prop_str(properties, "type_id") {
+        if types.iter().any(|(type_id, _)| *type_id == id) {
+            return Some(id);
+        }
+    }
+    let name = prop_str(properties, "type_name")?.to_lowercase();
+    types
+        .iter()
+        .find(|(_, type_name)| {
+            type_name
+                .as_deref()
+                .is_some_and(|t| t.to_lowercase() == name)
+        })
+        .map(|(id, _)| id.clone())
+}
+
+// ==================== Chargement des données ====================
+
+/// Colonnes d'une ligne SQLite en propriétés JSON, hors `skip`
+fn row_properties(row: &SqliteRow, skip: &[&str]) -> Map<String, Value> {
+    let mut properties = Map::new();
+    for column in row.columns() {
+        let name = column.name();
+        if skip.contains(&name) {
+            continue;
+        }
+        let Ok(raw) = row.try_get_raw(column.ordinal()) else {
+            continue;
+        };
+        let value = if raw.is_null() {
+            Value::Null
+        } else {
+            match raw.type_info().name() {
+                "INTEGER" | "BOOLEAN" => json!(row.get::<i64, _>(column.ordinal())),
+                "REAL" => json!(row.get::<f64, _>(column.ordinal())),
+                _ => row
+                    .try_get::<String, _>(column.ordinal())
+                    .map(Value::from)
+                    .unwrap_or(Value::Null),
+            }
+        };
+        properties.insert(name.to_string(), value);
+    }
+    properties
+}
+
+/// Toute la géométrie d'un événement (parcours, zones, points, points d'intérêt, équipements)
+pub async fn load_event_features(
+    pool: &SqlitePool,
+    event_id: &str,
+) -> Result<Vec<GeoFeature>, String> {
+    let mut features = Vec::new();
+
+    let rows = sqlx::query("SELECT * FROM parcours WHERE event_id = ?")
+        .bind(event_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    for row in rows {
+        let geometry: Option<String> = row.get("geometry_json");
+        let line = parse_linestring_wkt(geometry.as_deref().unwrap_or(""));
+        if line.len() >= 2 {
+            features.push(GeoFeature {
+                layer: "parcours".to_string(),
+                id: row.get("id"),
+                name: row.get("name"),
+                geometry: GeoGeometry::LineString(line),
+                properties: row_properties(&row, &["geometry_json", "event_id"]),
+            });
+        }
+    }
+
+    let rows = sqlx::query("SELECT * FROM zone WHERE event_id = ?")
+        .bind(event_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    for row in rows {
+        let geometry: Option<String> = row.get("geometry_json");
+        let ring = parse_polygon_wkt(geometry.as_deref().unwrap_or(""));
+        if ring.len() >= 3 {
+            features.push(GeoFeature {
+                layer: "zone".to_string(),
+                id: row.get("id"),
+                name: row.get("name"),
+                geometry: GeoGeometry::Polygon(ring),
+                properties: row_properties(&row, &["geometry_json", "event_id"]),
+            });
+        }
+    }
+
+    let rows = sqlx::query("SELECT * FROM point WHERE event_id = ?")
+        .bind(event_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    for row in rows {
+        features.push(GeoFeature {
+            layer: "point".to_string(),
+            id: row.get("id"),
+            name: row.get("name"),
+            geometry: GeoGeometry::Point((row.get("x"), row.get("y"))),
+            properties: row_properties(&row, &["x", "y", "event_id"]),
+        });
+    }
+
+    let rows = sqlx::query("SELECT * FROM interest WHERE event_id = ?")
+        .bind(event_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    for row in rows {
+        features.push(GeoFeature {
+            layer: "interest".to_string(),
+            id: row.get("id"),
+            name: row.get("description"),
+            geometry: GeoGeometry::Point((row.get("x"), row.get("y"))),
+            properties: row_properties(&row, &["x", "y", "event_id"]),
+        });
+    }
+
+    let mut coordinates = load_equipement_points(pool, event_id).await?;
+    let rows = sqlx::query(
+        r#"
+        SELECT e.*, t.name AS type_name
+        FROM equipement e
+        LEFT JOIN type t ON e.type_id = t.id
+        WHERE e.event_id = ?
+        "#,
+    )
+    .bind(event_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    for row in rows {
+        let id: String = row.get("id");
+        let geometry = match coordinates.remove(&id) {
+            Some(line) if line.len() >= 2 => GeoGeometry::LineString(line),
+            Some(line) if line.len() == 1 => GeoGeometry::Point(line[0]),
+            _ => continue,
+        };
+        features.push(GeoFeature {
+            layer: "equipement".to_string(),
+            id,
+            name: row.get("type_name"),
+            geometry,
+            properties: row_properties(&row, &["event_id"]),
+        });
+    }
+
+    Ok(features)
+}
+
+/// Nom d'un événement (pour les noms de fichiers et de collections)
+pub async fn load_event_name(pool: &SqlitePool, event_id: &str) -> Result<String, String> {
+    let name: Option<String> = sqlx::query_scalar("SELECT name FROM event WHERE id = ?")
+        .bind(event_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .flatten();
+    Ok(name.unwrap_or_else(|| "Événement".to_string()))
+}
+
+// ==================== Commandes Tauri ====================
+
+/// Exporte toute la géométrie d'un événement en FeatureCollection GeoJSON.
+/// `crs` : "4326" (défaut), "2154" (Lambert-93) ou "3948" (CC48).
+#[tauri::command]
+pub async fn export_event_geojson(
+    app: AppHandle,
+    event_id: String,
+    crs: Option<String>,
+) -> Result<(), String> {
+    let crs = Crs::parse(crs.as_deref())?;
+    let pool = db::get_db_pool(&app).await?;
+    let name = load_event_name(&pool, &event_id).await?;
+    let features = load_event_features(&pool, &event_id).await?;
+
+    let collection = feature_collection(&name, &features, crs);
+    let content = serde_json::to_string_pretty(&collection).map_err(|e| e.to_string())?;
+
+    let (dir_path, file_name) = utils::create_file_name(name.clone(), "geojson".to_string());
+    if let Some(save_path) = utils::show_save_dialog(&file_name, &dir_path, "geojson".to_string()) {
+        fs::write(save_path, content).map_err(|e| e.to_string())?;
+    }
+
+    println!(
+        "[GEOJSON] 🗺️ Événement '{}' exporté : {} entité(s) ({})",
+        name,
+        features.len(),
+        crs.name()
+    );
+    Ok(())
+}
+
+/// Importe une FeatureCollection GeoJSON dans un événement.
+/// Le fichier est entièrement validé avant toute écriture ; de nouveaux identifiants sont créés.
+/// `crs` impose le système des coordonnées ; sinon celui déclaré par le fichier (ou WGS84).
+#[tauri::command]
+pub async fn import_event_geojson(
+    app: AppHandle,
+    event_id: String,
+    file_path: String,
+    crs: Option<String>,
+) -> Result<GeoJsonImportSummary, String> {
+    let crs = crs
+        .as_deref()
+        .map(|code| Crs::parse(Some(code)))
+        .transpose()?;
+    let pool = db::get_db_pool(&app).await?;
+
+    let text = fs::read_to_string(&file_path).map_err(|e| e.to_string())?;
+    let (features, mut errors) = parse_feature_collection(&text, crs)?;
+
+    let types: Vec<(String, Option<String>)> = sqlx::query("SELECT id, name FROM type")
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|row| (row.get("id"), row.get("name")))
+        .collect();
+
+    let mut equipement_types = Vec::new();
+    for feature in features.iter().filter(|f| f.layer == "equipement") {
+        match resolve_type_id(&feature.properties, &types) {
+            Some(type_id) => equipement_types.push(type_id),
+            None => errors.push(format!(
+                "Équipement {} : type inconnu",
+                feature.name.as_deref().unwrap_or(&feature.id)
+            )),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(format!("Import GeoJSON refusé :\n{}", errors.join("\n")));
+    }
+
+    let mut summary = GeoJsonImportSummary::default();
+    let mut equipement_types = equipement_types.into_iter();
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    for feature in &features {
+        let props = &feature.properties;
+        let id = Uuid::new_v4().to_string();
+
+        match (feature.layer.as_str(), &feature.geometry) {
+            ("parcours", GeoGeometry::LineString(line)) => {
+                sqlx::query(
+                    r#"INSERT INTO parcours (id, event_id, name, color, start_time, speed_low, speed_high, geometry_json)
+                       VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+                )
+                .bind(&id)
+                .bind(&event_id)
+                .bind(&feature.name)
+                .bind(prop_str(props, "color"))
+                .bind(prop_i64(props, "start_time"))
+                .bind(prop_f64(props, "speed_low"))
+                .bind(prop_f64(props, "speed_high"))
+                .bind(format_linestring_wkt(line))
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Erreur lors de l'import d'un parcours : {}", e))?;
+                summary.parcours += 1;
+            }
+            ("zone", GeoGeometry::Polygon(ring)) => {
+                sqlx::query(
+                    r#"INSERT INTO zone (id, event_id, name, color, description, restricted, geometry_json)
+                       VALUES (?, ?, ?, ?, ?, ?, ?)"#,
+                )
+                .bind(&id)
+                .bind(&event_id)
+                .bind(&feature.name)
+                .bind(prop_str(props, "color"))
+                .bind(prop_str(props, "description"))
+                .bind(prop_bool(props, "restricted").unwrap_or(false))
+                .bind(format_polygon_wkt(ring))
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Erreur lors de l'import d'une zone : {}", e))?;
+                summary.zones += 1;
+            }
+            ("point", GeoGeometry::Point((x, y))) => {
+                sqlx::query(
+                    r#"INSERT INTO point (id, event_id, x, y, name, comment, type, status)
+                       VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+                )
+                .bind(&id)
+                .bind(&event_id)
+                .bind(x)
+                .bind(y)
+                .bind(feature.name.as_deref().unwrap_or("Nouveau point"))
+                .bind(prop_str(props, "comment"))
+                .bind(prop_str(props, "type"))
+                .bind(prop_bool(props, "status").unwrap_or(false))
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Erreur lors de l'import d'un point : {}", e))?;
+                summary.points += 1;
+            }
+            ("interest", GeoGeometry::Point((x, y))) => {
+                sqlx::query(
+                    "INSERT INTO interest (id, event_id, x, y, description) VALUES (?, ?, ?, ?, ?)",
+                )
+                .bind(&id)
+                .bind(&event_id)
+                .bind(x)
+                .bind(y)
+                .bind(prop_str(props, "description").or_else(|| feature.name.clone()))
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Erreur lors de l'import d'un point d'intérêt : {}", e))?;
+                summary.interests += 1;
+            }
+            ("equipement", geometry) => {
+                let type_id = equipement_types.next().unwrap_or_default();
+                sqlx::query(
+                    r#"INSERT INTO equipement (id, event_id, type_id, quantity, length_per_unit, description, date_pose, date_depose)
+                       VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+                )
+                .bind(&id)
+                .bind(&event_id)
+                .bind(&type_id)
+                .bind(prop_i64(props, "quantity"))
+                .bind(prop_i64(props, "length_per_unit"))
+                .bind(prop_str(props, "description"))
+                .bind(prop_str(props, "date_pose"))
+                .bind(prop_str(props, "date_depose"))
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Erreur lors de l'import d'un équipement : {}", e))?;
+
+                let coordinates = match geometry {
+                    GeoGeometry::LineString(line) => line.clone(),
+                    GeoGeometry::Point(point) => vec![*point],
+                    GeoGeometry::Polygon(_) => vec![],
+                };
+                for (index, (x, y)) in coordinates.iter().enumerate() {
+                    sqlx::query(
+                        "INSERT INTO equipement_coordinate (id, equipement_id, x, y, order_index) VALUES (?, ?, ?, ?, ?)",
+                    )
+                    .bind(Uuid::new_v4().to_string())
+                    .bind(&id)
+                    .bind(x)
+                    .bind(y)
+                    .bind(index as i64)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| format!("Erreur lors de l'import d'un équipement : {}", e))?;
+                }
+                summary.equipements += 1;
+            }
+            _ => {}
+        }
+    }
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    println!(
+        "[GEOJSON] ✅ Import dans l'événement {} : {} parcours, {} zone(s), {} point(s), {} point(s) d'intérêt, {} équipement(s)",
+        event_id,
+        summary.parcours,
+        summary.zones,
+        summary.points,
+        summary.interests,
+        summary.equipements
+    );
+    Ok(summary)
+}
@@ -0,0 +1,427 @@
+// Export GeoPackage (.gpkg) pour le service SIG
+// Le fichier est une base SQLite écrite directement avec rusqlite : tables gpkg_* du standard
+// OGC (1.3), une table par couche (parcours, zones, points, points d'intérêt, lignes et
+// sommets d'équipements) avec colonnes d'attributs typées et géométries GeoPackage (WKB).
+// Les géométries sont écrites en WGS84, Lambert-93 ou CC48 selon le système choisi.
+
+use crate::db;
+use crate::geojson::{load_event_features, load_event_name, GeoFeature, GeoGeometry};
+use crate::map::close_ring;
+use crate::projection::Crs;
+use crate::utils;
+use chrono::Utc;
+use rusqlite::types::Value as SqlValue;
+use serde_json::{json, Map, Value};
+use std::path::Path;
+use tauri::AppHandle;
+
+/// Système de coordonnées déclaré dans gpkg_spatial_ref_sys
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpkgSrs {
+    pub srs_id: i32,
+    pub name: &'static str,
+    pub organization: &'static str,
+    pub code: i32,
+    /// Définition WKT (OGC 01-009)
+    pub definition: &'static str,
+}
+
+/// Couche (table) d'un GeoPackage
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpkgLayer {
+    pub table: String,
+    /// "POINT", "LINESTRING" ou "POLYGON"
+    pub geometry_type: &'static str,
+    /// Colonnes d'attributs (nom, type SQLite)
+    pub columns: Vec<(String, &'static str)>,
+    /// Géométrie et valeurs dans l'ordre des colonnes
+    pub rows: Vec<(GeoGeometry, Vec<Value>)>,
+}
+
+// ==================== Fonctions helper publiques et testables ====================
+
+/// Entrée gpkg_spatial_ref_sys d'un système de coordonnées
+pub fn gpkg_srs(crs: Crs) -> GpkgSrs {
+    GpkgSrs {
+        srs_id: crs.epsg() as i32,
+        name: crs.name(),
+        organization: "EPSG",
+        code: crs.epsg() as i32,
+        definition: crs.wkt(),
+    }
+}
+
+fn push_coords(wkb: &mut Vec<u8>, points: &[(f64, f64)]) {
+    wkb.extend_from_slice(&(points.len() as u32).to_le_bytes());
+    for (x, y) in points {
+        wkb.extend_from_slice(&x.to_le_bytes());
+        wkb.extend_from_slice(&y.to_le_bytes());
+    }
+}
+
+/// Géométrie WKB (petit-boutiste, 2D)
+pub fn geometry_wkb(geometry: &GeoGeometry) -> Vec<u8> {
+    let mut wkb = vec![1u8];
+    match geometry {
+        GeoGeometry::Point((x, y)) => {
+            wkb.extend_from_slice(&1u32.to_le_bytes());
+            wkb.extend_from_slice(&x.to_le_bytes());
+            wkb.extend_from_slice(&y.to_le_bytes());
+        }
+        GeoGeometry::LineString(line) => {
+            wkb.extend_from_slice(&2u32.to_le_bytes());
+            push_coords(&mut wkb, line);
+        }
+        GeoGeometry::Polygon(ring) => {
+            wkb.extend_from_slice(&3u32.to_le_bytes());
+            wkb.extend_from_slice(&1u32.to_le_bytes());
+            push_coords(&mut wkb, &close_ring(ring));
+        }
+    }
+    wkb
+}
+
+fn geometry_points(geometry: &GeoGeometry) -> &[(f64, f64)] {
+    match geometry {
+        GeoGeometry::Point(point) => std::slice::from_ref(point),
+        GeoGeometry::LineString(line) => line,
+        GeoGeometry::Polygon(ring) => ring,
+    }
+}
+
+/// Emprise (min_x, min_y, max_x, max_y) d'un ensemble de géométries
+pub fn geometries_envelope<'a>(
+    geometries: impl IntoIterator<Item = &'a GeoGeometry>,
+) -> Option<(f64, f64, f64, f64)> {
+    geometries
+        .into_iter()
+        .flat_map(geometry_points)
+        .fold(None, |envelope, &(x, y)| {
+            Some(match envelope {
+                None => (x, y, x, y),
+                Some((min_x, min_y, max_x, max_y)) => {
+                    (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+                }
+            })
+        })
+}
+
+/// Géométrie au format binaire GeoPackage : en-tête "GP", SRS, emprise (sauf points), WKB
+pub fn gpkg_geometry(geometry: &GeoGeometry, srs_id: i32) -> Vec<u8> {
+    let mut blob = vec![b'G', b'P', 0];
+    match geometry {
+        // Pas d'emprise pour un point ; bit 0 = petit-boutiste
+        GeoGeometry::Point(_) => {
+            blob.push(0b0000_0001);
+            blob.extend_from_slice(&srs_id.to_le_bytes());
+        }
+        // Emprise [min_x, max_x, min_y, max_y]
+        _ => {
+            blob.push(0b0000_0011);
+            blob.extend_from_slice(&srs_id.to_le_bytes());
+            let (min_x, min_y, max_x, max_y) = geometries_envelope([geometry]).unwrap_or_default();
+            for value in [min_x, max_x, min_y, max_y] {
+                blob.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+    }
+    blob.extend_from_slice(&geometry_wkb(geometry));
+    blob
+}
+
+/// Type SQLite d'une colonne d'après ses valeurs (INTEGER, REAL ou TEXT ; TEXT si toutes nulles)
+pub fn column_type<'a>(values: impl IntoIterator<Item = &'a Value>) -> &'static str {
+    let mut column_type = None;
+    for value in values {
+        match value {
+            Value::Null => {}
+            Value::Bool(_) => {
+                column_type.get_or_insert("INTEGER");
+            }
+            Value::Number(n) if n.is_i64() || n.is_u64() => {
+                column_type.get_or_insert("INTEGER");
+            }
+            Value::Number(_) => column_type = Some("REAL"),
+            _ => return "TEXT",
+        }
+    }
+    column_type.unwrap_or("TEXT")
+}
+
+fn build_layer(
+    table: &str,
+    geometry_type: &'static str,
+    rows: Vec<(GeoGeometry, Map<String, Value>)>,
+) -> GpkgLayer {
+    let mut names: Vec<String> = Vec::new();
+    for (_, properties) in &rows {
+        for key in properties.keys() {
+            if !names.contains(key) {
+                names.push(key.clone());
+            }
+        }
+    }
+
+    let columns = names
+        .iter()
+        .map(|name| {
+            let values = rows.iter().filter_map(|(_, p)| p.get(name));
+            (name.clone(), column_type(values))
+        })
+        .collect();
+
+    let rows = rows
+        .into_iter()
+        .map(|(geometry, properties)| {
+            let values = names
+                .iter()
+                .map(|name| properties.get(name).cloned().unwrap_or(Value::Null))
+                .collect();
+            (geometry, values)
+        })
+        .collect();
+
+    GpkgLayer {
+        table: table.to_string(),
+        geometry_type,
+        columns,
+        rows,
+    }
+}
+
+/// Répartit les entités d'un événement en couches GeoPackage.
+/// Les équipements donnent deux couches : leurs lignes (2 points ou plus) et tous leurs sommets.
+/// Les géométries sont projetées dans `crs`.
+pub fn gpkg_layers(features: &[GeoFeature], crs: Crs) -> Vec<GpkgLayer> {
+    let rows_for = |layer: &str| -> Vec<(GeoGeometry, Map<String, Value>)> {
+        features
+            .iter()
+            .filter(|f| f.layer == layer)
+            .map(|f| {
+                let mut properties = f.properties.clone();
+                properties.insert("id".to_string(), json!(f.id));
+                (
+                    f.geometry.map_points(|point| crs.project(point)),
+                    properties,
+                )
+            })
+            .collect()
+    };
+
+    let equipements = rows_for("equipement");
+    let lines = equipements
+        .iter()
+        .filter(|(g, _)| matches!(g, GeoGeometry::LineString(_)))
+        .cloned()
+        .collect();
+    let nodes = equipements
+        .iter()
+        .flat_map(|(geometry, properties)| {
+            geometry_points(geometry)
+                .iter()
+                .enumerate()
+                .map(move |(index, point)| {
+                    let mut node = Map::new();
+                    node.insert("equipement_id".to_string(), properties["id"].clone());
+                    node.insert(
+                        "type_name".to_string(),
+                        properties.get("type_name").cloned().unwrap_or(Value::Null),
+                    );
+                    node.insert("order_index".to_string(), json!(index));
+                    (GeoGeometry::Point(*point), node)
+                })
+        })
+        .collect();
+
+    vec![
+        build_layer("parcours", "LINESTRING", rows_for("parcours")),
+        build_layer("zones", "POLYGON", rows_for("zone")),
+        build_layer("points", "POINT", rows_for("point")),
+        build_layer("interest_points", "POINT", rows_for("interest")),
+        build_layer("equipement_lines", "LINESTRING", lines),
+        build_layer("equipement_nodes", "POINT", nodes),
+    ]
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn sql_value(value: &Value) -> SqlValue {
+    match value {
+        Value::Null => SqlValue::Null,
+        Value::Bool(b) => SqlValue::Integer(*b as i64),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => SqlValue::Integer(i),
+            None => SqlValue::Real(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) => SqlValue::Text(s.clone()),
+        other => SqlValue::Text(other.to_string()),
+    }
+}
+
+/// Écrit un GeoPackage complet (le fichier existant est remplacé)
+pub fn write_geopackage(path: &Path, layers: &[GpkgLayer], srs: &GpkgSrs) -> Result<(), String> {
+    if path.exists() {
+        std::fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+    let mut conn = rusqlite::Connection::open(path)
+        .map_err(|e| format!("Erreur création GeoPackage: {}", e))?;
+
+    // 0x47504B47 = "GPKG", version 1.3.0
+    conn.execute_batch(
+        r#"
+        PRAGMA application_id = 1196444487;
+        PRAGMA user_version = 10300;
+        CREATE TABLE gpkg_spatial_ref_sys (
+            srs_name TEXT NOT NULL,
+            srs_id INTEGER NOT NULL PRIMARY KEY,
+            organization TEXT NOT NULL,
+            organization_coordsys_id INTEGER NOT NULL,
+            definition TEXT NOT NULL,
+            description TEXT
+        );
+        CREATE TABLE gpkg_contents (
+            table_name TEXT NOT NULL PRIMARY KEY,
+            data_type TEXT NOT NULL,
+            identifier TEXT UNIQUE,
+            description TEXT DEFAULT '',
+            last_change DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
+            min_x DOUBLE, min_y DOUBLE, max_x DOUBLE, max_y DOUBLE,
+            srs_id INTEGER,
+            CONSTRAINT fk_gc_r_srs_id FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys(srs_id)
+        );
+        CREATE TABLE gpkg_geometry_columns (
+            table_name TEXT NOT NULL,
+            column_name TEXT NOT NULL,
+            geometry_type_name TEXT NOT NULL,
+            srs_id INTEGER NOT NULL,
+            z TINYINT NOT NULL,
+            m TINYINT NOT NULL,
+            CONSTRAINT pk_geom_cols PRIMARY KEY (table_name, column_name),
+            CONSTRAINT fk_gc_tn FOREIGN KEY (table_name) REFERENCES gpkg_contents(table_name),
+            CONSTRAINT fk_gc_srs FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys (srs_id)
+        );
+        INSERT INTO gpkg_spatial_ref_sys VALUES
+            ('Undefined cartesian SRS', -1, 'NONE', -1, 'undefined', 'undefined cartesian coordinate reference system'),
+            ('Undefined geographic SRS', 0, 'NONE', 0, 'undefined', 'undefined geographic coordinate reference system');
+        "#,
+    )
+    .map_err(|e| format!("Erreur création GeoPackage: {}", e))?;
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    // WGS 84 est toujours déclaré (exigence du standard), en plus du système des couches
+    for entry in [gpkg_srs(Crs::Wgs84), srs.clone()] {
+        if entry.srs_id > 0 {
+            tx.execute(
+                "INSERT OR REPLACE INTO gpkg_spatial_ref_sys VALUES (?1, ?2, ?3, ?4, ?5, NULL)",
+                rusqlite::params![
+                    entry.name,
+                    entry.srs_id,
+                    entry.organization,
+                    entry.code,
+                    entry.definition
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+    }
+
+    let now = Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
+
+    for layer in layers {
+        let table = quote_identifier(&layer.table);
+        let columns: String = layer
+            .columns
+            .iter()
+            .map(|(name, kind)| format!(", {} {}", quote_identifier(name), kind))
+            .collect();
+        tx.execute(
+            &format!(
+                "CREATE TABLE {} (fid INTEGER PRIMARY KEY AUTOINCREMENT, geom {}{})",
+                table, layer.geometry_type, columns
+            ),
+            [],
+        )
+        .map_err(|e| format!("Erreur création couche {}: {}", layer.table, e))?;
+
+        let envelope = geometries_envelope(layer.rows.iter().map(|(g, _)| g));
+        tx.execute(
+            r#"INSERT INTO gpkg_contents (table_name, data_type, identifier, last_change, min_x, min_y, max_x, max_y, srs_id)
+               VALUES (?1, 'features', ?1, ?2, ?3, ?4, ?5, ?6, ?7)"#,
+            rusqlite::params![
+                layer.table,
+                now,
+                envelope.map(|e| e.0),
+                envelope.map(|e| e.1),
+                envelope.map(|e| e.2),
+                envelope.map(|e| e.3),
+                srs.srs_id
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        tx.execute(
+            "INSERT INTO gpkg_geometry_columns VALUES (?1, 'geom', ?2, ?3, 0, 0)",
+            rusqlite::params![layer.table, layer.geometry_type, srs.srs_id],
+        )
+        .map_err(|e| e.to_string())?;
+
+        let names: String = layer
+            .columns
+            .iter()
+            .map(|(name, _)| format!(", {}", quote_identifier(name)))
+            .collect();
+        let placeholders = ", ?".repeat(layer.columns.len());
+        let mut stmt = tx
+            .prepare(&format!(
+                "INSERT INTO {} (geom{}) VALUES (?{})",
+                table, names, placeholders
+            ))
+            .map_err(|e| e.to_string())?;
+
+        for (geometry, values) in &layer.rows {
+            let mut params = vec![SqlValue::Blob(gpkg_geometry(geometry, srs.srs_id))];
+            params.extend(values.iter().map(sql_value));
+            stmt.execute(rusqlite::params_from_iter(params))
+                .map_err(|e| format!("Erreur écriture couche {}: {}", layer.table, e))?;
+        }
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// ==================== Commandes Tauri ====================
+
+/// Exporte un événement en GeoPackage (une couche par type d'entité).
+/// `crs` : "4326" (défaut), "2154" (Lambert-93) ou "3948" (CC48).
+#[tauri::command]
+pub async fn export_event_geopackage(
+    app: AppHandle,
+    event_id: String,
+    crs: Option<String>,
+) -> Result<(), String> {
+    let crs = Crs::parse(crs.as_deref())?;
+    let pool = db::get_db_pool(&app).await?;
+    let name = load_event_name(&pool, &event_id).await?;
+    let features = load_event_features(&pool, &event_id).await?;
+    let layers = gpkg_layers(&features, crs);
+
+    let (dir_path, file_name) = utils::create_file_name(name.clone(), "gpkg".to_string());
+    if let Some(save_path) = utils::show_save_dialog(&file_name, &dir_path, "gpkg".to_string()) {
+        write_geopackage(&save_path, &layers, &gpkg_srs(crs))?;
+    }
+
+    println!(
+        "[GPKG] 🗃️ Événement '{}' exporté en GeoPackage ({}) : {}",
+        name,
+        crs.name(),
+        layers
+            .iter()
+            .map(|l| format!("{} {}", l.rows.len(), l.table))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    Ok(())
+}
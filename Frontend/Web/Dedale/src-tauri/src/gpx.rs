@@ -0,0 +1,325 @@
+// Échange GPX des parcours
+// Import d'une trace (trk) ou d'une route (rte) enregistrée sur une montre comme nouveau
+// parcours, avec simplification optionnelle (Douglas-Peucker) ; export d'un parcours en trace
+// accompagnée des points de l'événement en waypoints pour les GPS de poche.
+
+use crate::db;
+use crate::map::{format_linestring_wkt, is_valid_coordinate, parse_linestring_wkt};
+use crate::types::Parcours;
+use crate::utils::{self, escape_xml};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+use std::fmt::Write;
+use std::fs;
+use tauri::AppHandle;
+use uuid::Uuid;
+
+/// Couleur d'un parcours importé (même rouge que sur les cartes PDF)
+pub const GPX_PARCOURS_COLOR: &str = "#ef4444";
+
+/// Trace GPX (lue ou écrite), coordonnées (lon, lat)
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpxTrack {
+    pub name: Option<String>,
+    pub points: Vec<(f64, f64)>,
+}
+
+/// Waypoint écrit dans un fichier GPX
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GpxWaypoint {
+    pub name: String,
+    pub description: Option<String>,
+    pub x: f64,
+    pub y: f64,
+}
+
+// ==================== Fonctions helper publiques et testables ====================
+
+fn child_text(node: roxmltree::Node, tag: &str) -> Option<String> {
+    node.children()
+        .find(|c| c.has_tag_name(tag))
+        .and_then(|c| c.text())
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+}
+
+fn gpx_point(node: roxmltree::Node) -> Result<(f64, f64), String> {
+    let lat = node
+        .attribute("lat")
+        .and_then(|v| v.trim().parse::<f64>().ok());
+    let lon = node
+        .attribute("lon")
+        .and_then(|v| v.trim().parse::<f64>().ok());
+    match (lat, lon) {
+        (Some(lat), Some(lon)) if is_valid_coordinate(lat, lon) => Ok((lon, lat)),
+        _ => Err(format!(
+            "Point GPX invalide (ligne {})",
+            node.document().text_pos_at(node.range().start).row
+        )),
+    }
+}
+
+/// Lit la première trace (tous ses segments bout à bout) ou, à défaut, la première route
+pub fn parse_gpx(xml: &str) -> Result<GpxTrack, String> {
+    let doc = roxmltree::Document::parse(xml).map_err(|e| format!("GPX illisible : {}", e))?;
+    let root = doc.root_element();
+    if !root.has_tag_name("gpx") {
+        return Err("Le fichier n'est pas un GPX".to_string());
+    }
+
+    let metadata_name = root
+        .children()
+        .find(|c| c.has_tag_name("metadata"))
+        .and_then(|m| child_text(m, "name"));
+
+    let (name, points) = if let Some(trk) = root.children().find(|c| c.has_tag_name("trk")) {
+        let points = trk
+            .children()
+            .filter(|c| c.has_tag_name("trkseg"))
+            .flat_map(|seg| seg.children().filter(|c| c.has_tag_name("trkpt")))
+            .map(gpx_point)
+            .collect::<Result<Vec<_>, _>>()?;
+        (child_text(trk, "name"), points)
+    } else if let Some(rte) = root.children().find(|c| c.has_tag_name("rte")) {
+        let points = rte
+            .children()
+            .filter(|c| c.has_tag_name("rtept"))
+            .map(gpx_point)
+            .collect::<Result<Vec<_>, _>>()?;
+        (child_text(rte, "name"), points)
+    } else {
+        return Err("Aucune trace ni route dans le fichier GPX".to_string());
+    };
+
+    if points.len() < 2 {
+        return Err("La trace GPX doit contenir au moins 2 points".to_string());
+    }
+
+    Ok(GpxTrack {
+        name: name.or(metadata_name),
+        points,
+    })
+}
+
+/// Distance (m) d'un point au segment [a, b], en projection locale équirectangulaire
+fn distance_to_segment_m(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let scale_x = 111_320.0 * p.1.to_radians().cos();
+    let scale_y = 110_574.0;
+    let (px, py) = ((p.0 - a.0) * scale_x, (p.1 - a.1) * scale_y);
+    let (bx, by) = ((b.0 - a.0) * scale_x, (b.1 - a.1) * scale_y);
+
+    let length_sq = bx * bx + by * by;
+    let t = if length_sq > 0.0 {
+        ((px * bx + py * by) / length_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    ((px - t * bx).powi(2) + (py - t * by).powi(2)).sqrt()
+}
+
+/// Simplifie un tracé (Douglas-Peucker) : les points à moins de `tolerance_m` de la ligne
+/// simplifiée sont supprimés. Le départ et l'arrivée sont toujours conservés.
+pub fn thin_points(line: &[(f64, f64)], tolerance_m: f64) -> Vec<(f64, f64)> {
+    if line.len() <= 2 || tolerance_m <= 0.0 {
+        return line.to_vec();
+    }
+
+    let mut keep = vec![false; line.len()];
+    keep[0] = true;
+    keep[line.len() - 1] = true;
+
+    let mut stack = vec![(0, line.len() - 1)];
+    while let Some((start, end)) = stack.pop() {
+        let farthest = (start + 1..end)
+            .map(|i| (i, distance_to_segment_m(line[i], line[start], line[end])))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        if let Some((index, distance)) = farthest {
+            if distance > tolerance_m {
+                keep[index] = true;
+                stack.push((start, index));
+                stack.push((index, end));
+            }
+        }
+    }
+
+    line.iter()
+        .zip(keep)
+        .filter(|(_, kept)| *kept)
+        .map(|(point, _)| *point)
+        .collect()
+}
+
+/// Document GPX 1.1 : waypoints puis une trace par parcours
+pub fn build_gpx(name: &str, tracks: &[GpxTrack], waypoints: &[GpxWaypoint]) -> String {
+    let mut gpx = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <gpx version=\"1.1\" creator=\"Dedale\" xmlns=\"http://www.topografix.com/GPX/1/1\">\n",
+    );
+    write!(
+        gpx,
+        "  <metadata>\n    <name>{}</name>\n  </metadata>\n",
+        escape_xml(name)
+    )
+    .unwrap();
+
+    for waypoint in waypoints {
+        write!(
+            gpx,
+            "  <wpt lat=\"{}\" lon=\"{}\">\n    <name>{}</name>\n",
+            waypoint.y,
+            waypoint.x,
+            escape_xml(&waypoint.name)
+        )
+        .unwrap();
+        if let Some(description) = &waypoint.description {
+            writeln!(gpx, "    <desc>{}</desc>", escape_xml(description)).unwrap();
+        }
+        gpx.push_str("  </wpt>\n");
+    }
+
+    for track in tracks {
+        gpx.push_str("  <trk>\n");
+        if let Some(track_name) = &track.name {
+            writeln!(gpx, "    <name>{}</name>", escape_xml(track_name)).unwrap();
+        }
+        gpx.push_str("    <trkseg>\n");
+        for (lon, lat) in &track.points {
+            writeln!(gpx, "      <trkpt lat=\"{}\" lon=\"{}\"/>", lat, lon).unwrap();
+        }
+        gpx.push_str("    </trkseg>\n  </trk>\n");
+    }
+
+    gpx.push_str("</gpx>\n");
+    gpx
+}
+
+// ==================== Chargement des données ====================
+
+/// Points de sécurité d'un événement en waypoints, dans l'ordre du parcours
+pub async fn load_point_waypoints(
+    pool: &SqlitePool,
+    event_id: &str,
+) -> Result<Vec<GpxWaypoint>, String> {
+    let rows = sqlx::query(
+        r#"
+        SELECT name, comment, x, y FROM point
+        WHERE event_id = ?
+        ORDER BY chainage_m IS NULL, parcours_id, chainage_m, rowid
+        "#,
+    )
+    .bind(event_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let name: Option<String> = row.get("name");
+            let comment: Option<String> = row.get("comment");
+            GpxWaypoint {
+                name: name.unwrap_or_else(|| "Point".to_string()),
+                description: comment.filter(|c| !c.is_empty()),
+                x: row.get("x"),
+                y: row.get("y"),
+            }
+        })
+        .collect())
+}
+
+// ==================== Commandes Tauri ====================
+
+/// Importe une trace ou une route GPX comme nouveau parcours.
+/// `thinning_m` (optionnel) : tolérance de simplification du tracé, en mètres.
+#[tauri::command]
+pub async fn import_parcours_gpx(
+    app: AppHandle,
+    event_id: String,
+    file_path: String,
+    name: Option<String>,
+    thinning_m: Option<f64>,
+) -> Result<Parcours, String> {
+    let pool = db::get_db_pool(&app).await?;
+
+    let xml = fs::read_to_string(&file_path).map_err(|e| e.to_string())?;
+    let track = parse_gpx(&xml)?;
+    let points = thin_points(&track.points, thinning_m.unwrap_or(0.0));
+
+    let parcours = Parcours {
+        id: Uuid::new_v4().to_string(),
+        event_id,
+        name: name.or(track.name).or(Some("Parcours GPX".to_string())),
+        color: Some(GPX_PARCOURS_COLOR.to_string()),
+        start_time: None,
+        speed_low: None,
+        speed_high: None,
+        geometry_json: Some(format_linestring_wkt(&points)),
+    };
+
+    sqlx::query(
+        "INSERT INTO parcours (id, event_id, geometry_json, name, color) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(&parcours.id)
+    .bind(&parcours.event_id)
+    .bind(&parcours.geometry_json)
+    .bind(&parcours.name)
+    .bind(&parcours.color)
+    .execute(&pool)
+    .await
+    .map_err(|e| format!("Erreur lors de l'import du parcours GPX : {}", e))?;
+
+    println!(
+        "[GPX] 📥 Parcours {} importé : {} point(s) sur {} lu(s)",
+        parcours.id,
+        points.len(),
+        track.points.len()
+    );
+    Ok(parcours)
+}
+
+/// Exporte un parcours en trace GPX, avec les points de l'événement en waypoints
+#[tauri::command]
+pub async fn export_parcours_gpx(
+    app: AppHandle,
+    parcours_id: String,
+    include_points: Option<bool>,
+) -> Result<(), String> {
+    let pool = db::get_db_pool(&app).await?;
+
+    let row = sqlx::query("SELECT name, event_id, geometry_json FROM parcours WHERE id = ?")
+        .bind(&parcours_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Parcours {} introuvable", parcours_id))?;
+
+    let name: Option<String> = row.get("name");
+    let event_id: String = row.get("event_id");
+    let geometry: Option<String> = row.get("geometry_json");
+    let line = parse_linestring_wkt(geometry.as_deref().unwrap_or(""));
+    if line.len() < 2 {
+        return Err("Le parcours n'a pas de tracé".to_string());
+    }
+
+    let waypoints = if include_points.unwrap_or(true) {
+        load_point_waypoints(&pool, &event_id).await?
+    } else {
+        vec![]
+    };
+
+    let title = name.clone().unwrap_or_else(|| "Parcours".to_string());
+    let gpx = build_gpx(&title, &[GpxTrack { name, points: line }], &waypoints);
+
+    let (dir_path, file_name) = utils::create_file_name(title.clone(), "gpx".to_string());
+    if let Some(save_path) = utils::show_save_dialog(&file_name, &dir_path, "gpx".to_string()) {
+        fs::write(save_path, gpx).map_err(|e| e.to_string())?;
+    }
+
+    println!(
+        "[GPX] 🧭 Parcours '{}' exporté avec {} waypoint(s)",
+        title,
+        waypoints.len()
+    );
+    Ok(())
+}
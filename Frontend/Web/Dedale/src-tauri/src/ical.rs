@@ -0,0 +1,431 @@
+// Export iCalendar (.ics) du planning
+// Chaque action devient un VEVENT. L'UID est dérivé de l'ID de l'action : réimporter
+// le fichier après modification met à jour les entrées au lieu de les dupliquer : SEQUENCE et
+// LAST-MODIFIED reprennent la révision de l'action (incrémentée à chaque modification), plus
+// celle du contenu exporté (une durée estimée recalculée déplace DTEND sans toucher l'action).
+
+use crate::db;
+use crate::durations::{duration_of, load_action_durations};
+use crate::map::format_coordinates;
+use crate::utils;
+use chrono::{DateTime, Duration, Local, NaiveDateTime, TimeZone, Utc};
+use sqlx::{Row, SqlitePool};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use tauri::AppHandle;
+
+/// Longueur maximale d'une ligne iCalendar (en octets, hors CRLF)
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Domaine des UID (RFC 5545 recommande un identifiant global « id@domaine »)
+const UID_DOMAIN: &str = "dedale";
+
+/// Action à exporter en VEVENT
+#[derive(Debug, Clone)]
+pub struct CalendarEntry {
+    pub action_id: String,
+    pub action_type: String,
+    pub equipement_description: Option<String>,
+    pub type_name: Option<String>,
+    pub team_name: Option<String>,
+    /// (lon, lat)
+    pub position: Option<(f64, f64)>,
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    /// Révision de l'action (0 à la création) ; la révision du contenu exporté s'y ajoute
+    pub sequence: i64,
+    /// Dernière modification de l'action (absente pour les actions jamais modifiées)
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+// ==================== Fonctions helper publiques et testables ====================
+
+/// UID stable d'une action
+pub fn action_uid(action_id: &str) -> String {
+    format!("{}@{}", action_id, UID_DOMAIN)
+}
+
+/// Échappe un texte iCalendar (\, ; , et retours à la ligne)
+pub fn escape_ical_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Date locale « flottante » (sans fuseau), interprétée dans le fuseau du téléphone
+pub fn format_ical_datetime(dt: &NaiveDateTime) -> String {
+    dt.format("%Y%m%dT%H%M%S").to_string()
+}
+
+/// Horodatage UTC (DTSTAMP, LAST-MODIFIED)
+pub fn format_ical_utc(dt: &DateTime<Utc>) -> String {
+    dt.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Plie une ligne à 75 octets (RFC 5545 §3.1) sans couper un caractère UTF-8
+pub fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut current = 0;
+
+    for c in line.chars() {
+        let len = c.len_utf8();
+        // Les lignes de continuation commencent par un espace qui compte dans la limite
+        if current + len > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            current = 1;
+        }
+        folded.push(c);
+        current += len;
+    }
+
+    folded
+}
+
+/// Lignes d'un VEVENT hors identifiant, horodatages et révision (UID, DTSTAMP, LAST-MODIFIED,
+/// SEQUENCE). Comparées d'un export à l'autre pour savoir si l'entrée a changé.
+pub fn event_content_lines(entry: &CalendarEntry) -> Vec<String> {
+    let label = utils::action_label(&entry.action_type);
+    let equipement = entry
+        .equipement_description
+        .clone()
+        .filter(|d| !d.is_empty())
+        .or(entry.type_name.clone())
+        .unwrap_or_else(|| "Équipement".to_string());
+
+    let mut description = format!("{} : {}", label, equipement);
+    if let Some(type_name) = &entry.type_name {
+        write!(description, "\nType : {}", type_name).unwrap();
+    }
+    if let Some(team_name) = &entry.team_name {
+        write!(description, "\nÉquipe : {}", team_name).unwrap();
+    }
+
+    let mut lines = vec![
+        format!("DTSTART:{}", format_ical_datetime(&entry.start)),
+        format!("DTEND:{}", format_ical_datetime(&entry.end)),
+        format!(
+            "SUMMARY:{}",
+            escape_ical_text(&format!("{} - {}", label, equipement))
+        ),
+        format!("DESCRIPTION:{}", escape_ical_text(&description)),
+        format!("CATEGORIES:{}", label.to_uppercase()),
+    ];
+    if let Some((lon, lat)) = entry.position {
+        lines.push(format!(
+            "LOCATION:{}",
+            escape_ical_text(&format_coordinates(lon, lat))
+        ));
+        lines.push(format!("GEO:{:.6};{:.6}", lat, lon));
+    }
+    lines
+}
+
+/// Révision du contenu exporté : inchangée si le contenu est celui du dernier export,
+/// incrémentée sinon
+pub fn next_content_revision(previous_content: &str, previous_revision: i64, content: &str) -> i64 {
+    if previous_content == content {
+        previous_revision
+    } else {
+        previous_revision + 1
+    }
+}
+
+/// Date locale enregistrée en base ("YYYY-MM-DDTHH:MM:SS") convertie en UTC
+fn local_to_utc(value: &str) -> Option<DateTime<Utc>> {
+    utils::parse_datetime(value)
+        .and_then(|dt| Local.from_local_datetime(&dt).earliest())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Construit le calendrier complet.
+/// DTSTAMP vaut la dernière modification de l'action (l'heure d'export à défaut) : un
+/// réexport sans changement produit des VEVENT identiques.
+pub fn build_calendar(name: &str, entries: &[CalendarEntry], dtstamp: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Dedale//Planning//FR".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_ical_text(name)),
+    ];
+
+    for entry in entries {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", action_uid(&entry.action_id)));
+        lines.push(format!(
+            "DTSTAMP:{}",
+            format_ical_utc(entry.last_modified.as_ref().unwrap_or(&dtstamp))
+        ));
+        if let Some(modified) = &entry.last_modified {
+            lines.push(format!("LAST-MODIFIED:{}", format_ical_utc(modified)));
+        }
+        lines.push(format!("SEQUENCE:{}", entry.sequence.max(0)));
+        lines.extend(event_content_lines(entry));
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut calendar = String::new();
+    for line in lines {
+        calendar.push_str(&fold_line(&line));
+        calendar.push_str("\r\n");
+    }
+    calendar
+}
+
+// ==================== Chargement des données ====================
+
+/// Actions planifiées correspondant au filtre SQL (portant sur a = action, e = equipement).
+/// `durations` couvre au moins les actions du filtre.
+async fn load_calendar_entries(
+    pool: &SqlitePool,
+    filter: &str,
+    id: &str,
+    durations: &HashMap<String, i64>,
+) -> Result<Vec<CalendarEntry>, String> {
+    let query = format!(
+        r#"
+        SELECT
+            a.id, a.type, a.scheduled_time, COALESCE(a.sequence, 0) as sequence,
+            COALESCE(a.updated_at, a.status_changed_at) as updated_at,
+            e.description, t.name as type_name, tm.name as team_name,
+            (SELECT c.x FROM equipement_coordinate c WHERE c.equipement_id = e.id
+             ORDER BY c.order_index ASC LIMIT 1) as x,
+            (SELECT c.y FROM equipement_coordinate c WHERE c.equipement_id = e.id
+             ORDER BY c.order_index ASC LIMIT 1) as y
+        FROM action a
+        JOIN equipement e ON a.equipement_id = e.id
+        LEFT JOIN type t ON e.type_id = t.id
+        LEFT JOIN team tm ON a.team_id = tm.id
+        WHERE {}
+        ORDER BY a.scheduled_time ASC
+    "#,
+        filter
+    );
+
+    let rows = sqlx::query(&query)
+        .bind(id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let entries = rows
+        .into_iter()
+        .filter_map(|row| {
+            let scheduled_time: Option<String> = row.get("scheduled_time");
+            let start = scheduled_time.as_deref().and_then(utils::parse_datetime)?;
+            let x: Option<f64> = row.get("x");
+            let y: Option<f64> = row.get("y");
+            let action_id: String = row.get("id");
+            let duration = duration_of(durations, &action_id);
+            // Horodatages enregistrés en heure locale
+            let updated_at: Option<String> = row.get("updated_at");
+            let last_modified = updated_at.as_deref().and_then(local_to_utc);
+
+            Some(CalendarEntry {
+                action_id,
+                action_type: row.get::<Option<String>, _>("type").unwrap_or_default(),
+                equipement_description: row.get("description"),
+                type_name: row.get("type_name"),
+                team_name: row.get("team_name"),
+                position: x.zip(y),
+                start,
+                end: start + Duration::minutes(duration),
+                sequence: row.get("sequence"),
+                last_modified,
+            })
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+/// Ajoute aux entrées la révision de leur contenu exporté (table ical_export_state) :
+/// SEQUENCE et LAST-MODIFIED suivent aussi les changements de durée estimée, de libellé
+/// ou de position qui ne modifient pas la ligne de l'action
+async fn apply_content_revisions(
+    pool: &SqlitePool,
+    entries: &mut [CalendarEntry],
+) -> Result<(), String> {
+    let rows =
+        sqlx::query("SELECT action_id, content, revision, modified_at FROM ical_export_state")
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+    let mut states: HashMap<String, (String, i64, Option<String>)> = rows
+        .into_iter()
+        .map(|row| {
+            (
+                row.get("action_id"),
+                (
+                    row.get("content"),
+                    row.get("revision"),
+                    row.get("modified_at"),
+                ),
+            )
+        })
+        .collect();
+
+    let now = Local::now()
+        .naive_local()
+        .format("%Y-%m-%dT%H:%M:%S")
+        .to_string();
+
+    for entry in entries.iter_mut() {
+        let content = event_content_lines(entry).join("\n");
+
+        let (revision, modified_at) = match states.remove(&entry.action_id) {
+            None => {
+                sqlx::query(
+                    "INSERT INTO ical_export_state (action_id, content, revision) VALUES (?, ?, 0)",
+                )
+                .bind(&entry.action_id)
+                .bind(&content)
+                .execute(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+                (0, None)
+            }
+            Some((previous, revision, modified_at)) => {
+                let next = next_content_revision(&previous, revision, &content);
+                if next == revision {
+                    (revision, modified_at)
+                } else {
+                    sqlx::query(
+                        "UPDATE ical_export_state SET content = ?, revision = ?, modified_at = ? WHERE action_id = ?",
+                    )
+                    .bind(&content)
+                    .bind(next)
+                    .bind(&now)
+                    .bind(&entry.action_id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                    (next, Some(now.clone()))
+                }
+            }
+        };
+
+        entry.sequence += revision;
+        entry.last_modified = entry
+            .last_modified
+            .max(modified_at.as_deref().and_then(local_to_utc));
+    }
+
+    Ok(())
+}
+
+async fn save_calendar(
+    pool: &SqlitePool,
+    calendar_name: &str,
+    filter: &str,
+    id: &str,
+    durations: &HashMap<String, i64>,
+) -> Result<(), String> {
+    let mut entries = load_calendar_entries(pool, filter, id, durations).await?;
+    apply_content_revisions(pool, &mut entries).await?;
+    let calendar = build_calendar(calendar_name, &entries, Utc::now());
+
+    let (dir_path, file_name) =
+        utils::create_file_name(format!("Planning_{}", calendar_name), "ics".to_string());
+    if let Some(save_path) = utils::show_save_dialog(&file_name, &dir_path, "ics".to_string()) {
+        fs::write(save_path, calendar).map_err(|e| e.to_string())?;
+    }
+
+    println!(
+        "[ICS] 📅 Calendrier '{}' exporté : {} action(s)",
+        calendar_name,
+        entries.len()
+    );
+    Ok(())
+}
+
+// ==================== Commandes Tauri ====================
+
+/// Exporte le planning d'une équipe au format iCalendar
+#[tauri::command]
+pub async fn export_team_ics(app: AppHandle, team_id: String) -> Result<(), String> {
+    let pool = db::get_db_pool(&app).await?;
+    let name: Option<String> = sqlx::query_scalar("SELECT name FROM team WHERE id = ?")
+        .bind(&team_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let durations = load_action_durations(&pool, None, Some(&team_id)).await?;
+
+    save_calendar(
+        &pool,
+        &name.unwrap_or_else(|| "Équipe".to_string()),
+        "a.team_id = ?",
+        &team_id,
+        &durations,
+    )
+    .await
+}
+
+/// Exporte le planning d'une personne (actions de toutes ses équipes)
+#[tauri::command]
+pub async fn export_person_ics(app: AppHandle, person_id: String) -> Result<(), String> {
+    let pool = db::get_db_pool(&app).await?;
+    let row = sqlx::query("SELECT firstname, lastname FROM person WHERE id = ?")
+        .bind(&person_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Personne {} introuvable", person_id))?;
+
+    let firstname: Option<String> = row.get("firstname");
+    let lastname: Option<String> = row.get("lastname");
+    let name = format!(
+        "{} {}",
+        firstname.unwrap_or_default(),
+        lastname.unwrap_or_default()
+    );
+
+    let team_ids: Vec<String> =
+        sqlx::query_scalar("SELECT DISTINCT team_id FROM member WHERE person_id = ?")
+            .bind(&person_id)
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| e.to_string())?;
+    let mut durations = HashMap::new();
+    for team_id in &team_ids {
+        durations.extend(load_action_durations(&pool, None, Some(team_id)).await?);
+    }
+
+    save_calendar(
+        &pool,
+        name.trim(),
+        "a.team_id IN (SELECT team_id FROM member WHERE person_id = ?)",
+        &person_id,
+        &durations,
+    )
+    .await
+}
+
+/// Exporte le planning complet d'un événement
+#[tauri::command]
+pub async fn export_event_ics(app: AppHandle, event_id: String) -> Result<(), String> {
+    let pool = db::get_db_pool(&app).await?;
+    let name: Option<String> = sqlx::query_scalar("SELECT name FROM event WHERE id = ?")
+        .bind(&event_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let durations = load_action_durations(&pool, Some(&event_id), None).await?;
+
+    save_calendar(
+        &pool,
+        &name.unwrap_or_else(|| "Événement".to_string()),
+        "e.event_id = ?",
+        &event_id,
+        &durations,
+    )
+    .await
+}
@@ -0,0 +1,369 @@
+// Ordonnancement des actions d'une équipe en tournée
+// Les actions sont regroupées par créneau horaire planifié ; dans chaque créneau, plus proche
+// voisin depuis la position courante puis amélioration 2-opt du trajet. La pose d'un équipement
+// reste avant sa dépose et les dépendances (action_dependency) sont respectées.
+
+use crate::scheduling::{distance_between, travel_minutes};
+use crate::types::ActionDependency;
+use crate::utils::{action_kind, format_datetime, parse_datetime};
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Nombre maximal de passes 2-opt (chaque passe est en O(n²))
+const MAX_TWO_OPT_PASSES: usize = 50;
+
+/// Durée d'un créneau : les actions planifiées moins de 60 minutes après la première action
+/// du créneau sont optimisées ensemble
+pub const TOUR_WINDOW_MINUTES: i64 = 60;
+
+/// Action à placer dans la tournée
+#[derive(Debug, Clone)]
+pub struct ItineraryInput {
+    pub action_id: String,
+    pub equipement_id: String,
+    pub action_type: Option<String>,
+    pub position: Option<(f64, f64)>,
+    pub scheduled_time: Option<String>,
+    pub duration_minutes: i64,
+}
+
+/// Étape de la tournée avec distance cumulée et heure d'arrivée estimée
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ItineraryStop {
+    /// Numéro de l'étape (1, 2, 3...)
+    pub order: usize,
+    pub action_id: String,
+    pub equipement_id: String,
+    pub action_type: Option<String>,
+    pub x: Option<f64>,
+    pub y: Option<f64>,
+    pub scheduled_time: Option<String>,
+    pub leg_distance_m: f64,
+    pub cumulative_distance_m: f64,
+    pub eta: Option<String>,
+}
+
+/// Tournée complète d'une équipe
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamItinerary {
+    pub team_id: String,
+    pub team_name: Option<String>,
+    pub start_x: Option<f64>,
+    pub start_y: Option<f64>,
+    pub start_time: Option<String>,
+    pub total_distance_m: f64,
+    pub stops: Vec<ItineraryStop>,
+}
+
+// ==================== Fonctions helper publiques et testables ====================
+
+/// Contraintes d'ordre (avant, après) entre indices d'actions : pose avant dépose du même
+/// équipement, puis dépendances dont les deux actions font partie de la tournée
+pub fn precedence_pairs(
+    inputs: &[ItineraryInput],
+    dependencies: &[ActionDependency],
+) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    for (i, pose) in inputs.iter().enumerate() {
+        if pose.action_type.as_deref().map(action_kind) != Some("pose") {
+            continue;
+        }
+        for (j, depose) in inputs.iter().enumerate() {
+            if depose.equipement_id == pose.equipement_id
+                && depose.action_type.as_deref().map(action_kind) == Some("depose")
+            {
+                pairs.push((i, j));
+            }
+        }
+    }
+
+    let index_of: HashMap<&str, usize> = inputs
+        .iter()
+        .enumerate()
+        .map(|(i, input)| (input.action_id.as_str(), i))
+        .collect();
+    for dependency in dependencies {
+        if let (Some(&before), Some(&after)) = (
+            index_of.get(dependency.depends_on_id.as_str()),
+            index_of.get(dependency.action_id.as_str()),
+        ) {
+            pairs.push((before, after));
+        }
+    }
+    pairs
+}
+
+/// Créneaux horaires (indices) dans l'ordre chronologique. Un créneau commence à la première
+/// action non encore placée et dure `TOUR_WINDOW_MINUTES` ; les actions sans horaire forment
+/// le dernier créneau.
+pub fn time_windows(scheduled: &[Option<NaiveDateTime>]) -> Vec<Vec<usize>> {
+    let mut timed: Vec<(usize, NaiveDateTime)> = scheduled
+        .iter()
+        .enumerate()
+        .filter_map(|(i, time)| time.map(|t| (i, t)))
+        .collect();
+    timed.sort_by_key(|(_, time)| *time);
+
+    let mut windows: Vec<Vec<usize>> = Vec::new();
+    let mut window_start: Option<NaiveDateTime> = None;
+    for (index, time) in timed {
+        match window_start {
+            Some(start) if time < start + Duration::minutes(TOUR_WINDOW_MINUTES) => {
+                windows.last_mut().unwrap().push(index);
+            }
+            _ => {
+                window_start = Some(time);
+                windows.push(vec![index]);
+            }
+        }
+    }
+
+    let untimed: Vec<usize> = (0..scheduled.len())
+        .filter(|&i| scheduled[i].is_none())
+        .collect();
+    if !untimed.is_empty() {
+        windows.push(untimed);
+    }
+    windows
+}
+
+/// Réordonne une séquence pour respecter les contraintes (avant, après) en déplaçant le moins
+/// possible : on prend à chaque fois le premier élément dont les prédécesseurs sont placés.
+/// En cas de cycle, le premier élément restant est pris tel quel.
+pub fn enforce_precedences(sequence: &[usize], precedences: &[(usize, usize)]) -> Vec<usize> {
+    let mut remaining = sequence.to_vec();
+    let mut ordered = Vec::with_capacity(sequence.len());
+
+    while !remaining.is_empty() {
+        let pos = remaining
+            .iter()
+            .position(|&candidate| {
+                precedences.iter().all(|&(before, after)| {
+                    after != candidate || !remaining.contains(&before) || before == candidate
+                })
+            })
+            .unwrap_or(0);
+        ordered.push(remaining.remove(pos));
+    }
+    ordered
+}
+
+/// Nombre de contraintes (avant, après) non respectées par un ordre de visite
+fn precedence_violations(order: &[usize], precedences: &[(usize, usize)]) -> usize {
+    let rank = |index: usize| order.iter().position(|&i| i == index);
+    precedences
+        .iter()
+        .filter(|&&(before, after)| match (rank(before), rank(after)) {
+            (Some(b), Some(a)) => b > a,
+            _ => false,
+        })
+        .count()
+}
+
+/// Ordre de visite des points (indices) : plus proche voisin puis 2-opt, sous contraintes
+/// (avant, après) entre indices de points.
+/// Sans point de départ, la tournée commence au premier point disponible.
+pub fn order_tour(
+    start: Option<(f64, f64)>,
+    points: &[(f64, f64)],
+    precedences: &[(usize, usize)],
+) -> Vec<usize> {
+    if points.is_empty() {
+        return vec![];
+    }
+
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    let mut order: Vec<usize> = Vec::with_capacity(points.len());
+    let mut current = start;
+
+    while !remaining.is_empty() {
+        // Points dont tous les prédécesseurs sont visités (tous en cas de cycle)
+        let mut candidates: Vec<usize> = (0..remaining.len())
+            .filter(|&pos| {
+                precedences
+                    .iter()
+                    .all(|&(before, after)| after != remaining[pos] || !remaining.contains(&before))
+            })
+            .collect();
+        if candidates.is_empty() {
+            candidates = (0..remaining.len()).collect();
+        }
+
+        let pos = match current {
+            Some(from) => candidates
+                .into_iter()
+                .min_by(|&a, &b| {
+                    distance_between(from, points[remaining[a]])
+                        .total_cmp(&distance_between(from, points[remaining[b]]))
+                })
+                .unwrap(),
+            None => candidates[0],
+        };
+        let next = remaining.remove(pos);
+        current = Some(points[next]);
+        order.push(next);
+    }
+
+    // Sans point de départ, la première étape reste fixe
+    let first_movable = if start.is_some() { 0 } else { 1 };
+    two_opt(start, points, &mut order, first_movable, precedences);
+    order
+}
+
+/// Inverse des segments du trajet tant que cela le raccourcit (trajet ouvert, fin libre),
+/// sans ajouter de contrainte non respectée
+fn two_opt(
+    start: Option<(f64, f64)>,
+    points: &[(f64, f64)],
+    order: &mut [usize],
+    first: usize,
+    precedences: &[(usize, usize)],
+) {
+    let n = order.len();
+    if n < first + 2 {
+        return;
+    }
+
+    let point_before = |order: &[usize], i: usize| -> Option<(f64, f64)> {
+        if i == 0 {
+            start
+        } else {
+            Some(points[order[i - 1]])
+        }
+    };
+
+    for _ in 0..MAX_TWO_OPT_PASSES {
+        let mut improved = false;
+
+        for i in first..n - 1 {
+            for k in i + 1..n {
+                let a = point_before(order, i);
+                let b = points[order[i]];
+                let c = points[order[k]];
+                let e = (k + 1 < n).then(|| points[order[k + 1]]);
+
+                let before = a.map_or(0.0, |a| distance_between(a, b))
+                    + e.map_or(0.0, |e| distance_between(c, e));
+                let after = a.map_or(0.0, |a| distance_between(a, c))
+                    + e.map_or(0.0, |e| distance_between(b, e));
+
+                if after + 1e-6 < before {
+                    let violations = precedence_violations(order, precedences);
+                    order[i..=k].reverse();
+                    if precedence_violations(order, precedences) > violations {
+                        order[i..=k].reverse();
+                    } else {
+                        improved = true;
+                    }
+                }
+            }
+        }
+
+        if !improved {
+            break;
+        }
+    }
+}
+
+/// Construit la tournée : ordre de passage, distances cumulées et heures d'arrivée.
+/// Les créneaux horaires se suivent dans l'ordre chronologique et la tournée n'est optimisée
+/// qu'à l'intérieur de chacun ; les actions sans coordonnées terminent leur créneau.
+/// Le chronomètre repart de l'horaire planifié du créneau (jamais avant `start_time`).
+pub fn build_itinerary(
+    start: Option<(f64, f64)>,
+    start_time: Option<NaiveDateTime>,
+    inputs: Vec<ItineraryInput>,
+    dependencies: &[ActionDependency],
+    travel_speed_kmh: f64,
+) -> Vec<ItineraryStop> {
+    let precedences = precedence_pairs(&inputs, dependencies);
+    let scheduled: Vec<Option<NaiveDateTime>> = inputs
+        .iter()
+        .map(|i| i.scheduled_time.as_deref().and_then(parse_datetime))
+        .collect();
+
+    let mut sequence = Vec::with_capacity(inputs.len());
+    let mut window_start = vec![None; inputs.len()];
+    let mut position = start;
+
+    for window in time_windows(&scheduled) {
+        let first_time = window.iter().filter_map(|&i| scheduled[i]).min();
+        let located: Vec<usize> = window
+            .iter()
+            .copied()
+            .filter(|&i| inputs[i].position.is_some())
+            .collect();
+        let points: Vec<(f64, f64)> = located.iter().filter_map(|&i| inputs[i].position).collect();
+        let local: Vec<(usize, usize)> = precedences
+            .iter()
+            .filter_map(|&(before, after)| {
+                Some((
+                    located.iter().position(|&i| i == before)?,
+                    located.iter().position(|&i| i == after)?,
+                ))
+            })
+            .collect();
+
+        let order = order_tour(position, &points, &local);
+        if let Some(&last) = order.last() {
+            position = Some(points[last]);
+        }
+        sequence.extend(order.into_iter().map(|k| located[k]));
+        sequence.extend(
+            window
+                .iter()
+                .copied()
+                .filter(|&i| inputs[i].position.is_none()),
+        );
+        for &i in &window {
+            window_start[i] = first_time;
+        }
+    }
+
+    // Contraintes entre créneaux (dépose planifiée avant la pose, dépendance à contre-temps)
+    let sequence = enforce_precedences(&sequence, &precedences);
+
+    let mut inputs: Vec<Option<ItineraryInput>> = inputs.into_iter().map(Some).collect();
+    let mut stops = Vec::new();
+    let mut previous = start;
+    let mut cumulative = 0.0;
+    let mut clock = start_time;
+
+    for (order, index) in sequence.into_iter().enumerate() {
+        let Some(input) = inputs[index].take() else {
+            continue;
+        };
+        let leg = match (previous, input.position) {
+            (Some(from), Some(to)) => distance_between(from, to),
+            _ => 0.0,
+        };
+        cumulative += leg;
+
+        clock = match (clock, window_start[index]) {
+            (Some(now), Some(planned)) => Some(now.max(planned)),
+            (now, planned) => now.or(planned),
+        };
+        let eta = clock.map(|t| t + Duration::minutes(travel_minutes(leg, travel_speed_kmh)));
+        clock = eta.map(|t| t + Duration::minutes(input.duration_minutes));
+
+        if input.position.is_some() {
+            previous = input.position;
+        }
+
+        stops.push(ItineraryStop {
+            order: order + 1,
+            action_id: input.action_id,
+            equipement_id: input.equipement_id,
+            action_type: input.action_type,
+            x: input.position.map(|p| p.0),
+            y: input.position.map(|p| p.1),
+            scheduled_time: input.scheduled_time,
+            leg_distance_m: leg,
+            cumulative_distance_m: cumulative,
+            eta: eta.as_ref().map(format_datetime),
+        });
+    }
+
+    stops
+}
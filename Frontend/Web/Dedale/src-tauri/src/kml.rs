@@ -0,0 +1,342 @@
+// Export KML/KMZ pour Google Earth (police, pompiers, partenaires)
+// Un dossier par couche : parcours colorés selon `parcours.color`, zones remplies en
+// transparence, points avec description et photos intégrées, équipements regroupés par type.
+// Le KMZ est une archive zip contenant doc.kml et les photos (dossier files/).
+
+use crate::db;
+use crate::geojson::{load_event_features, load_event_name, prop_str, GeoFeature, GeoGeometry};
+use crate::map::close_ring;
+use crate::utils::{self, escape_xml};
+use base64::{engine::general_purpose, Engine as _};
+use sqlx::{Row, SqlitePool};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{Cursor, Write};
+use tauri::AppHandle;
+use zip::write::SimpleFileOptions;
+
+/// Couleurs par défaut (mêmes que sur les cartes PDF)
+pub const KML_DEFAULT_PARCOURS_COLOR: &str = "#ef4444";
+pub const KML_DEFAULT_ZONE_COLOR: &str = "#3b82f6";
+pub const KML_EQUIPEMENT_COLOR: &str = "#f59e0b";
+
+/// Opacité du remplissage des zones (0-255)
+pub const KML_ZONE_FILL_ALPHA: u8 = 0x66;
+
+/// Photo à intégrer dans l'archive KMZ
+#[derive(Debug, Clone, PartialEq)]
+pub struct KmzPicture {
+    /// Chemin dans l'archive ("files/<point>_1.jpg")
+    pub path: String,
+    pub bytes: Vec<u8>,
+}
+
+// ==================== Fonctions helper publiques et testables ====================
+
+/// Couleur KML (aabbggrr) à partir d'une couleur CSS "#rrggbb"
+pub fn kml_color(hex: &str, alpha: u8) -> String {
+    let hex = hex.trim().trim_start_matches('#');
+    let hex = if hex.len() == 3 {
+        hex.chars().flat_map(|c| [c, c]).collect::<String>()
+    } else {
+        hex.to_string()
+    };
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return kml_color(KML_DEFAULT_PARCOURS_COLOR, alpha);
+    }
+    format!("{:02x}{}{}{}", alpha, &hex[4..6], &hex[2..4], &hex[0..2]).to_lowercase()
+}
+
+/// Décode une photo base64 (avec ou sans préfixe "data:image/...;base64,").
+/// Renvoie les octets et l'extension du fichier.
+pub fn decode_picture(image_data: &str) -> Option<(Vec<u8>, &'static str)> {
+    let encoded = match image_data.split_once(',') {
+        Some((prefix, data)) if prefix.starts_with("data:") => data,
+        _ => image_data,
+    };
+    let bytes = general_purpose::STANDARD.decode(encoded.trim()).ok()?;
+
+    let extension = if bytes.starts_with(&[0x89, b'P', b'N', b'G']) {
+        "png"
+    } else if bytes.starts_with(&[0xFF, 0xD8]) {
+        "jpg"
+    } else if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+        "webp"
+    } else {
+        return None;
+    };
+    Some((bytes, extension))
+}
+
+fn coordinates(points: &[(f64, f64)]) -> String {
+    points
+        .iter()
+        .map(|(lon, lat)| format!("{},{},0", lon, lat))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn geometry_kml(geometry: &GeoGeometry) -> String {
+    match geometry {
+        GeoGeometry::Point(point) => format!(
+            "<Point><coordinates>{}</coordinates></Point>",
+            coordinates(&[*point])
+        ),
+        GeoGeometry::LineString(line) => format!(
+            "<LineString><tessellate>1</tessellate><coordinates>{}</coordinates></LineString>",
+            coordinates(line)
+        ),
+        GeoGeometry::Polygon(ring) => format!(
+            "<Polygon><outerBoundaryIs><LinearRing><coordinates>{}</coordinates></LinearRing></outerBoundaryIs></Polygon>",
+            coordinates(&close_ring(ring))
+        ),
+    }
+}
+
+fn placemark(name: &str, style: &str, description: Option<&str>, geometry: &GeoGeometry) -> String {
+    let description = description
+        .map(|d| format!("<description><![CDATA[{}]]></description>", d))
+        .unwrap_or_default();
+    format!(
+        "      <Placemark><name>{}</name>{}<styleUrl>#{}</styleUrl>{}</Placemark>\n",
+        escape_xml(name),
+        description,
+        style,
+        geometry_kml(geometry)
+    )
+}
+
+fn line_style(id: &str, color: &str, fill: Option<&str>) -> String {
+    let poly = fill
+        .map(|f| format!("<PolyStyle><color>{}</color></PolyStyle>", f))
+        .unwrap_or_default();
+    format!(
+        "  <Style id=\"{}\"><LineStyle><color>{}</color><width>4</width></LineStyle>{}</Style>\n",
+        id, color, poly
+    )
+}
+
+/// Description HTML d'un point (commentaire, type, photos de l'archive)
+pub fn point_description(feature: &GeoFeature, picture_paths: &[String]) -> Option<String> {
+    let mut parts = Vec::new();
+    if let Some(kind) = prop_str(&feature.properties, "type") {
+        parts.push(format!("<b>Type :</b> {}", escape_xml(&kind)));
+    }
+    if let Some(comment) = prop_str(&feature.properties, "comment").filter(|c| !c.is_empty()) {
+        parts.push(escape_xml(&comment));
+    }
+    if let Some(description) =
+        prop_str(&feature.properties, "description").filter(|d| !d.is_empty())
+    {
+        parts.push(escape_xml(&description));
+    }
+    for path in picture_paths {
+        parts.push(format!("<img src=\"{}\" width=\"320\"/>", path));
+    }
+    (!parts.is_empty()).then(|| parts.join("<br/>"))
+}
+
+/// Document KML d'un événement. `pictures` associe l'ID d'un point aux chemins de ses photos
+/// dans l'archive.
+pub fn build_kml(
+    name: &str,
+    features: &[GeoFeature],
+    pictures: &HashMap<String, Vec<String>>,
+) -> String {
+    let mut styles = String::new();
+    let mut parcours = String::new();
+    let mut zones = String::new();
+    let mut points = String::new();
+    let mut interests = String::new();
+    let mut equipements: BTreeMap<String, String> = BTreeMap::new();
+
+    styles
+        .push_str("  <Style id=\"point\"><IconStyle><color>ff0000ff</color></IconStyle></Style>\n");
+    styles.push_str(
+        "  <Style id=\"interest\"><IconStyle><color>ff00ffff</color></IconStyle></Style>\n",
+    );
+    styles.push_str(&line_style(
+        "equipement",
+        &kml_color(KML_EQUIPEMENT_COLOR, 0xff),
+        None,
+    ));
+
+    for (index, feature) in features.iter().enumerate() {
+        let label = feature
+            .name
+            .clone()
+            .unwrap_or_else(|| feature.layer.clone());
+        let color = prop_str(&feature.properties, "color");
+
+        match feature.layer.as_str() {
+            "parcours" => {
+                let style = format!("parcours-{}", index);
+                let color = color.as_deref().unwrap_or(KML_DEFAULT_PARCOURS_COLOR);
+                styles.push_str(&line_style(&style, &kml_color(color, 0xff), None));
+                parcours.push_str(&placemark(&label, &style, None, &feature.geometry));
+            }
+            "zone" => {
+                let style = format!("zone-{}", index);
+                let color = color.as_deref().unwrap_or(KML_DEFAULT_ZONE_COLOR);
+                styles.push_str(&line_style(
+                    &style,
+                    &kml_color(color, 0xff),
+                    Some(&kml_color(color, KML_ZONE_FILL_ALPHA)),
+                ));
+                let description =
+                    prop_str(&feature.properties, "description").map(|d| escape_xml(&d));
+                zones.push_str(&placemark(
+                    &label,
+                    &style,
+                    description.as_deref(),
+                    &feature.geometry,
+                ));
+            }
+            "point" => {
+                let paths = pictures.get(&feature.id).map(Vec::as_slice).unwrap_or(&[]);
+                let description = point_description(feature, paths);
+                points.push_str(&placemark(
+                    &label,
+                    "point",
+                    description.as_deref(),
+                    &feature.geometry,
+                ));
+            }
+            "interest" => {
+                interests.push_str(&placemark(&label, "interest", None, &feature.geometry));
+            }
+            "equipement" => {
+                let description = point_description(feature, &[]);
+                equipements
+                    .entry(label.clone())
+                    .or_default()
+                    .push_str(&placemark(
+                        &label,
+                        "equipement",
+                        description.as_deref(),
+                        &feature.geometry,
+                    ));
+            }
+            _ => {}
+        }
+    }
+
+    let folder = |title: &str, content: &str| {
+        if content.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "    <Folder><name>{}</name>\n{}    </Folder>\n",
+                escape_xml(title),
+                content
+            )
+        }
+    };
+
+    let equipement_folders: String = equipements
+        .iter()
+        .map(|(type_name, content)| folder(type_name, content))
+        .collect();
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <kml xmlns=\"http://www.opengis.net/kml/2.2\">\n<Document>\n  <name>{}</name>\n{}{}{}{}{}{}</Document>\n</kml>\n",
+        escape_xml(name),
+        styles,
+        folder("Parcours", &parcours),
+        folder("Zones", &zones),
+        folder("Points", &points),
+        folder("Points d'intérêt", &interests),
+        if equipement_folders.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "    <Folder><name>Équipements</name>\n{}    </Folder>\n",
+                equipement_folders
+            )
+        }
+    )
+}
+
+/// Archive KMZ : doc.kml en premier, puis les photos
+pub fn build_kmz(kml: &str, pictures: &[KmzPicture]) -> Result<Vec<u8>, String> {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file("doc.kml", options)
+        .map_err(|e| e.to_string())?;
+    zip.write_all(kml.as_bytes()).map_err(|e| e.to_string())?;
+
+    for picture in pictures {
+        zip.start_file(picture.path.as_str(), options)
+            .map_err(|e| e.to_string())?;
+        zip.write_all(&picture.bytes).map_err(|e| e.to_string())?;
+    }
+
+    Ok(zip.finish().map_err(|e| e.to_string())?.into_inner())
+}
+
+// ==================== Chargement des données ====================
+
+/// Photos des points d'un événement, décodées et nommées pour l'archive
+pub async fn load_kmz_pictures(
+    pool: &SqlitePool,
+    event_id: &str,
+) -> Result<(Vec<KmzPicture>, HashMap<String, Vec<String>>), String> {
+    let rows = sqlx::query(
+        r#"
+        SELECT p.point_id, p.image_data
+        FROM picture p
+        JOIN point pt ON p.point_id = pt.id
+        WHERE pt.event_id = ?
+        ORDER BY p.point_id, p.id
+        "#,
+    )
+    .bind(event_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut pictures = Vec::new();
+    let mut paths: HashMap<String, Vec<String>> = HashMap::new();
+    for row in rows {
+        let point_id: String = row.get("point_id");
+        let image_data: Option<String> = row.get("image_data");
+        let Some((bytes, extension)) = image_data.as_deref().and_then(decode_picture) else {
+            continue;
+        };
+
+        let point_paths = paths.entry(point_id.clone()).or_default();
+        let path = format!("files/{}_{}.{}", point_id, point_paths.len() + 1, extension);
+        point_paths.push(path.clone());
+        pictures.push(KmzPicture { path, bytes });
+    }
+    Ok((pictures, paths))
+}
+
+// ==================== Commandes Tauri ====================
+
+/// Exporte un événement en KMZ (Google Earth), photos des points comprises
+#[tauri::command]
+pub async fn export_event_kmz(app: AppHandle, event_id: String) -> Result<(), String> {
+    let pool = db::get_db_pool(&app).await?;
+    let name = load_event_name(&pool, &event_id).await?;
+    let features = load_event_features(&pool, &event_id).await?;
+    let (pictures, paths) = load_kmz_pictures(&pool, &event_id).await?;
+
+    let kml = build_kml(&name, &features, &paths);
+    let kmz = build_kmz(&kml, &pictures)?;
+
+    let (dir_path, file_name) = utils::create_file_name(name.clone(), "kmz".to_string());
+    if let Some(save_path) = utils::show_save_dialog(&file_name, &dir_path, "kmz".to_string()) {
+        fs::write(save_path, kmz).map_err(|e| e.to_string())?;
+    }
+
+    println!(
+        "[KML] 🌍 Événement '{}' exporté en KMZ : {} entité(s), {} photo(s)",
+        name,
+        features.len(),
+        pictures.len()
+    );
+    Ok(())
+}
@@ -1,17 +1,36 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 use tauri::{Emitter, Manager};
 
+mod closures;
+mod conflicts;
+mod crossings;
 mod db;
+mod durations;
 mod excel;
+mod gantt;
+mod geojson;
+mod geopackage;
+mod gpx;
 mod geocoding;
+mod ical;
+mod itinerary;
+mod kml;
 mod map;
 mod map_pdf;
+mod markers;
 mod pdf;
+mod planning_excel;
 mod pmtiles;
+mod progress;
+mod projection;
+mod race;
+mod scheduling;
 mod seed;
+mod snapping;
 mod socket;
 mod types;
 mod utils;
+mod zone_conflicts;
 
 #[cfg(test)]
 mod tests;
@@ -93,6 +112,10 @@ pub fn run() {
             db::update_geometry,
             db::update_point_dates,
             db::update_parcours,
+            db::fetch_waves_for_parcours,
+            db::create_wave,
+            db::update_wave,
+            db::delete_wave,
             db::update_zone,
             db::create_zone,
             db::create_parcours,
@@ -124,10 +147,64 @@ pub fn run() {
             db::fetch_actions_for_team,
             db::fetch_actions_for_equipement,
             db::update_action_status,
-            db::export_planning_excel,
-            db::create_planning_pdf,
+            db::set_action_status,
+            db::fetch_action_status_history,
+            progress::fetch_event_progress,
+            durations::fetch_productivity_rates,
+            durations::save_productivity_rate,
+            durations::delete_productivity_rate,
+            durations::fetch_action_durations,
+            durations::recalibrate_productivity_rates,
+            planning_excel::export_planning_excel,
+            planning_excel::preview_planning_import,
+            planning_excel::apply_planning_import,
+            pdf::create_planning_pdf,
             db::send_equipements_to_mobile,
             db::send_planning,
+            db::generate_planning_proposal,
+            db::apply_planning_proposal,
+            db::fetch_team_itinerary,
+            db::validate_planning_against_race,
+            race::compute_passage_time,
+            race::fetch_event_passage_times,
+            closures::compute_street_closures,
+            pdf::create_street_closures_pdf,
+            excel::export_street_closures_excel,
+            markers::measure_parcours,
+            markers::generate_km_markers,
+            snapping::snap_point_to_parcours,
+            snapping::snap_equipement_to_parcours,
+            snapping::snap_event_to_parcours,
+            crossings::detect_parcours_crossings,
+            crossings::create_crossing_points,
+            zone_conflicts::analyze_zone_conflicts,
+            geojson::export_event_geojson,
+            geojson::import_event_geojson,
+            gpx::import_parcours_gpx,
+            gpx::export_parcours_gpx,
+            kml::export_event_kmz,
+            geopackage::export_event_geopackage,
+            projection::display_coordinates,
+            projection::convert_to_wgs84,
+            db::check_planning_conflicts,
+            db::fetch_action_dependencies,
+            db::add_action_dependency,
+            db::remove_action_dependency,
+            db::validate_action_dependencies,
+            pdf::create_gantt_pdf,
+            gantt::export_gantt_svg,
+            ical::export_team_ics,
+            ical::export_person_ics,
+            ical::export_event_ics,
+            // Dépôts
+            db::fetch_depots,
+            db::create_depot,
+            db::update_depot,
+            db::delete_depot,
+            db::set_depot_stock,
+            db::set_equipement_depot,
+            db::fetch_depot_report,
+            excel::export_depot_report_excel,
             pmtiles::get_pmtiles_file_path,
             geocoding::reverse_geocode,
         ])
@@ -163,12 +163,139 @@ pub fn midpoint(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> (f64, f64) {
     ((lat1 + lat2) / 2.0, (lon1 + lon2) / 2.0)
 }
 
+/// Point atteint depuis (lat, lon) en suivant un cap (degrés) sur une distance (mètres)
+pub fn destination_point(lat: f64, lon: f64, bearing: f64, distance: f64) -> (f64, f64) {
+    const EARTH_RADIUS: f64 = 6371000.0;
+
+    let lat_rad = lat.to_radians();
+    let bearing_rad = bearing.to_radians();
+    let angular = distance / EARTH_RADIUS;
+
+    let lat2 =
+        (lat_rad.sin() * angular.cos() + lat_rad.cos() * angular.sin() * bearing_rad.cos()).asin();
+    let lon2 = lon.to_radians()
+        + (bearing_rad.sin() * angular.sin() * lat_rad.cos())
+            .atan2(angular.cos() - lat_rad.sin() * lat2.sin());
+
+    (lat2.to_degrees(), normalize_longitude(lon2.to_degrees()))
+}
+
 /// Arrondit une coordonnée à un nombre de décimales
 pub fn round_coordinate(value: f64, decimals: u32) -> f64 {
     let multiplier = 10_f64.powi(decimals as i32);
     (value * multiplier).round() / multiplier
 }
 
+/// Parse une chaîne WKT LINESTRING et retourne une liste de coordonnées (lon, lat)
+pub fn parse_linestring_wkt(wkt: &str) -> Vec<(f64, f64)> {
+    // Format: "LINESTRING(lon1 lat1, lon2 lat2, ...)"
+    let wkt = wkt.trim();
+    if !wkt.to_uppercase().starts_with("LINESTRING") {
+        return vec![];
+    }
+
+    // Extraire le contenu entre les parenthèses
+    if let Some(start) = wkt.find('(') {
+        if let Some(end) = wkt.rfind(')') {
+            let coords_str = &wkt[start + 1..end];
+            let pairs: Vec<&str> = coords_str.split(',').collect();
+
+            let mut coords = vec![];
+            for pair in pairs {
+                let parts: Vec<&str> = pair.split_whitespace().collect();
+                if parts.len() >= 2 {
+                    if let (Ok(lon), Ok(lat)) = (parts[0].parse::<f64>(), parts[1].parse::<f64>()) {
+                        coords.push((lon, lat));
+                    }
+                }
+            }
+            return coords;
+        }
+    }
+    vec![]
+}
+
+/// Parse une chaîne WKT POLYGON et retourne une liste de coordonnées (lon, lat)
+pub fn parse_polygon_wkt(wkt: &str) -> Vec<(f64, f64)> {
+    // Format: "POLYGON((lon1 lat1, lon2 lat2, ..., lon1 lat1))"
+    let wkt = wkt.trim();
+    if !wkt.to_uppercase().starts_with("POLYGON") {
+        return vec![];
+    }
+
+    // Extraire le contenu du premier anneau (outer ring)
+    if let Some(start) = wkt.find('(') {
+        // Trouver la fin du premier anneau (pas du dernier ')')
+        if let Some(first_paren_close) = wkt[start + 1..].find(')') {
+            let inner_coords =
+                wkt[start + 1..start + 1 + first_paren_close].trim_start_matches('(');
+            let pairs: Vec<&str> = inner_coords.split(',').collect();
+
+            let mut coords = vec![];
+            for pair in pairs {
+                let parts: Vec<&str> = pair.split_whitespace().collect();
+                if parts.len() >= 2 {
+                    if let (Ok(lon), Ok(lat)) = (parts[0].parse::<f64>(), parts[1].parse::<f64>()) {
+                        coords.push((lon, lat));
+                    }
+                }
+            }
+            return coords;
+        }
+    }
+    vec![]
+}
+
+/// Formate une liste de coordonnées (lon, lat) en WKT LINESTRING
+pub fn format_linestring_wkt(coords: &[(f64, f64)]) -> String {
+    let pairs: Vec<String> = coords
+        .iter()
+        .map(|(lon, lat)| format!("{} {}", lon, lat))
+        .collect();
+    format!("LINESTRING({})", pairs.join(", "))
+}
+
+/// Formate une coordonnée (lon, lat) en WKT POINT
+pub fn format_point_wkt(lon: f64, lat: f64) -> String {
+    format!("POINT({} {})", lon, lat)
+}
+
+/// Referme un anneau (le dernier point répète le premier)
+pub fn close_ring(ring: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut closed = ring.to_vec();
+    if let (Some(first), Some(last)) = (ring.first(), ring.last()) {
+        if first != last {
+            closed.push(*first);
+        }
+    }
+    closed
+}
+
+/// Formate un anneau (lon, lat) en WKT POLYGON, refermé si besoin
+pub fn format_polygon_wkt(ring: &[(f64, f64)]) -> String {
+    let pairs: Vec<String> = close_ring(ring)
+        .iter()
+        .map(|(lon, lat)| format!("{} {}", lon, lat))
+        .collect();
+    format!("POLYGON(({}))", pairs.join(", "))
+}
+
+/// Vérifie si un point (lon, lat) est à l'intérieur d'un polygone (ray casting)
+pub fn point_in_ring(point: (f64, f64), ring: &[(f64, f64)]) -> bool {
+    let (x, y) = point;
+    let mut inside = false;
+    let n = ring.len();
+
+    for i in 0..n {
+        let (x1, y1) = ring[i];
+        let (x2, y2) = ring[(i + 1) % n];
+        if (y1 > y) != (y2 > y) && x < (x2 - x1) * (y - y1) / (y2 - y1) + x1 {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
 // ==================== Commandes Tauri ====================
 
 #[tauri::command]
@@ -1,3 +1,4 @@
+use crate::map::{parse_linestring_wkt, parse_polygon_wkt};
 use crate::pmtiles::get_pmtiles_file_path;
 use crate::types::Parcours;
 use ::pmtiles::{AsyncPmTilesReader, TileCoord};
@@ -55,66 +56,7 @@ fn tile_to_lon_lat(x: u32, y: u32, zoom: u8) -> (f64, f64) {
     (lon, lat)
 }
 
-// --- UTILITAIRES POUR PARSER WKT ET TRACER DES LIGNES ---
-
-/// Parse une chaîne WKT LINESTRING et retourne une liste de coordonnées (lon, lat)
-fn parse_linestring_wkt(wkt: &str) -> Vec<(f64, f64)> {
-    // Format: "LINESTRING(lon1 lat1, lon2 lat2, ...)"
-    let wkt = wkt.trim();
-    if !wkt.to_uppercase().starts_with("LINESTRING") {
-        return vec![];
-    }
-
-    // Extraire le contenu entre les parenthèses
-    if let Some(start) = wkt.find('(') {
-        if let Some(end) = wkt.rfind(')') {
-            let coords_str = &wkt[start + 1..end];
-            let pairs: Vec<&str> = coords_str.split(',').collect();
-
-            let mut coords = vec![];
-            for pair in pairs {
-                let parts: Vec<&str> = pair.split_whitespace().collect();
-                if parts.len() >= 2 {
-                    if let (Ok(lon), Ok(lat)) = (parts[0].parse::<f64>(), parts[1].parse::<f64>()) {
-                        coords.push((lon, lat));
-                    }
-                }
-            }
-            return coords;
-        }
-    }
-    vec![]
-}
-
-/// Parse une chaîne WKT POLYGON et retourne une liste de coordonnées (lon, lat)
-fn parse_polygon_wkt(wkt: &str) -> Vec<(f64, f64)> {
-    // Format: "POLYGON((lon1 lat1, lon2 lat2, ..., lon1 lat1))"
-    let wkt = wkt.trim();
-    if !wkt.to_uppercase().starts_with("POLYGON") {
-        return vec![];
-    }
-
-    // Extraire le contenu du premier anneau (outer ring)
-    if let Some(start) = wkt.find('(') {
-        // Trouver la fin du premier anneau (pas du dernier ')')
-        if let Some(first_paren_close) = wkt[start + 1..].find(')') {
-            let inner_coords = &wkt[start + 1..start + 1 + first_paren_close];
-            let pairs: Vec<&str> = inner_coords.split(',').collect();
-
-            let mut coords = vec![];
-            for pair in pairs {
-                let parts: Vec<&str> = pair.split_whitespace().collect();
-                if parts.len() >= 2 {
-                    if let (Ok(lon), Ok(lat)) = (parts[0].parse::<f64>(), parts[1].parse::<f64>()) {
-                        coords.push((lon, lat));
-                    }
-                }
-            }
-            return coords;
-        }
-    }
-    vec![]
-}
+// --- UTILITAIRES POUR TRACER DES LIGNES ---
 
 /// Convertit une couleur hexadécimale en RGBA
 /// Format attendu: "#RRGGBB" ou "RRGGBB"
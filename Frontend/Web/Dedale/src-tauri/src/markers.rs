@@ -0,0 +1,239 @@
+// Bornes kilométriques des parcours
+// Longueur géodésique du tracé (somme des distances de Haversine) et position des bornes
+// tous les kilomètres (ou demi-kilomètres), enregistrées comme points d'intérêt pour que
+// les signaleurs puissent poser les panneaux.
+
+use crate::db;
+use crate::map::{bearing_to_cardinal, calculate_bearing, destination_point, parse_linestring_wkt};
+use crate::race::{cumulative_lengths, interpolate_at_chainage};
+use crate::types::Interest;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+use tauri::AppHandle;
+use uuid::Uuid;
+
+/// Décalage (m) du panneau sur la droite du tracé, dans le sens de la course
+pub const MARKER_SIDE_OFFSET_METERS: f64 = 3.0;
+
+/// Borne placée le long d'un parcours
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct KmMarker {
+    /// Distance depuis le départ (m)
+    pub distance_m: f64,
+    pub label: String,
+    pub x: f64,
+    pub y: f64,
+    /// Cap du tracé au niveau de la borne (degrés, 0 = nord)
+    pub bearing: f64,
+}
+
+/// Mesure d'un parcours et bornes correspondantes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParcoursMeasure {
+    pub parcours_id: String,
+    pub name: Option<String>,
+    pub length_m: f64,
+    pub markers: Vec<KmMarker>,
+}
+
+// ==================== Fonctions helper publiques et testables ====================
+
+/// Longueur géodésique d'un tracé (m)
+pub fn parcours_length(line: &[(f64, f64)]) -> f64 {
+    cumulative_lengths(line).last().copied().unwrap_or(0.0)
+}
+
+/// Libellé d'une borne : "Km 5", "Km 5,5"
+pub fn marker_label(distance_m: f64) -> String {
+    let km = (distance_m / 100.0).round() / 10.0;
+    if km.fract() == 0.0 {
+        format!("Km {}", km as i64)
+    } else {
+        format!("Km {}", km.to_string().replace('.', ","))
+    }
+}
+
+/// Bornes tous les kilomètres (ou demi-kilomètres), hors départ.
+/// Chaque borne est décalée de `side_offset_m` sur la droite du tracé, perpendiculairement
+/// au cap du segment sur lequel elle se trouve.
+pub fn km_markers(line: &[(f64, f64)], half_km: bool, side_offset_m: f64) -> Vec<KmMarker> {
+    let lengths = cumulative_lengths(line);
+    let Some(&total) = lengths.last() else {
+        return vec![];
+    };
+    let step = if half_km { 500.0 } else { 1000.0 };
+
+    let mut markers = Vec::new();
+    let mut distance = step;
+    while distance <= total + 1e-6 {
+        let Some((segment, on_line)) = interpolate_at_chainage(line, &lengths, distance) else {
+            break;
+        };
+        let (a, b) = (line[segment], line[segment + 1]);
+        let bearing = calculate_bearing(a.1, a.0, b.1, b.0);
+        let (lat, lon) = destination_point(on_line.1, on_line.0, bearing + 90.0, side_offset_m);
+
+        markers.push(KmMarker {
+            distance_m: distance,
+            label: marker_label(distance),
+            x: lon,
+            y: lat,
+            bearing,
+        });
+        distance += step;
+    }
+    markers
+}
+
+/// Description du point d'intérêt associé à une borne
+pub fn marker_description(marker: &KmMarker, parcours_name: Option<&str>) -> String {
+    format!(
+        "{} – {} (sens {})",
+        marker.label,
+        parcours_name.unwrap_or("Parcours"),
+        bearing_to_cardinal(marker.bearing)
+    )
+}
+
+// ==================== Chargement des données ====================
+
+/// Bornes kilométriques déjà générées pour les parcours d'un événement
+pub async fn load_km_markers(pool: &SqlitePool, event_id: &str) -> Result<Vec<Interest>, String> {
+    let rows = sqlx::query(
+        r#"
+        SELECT id, x, y, description, event_id, parcours_id, marker_km, bearing
+        FROM interest
+        WHERE event_id = ? AND marker_km IS NOT NULL
+        ORDER BY parcours_id, marker_km
+        "#,
+    )
+    .bind(event_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| Interest {
+            id: row.get("id"),
+            x: row.get("x"),
+            y: row.get("y"),
+            description: row.get("description"),
+            event_id: row.get("event_id"),
+            parcours_id: row.get("parcours_id"),
+            marker_km: row.get("marker_km"),
+            bearing: row.get("bearing"),
+        })
+        .collect())
+}
+
+async fn load_parcours_measure(
+    pool: &SqlitePool,
+    parcours_id: &str,
+    half_km: bool,
+) -> Result<(ParcoursMeasure, String), String> {
+    let row = sqlx::query("SELECT name, event_id, geometry_json FROM parcours WHERE id = ?")
+        .bind(parcours_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Parcours {} introuvable", parcours_id))?;
+
+    let geometry: Option<String> = row.get("geometry_json");
+    let line = parse_linestring_wkt(geometry.as_deref().unwrap_or(""));
+    if line.len() < 2 {
+        return Err("Le parcours n'a pas de tracé".to_string());
+    }
+
+    let measure = ParcoursMeasure {
+        parcours_id: parcours_id.to_string(),
+        name: row.get("name"),
+        length_m: parcours_length(&line),
+        markers: km_markers(&line, half_km, MARKER_SIDE_OFFSET_METERS),
+    };
+    Ok((measure, row.get("event_id")))
+}
+
+// ==================== Commandes Tauri ====================
+
+/// Longueur d'un parcours et position des bornes, sans rien enregistrer
+#[tauri::command]
+pub async fn measure_parcours(
+    app: AppHandle,
+    parcours_id: String,
+    half_km: Option<bool>,
+) -> Result<ParcoursMeasure, String> {
+    let pool = db::get_db_pool(&app).await?;
+    let (measure, _) = load_parcours_measure(&pool, &parcours_id, half_km.unwrap_or(false)).await?;
+
+    println!(
+        "[MARKERS] 📏 Parcours {} : {:.0} m, {} borne(s)",
+        parcours_id,
+        measure.length_m,
+        measure.markers.len()
+    );
+    Ok(measure)
+}
+
+/// (Re)génère les bornes d'un parcours comme points d'intérêt.
+/// Les bornes générées précédemment pour ce parcours sont remplacées.
+#[tauri::command]
+pub async fn generate_km_markers(
+    app: AppHandle,
+    parcours_id: String,
+    half_km: Option<bool>,
+) -> Result<Vec<Interest>, String> {
+    let pool = db::get_db_pool(&app).await?;
+    let (measure, event_id) =
+        load_parcours_measure(&pool, &parcours_id, half_km.unwrap_or(false)).await?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    sqlx::query("DELETE FROM interest WHERE parcours_id = ? AND marker_km IS NOT NULL")
+        .bind(&parcours_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Erreur lors de la suppression des anciennes bornes : {}", e))?;
+
+    let mut interests = Vec::with_capacity(measure.markers.len());
+    for marker in &measure.markers {
+        let interest = Interest {
+            id: Uuid::new_v4().to_string(),
+            x: marker.x,
+            y: marker.y,
+            description: Some(marker_description(marker, measure.name.as_deref())),
+            event_id: Some(event_id.clone()),
+            parcours_id: Some(parcours_id.clone()),
+            marker_km: Some(marker.distance_m / 1000.0),
+            bearing: Some(marker.bearing),
+        };
+
+        sqlx::query(
+            r#"INSERT INTO interest (id, x, y, description, event_id, parcours_id, marker_km, bearing)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+        )
+        .bind(&interest.id)
+        .bind(interest.x)
+        .bind(interest.y)
+        .bind(&interest.description)
+        .bind(&interest.event_id)
+        .bind(&interest.parcours_id)
+        .bind(interest.marker_km)
+        .bind(interest.bearing)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Erreur lors de la création d'une borne : {}", e))?;
+
+        interests.push(interest);
+    }
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    println!(
+        "[MARKERS] ✅ {} borne(s) générée(s) pour le parcours {} ({:.0} m)",
+        interests.len(),
+        parcours_id,
+        measure.length_m
+    );
+    Ok(interests)
+}
@@ -1,8 +1,16 @@
+use crate::closures;
+use crate::conflicts::detect_conflicts;
 use crate::db;
 use crate::db::fetch_equipement_coordinates;
 use crate::db::EquipementActionComplet;
 use crate::db::EquipementComplet;
+use crate::durations;
+use crate::gantt;
 use crate::map_pdf;
+use crate::markers;
+use crate::race::{self, PassageTime};
+use crate::scheduling::DEFAULT_TRAVEL_SPEED_KMH;
+use crate::types::{ActionStatus, Interest};
 use crate::utils;
 use base64::Engine;
 use sqlx::Row;
@@ -40,6 +48,77 @@ fn format_date(date_str: &str) -> String {
     }
 }
 
+/// Passage de la course en un lieu : "km 3.2 · 08:15 → 09:40"
+fn format_passage(passage: &PassageTime) -> String {
+    let time = |value: &str| value.split('T').nth(1).unwrap_or(value).to_string();
+    format!(
+        "km {:.1} · {} → {}",
+        passage.chainage_m / 1000.0,
+        time(&passage.first_passage),
+        time(&passage.last_passage)
+    )
+}
+
+/// Cellule Typst du type d'action, avec le libellé des autres exports ("POSE" / "DÉPOSE")
+pub fn action_type_cell(action_type: Option<&str>) -> String {
+    let action_type = action_type.unwrap_or_default();
+    let color = if utils::action_kind(action_type) == "pose" {
+        "green"
+    } else {
+        "red"
+    };
+    format!(
+        r#"#text(fill: {}, weight: "bold")[{}]"#,
+        color,
+        utils::action_label(action_type).to_uppercase()
+    )
+}
+
+/// Totaux d'une équipe repris dans le récapitulatif du planning
+#[derive(Debug, Clone)]
+pub struct PlanningTeamSummary {
+    pub team_name: String,
+    pub member_count: usize,
+    pub action_count: usize,
+    pub distance_m: f64,
+    pub first: Option<String>,
+    pub last: Option<String>,
+}
+
+/// Tableau Typst récapitulatif du planning (une ligne par équipe)
+pub fn planning_summary_table(summary: &[PlanningTeamSummary]) -> String {
+    let mut typst_src = String::from(
+        r#"== Récapitulatif
+        #table(
+        columns: (1fr, auto, auto, auto, auto, auto),
+        inset: 6pt,
+        fill: (x, y) => if y == 0 { luma(240) },
+        [*Équipe*], [*Membres*], [*Actions*], [*Trajet*], [*Début*], [*Fin*],
+    "#,
+    );
+    for team in summary {
+        writeln!(
+            typst_src,
+            "[{}], [{}], [{}], [{:.1} km], [{}], [{}],",
+            utils::escape_typst(&team.team_name),
+            team.member_count,
+            team.action_count,
+            team.distance_m / 1000.0,
+            team.first
+                .as_deref()
+                .map(format_date)
+                .unwrap_or_else(|| "-".to_string()),
+            team.last
+                .as_deref()
+                .map(format_date)
+                .unwrap_or_else(|| "-".to_string())
+        )
+        .unwrap();
+    }
+    typst_src.push_str(")\n");
+    typst_src
+}
+
 // =============================================================================
 // 1. PDF GLOBAL (Vue d'ensemble de l'événement)
 // =============================================================================
@@ -124,6 +203,11 @@ pub async fn create_pdf(app: AppHandle, event_id: Option<String>) -> Result<(),
         vec![]
     };
 
+    let km_markers = match &event_id {
+        Some(eid) => markers::load_km_markers(&db::get_db_pool(&app).await?, eid).await?,
+        None => vec![],
+    };
+
     let map_res = map_pdf::generate_cropped_map_with_parcours_and_zones(
         &temp_dir,
         &data,
@@ -180,6 +264,7 @@ pub async fn create_pdf(app: AppHandle, event_id: Option<String>) -> Result<(),
                     ));
                 }
             }
+            typst_src.push_str(&km_marker_labels(&map, &km_markers));
             typst_src.push_str("]\n#v(1cm)\n");
         }
         Err(e) => {
@@ -188,6 +273,15 @@ pub async fn create_pdf(app: AppHandle, event_id: Option<String>) -> Result<(),
         }
     };
 
+    // Passage de la course au niveau de chaque point
+    let passages = match &event_id {
+        Some(eid) => {
+            let pool = db::get_db_pool(&app).await?;
+            race::passages_by_target(race::load_event_passage_times(&pool, eid).await?)
+        }
+        None => Default::default(),
+    };
+
     for (i, p) in data.iter().enumerate() {
         let point_number = i + 1;
         let heading = format!("== Point {}", point_number);
@@ -199,6 +293,17 @@ pub async fn create_pdf(app: AppHandle, event_id: Option<String>) -> Result<(),
             writeln!(typst_src, "*Nom:* {}", name).unwrap();
         }
 
+        if let Some(passage) = passages.get(&("point".to_string(), p.id.clone())) {
+            writeln!(
+                typst_src,
+                "*Passage course:* {} (1er coureur le {}, dernier le {})\n",
+                format_passage(passage),
+                format_date(&passage.first_passage),
+                format_date(&passage.last_passage)
+            )
+            .unwrap();
+        }
+
         // Afficher les commentaires s'il y en a
         if let Some(comment) = &p.comment {
             writeln!(typst_src, "*Commentaires:* {}", comment).unwrap();
@@ -339,12 +444,15 @@ pub async fn create_team_mission_pdf(
         });
     }
 
+    // Ordre de la tournée optimisée (départ du dépôt de l'équipe)
+    let itinerary =
+        db::compute_team_itinerary(&pool, &team_id, Some(&event_id), None, None).await?;
     mission_data.sort_by_key(|m| {
-        if m.action_type.as_deref() == Some("pose") {
-            m.equipement.date_pose.clone()
-        } else {
-            m.equipement.date_depose.clone()
-        }
+        itinerary
+            .stops
+            .iter()
+            .position(|s| Some(&s.action_id) == m.action_id.as_ref())
+            .unwrap_or(usize::MAX)
     });
 
     let temp_dir = std::env::temp_dir().join(format!("mission_gen_{}", team_id));
@@ -394,6 +502,8 @@ pub async fn create_team_mission_pdf(
                 comment: None,
                 r#type: None, // Utilisation de r#type pour échapper le mot clé
                 pictures: vec![],
+                parcours_id: None,
+                chainage_m: None,
             });
         }
     }
@@ -419,65 +529,11 @@ pub async fn create_team_mission_pdf(
                     map.image_path
                 ));
 
-                // Pour gérer les superpositions, on compte combien de fois une coordonnée (discrétisée) a été utilisée
-                let mut coord_counts: std::collections::HashMap<(i64, i64), usize> =
-                    std::collections::HashMap::new();
-
-                // Placement des marqueurs NUMÉROTÉS
-                for (index, m) in mission_data.iter().enumerate() {
-                    let point_number = index + 1;
-
-                    if let Some(coord) = m.equipement.coordinates.first() {
-                        let (pct_x, pct_y) = map.get_percent_pos(coord.x, coord.y);
-
-                        if (0.0..=1.0).contains(&pct_x) && (0.0..=1.0).contains(&pct_y) {
-                            // Plus de distinction de couleur entre pose et retrait
-                            let marker_color = "red";
-                            let marker = format!(
-                                r#"#circle(radius: 3pt, fill: {}, stroke: 1pt + white)"#,
-                                marker_color
-                            );
-                            let number_label = format!(
-                                r#"#box(fill: white, stroke: 0.5pt + gray, radius: 2pt, inset: 2pt)[#text(size: 8pt, weight: "bold")[{}]]"#,
-                                point_number
-                            );
-
-                            // Discrétisation pour détecter les superpositions (précision ~0.1%)
-                            let x_key = (pct_x * 1000.0) as i64;
-                            let y_key = (pct_y * 1000.0) as i64;
-                            let count = *coord_counts.get(&(x_key, y_key)).unwrap_or(&0);
-                            coord_counts.insert((x_key, y_key), count + 1);
-
-                            // Liste d'offsets prédéfinis pour éviter les chevauchements
-                            // (dx, dy) en points (pt)
-                            let offsets = [
-                                (8, -8),    // 0: haut-droite
-                                (8, 8),     // 1: bas-droite
-                                (-20, -8),  // 2: haut-gauche
-                                (-20, 8),   // 3: bas-gauche
-                                (8, -20),   // 4: plus haut-droite
-                                (8, 20),    // 5: plus bas-droite
-                                (-20, -20), // 6: plus haut-gauche
-                                (-20, 20),  // 7: plus bas-gauche
-                            ];
-
-                            // On utilise le compteur pour choisir l'offset, modulo la taille de la liste
-                            let (dx_offset, dy_offset) = offsets[count % offsets.len()];
-
-                            typst_src.push_str(&format!(
-                                r#"#place(top + left, dx: {}% - 1.5pt, dy: {}% - 1.5pt)[ {} ]#place(top + left, dx: {}% + {}pt, dy: {}% {}pt)[ {} ]"#,
-                                pct_x * 100.0,
-                                pct_y * 100.0,
-                                marker,
-                                pct_x * 100.0,
-                                dx_offset,
-                                pct_y * 100.0,
-                                if dy_offset >= 0 { format!("+ {}", dy_offset) } else { format!("- {}", -dy_offset) },
-                                number_label
-                            ));
-                        }
-                    }
-                }
+                let positions: Vec<Option<(f64, f64)>> = mission_data
+                    .iter()
+                    .map(|m| m.equipement.coordinates.first().map(|c| (c.x, c.y)))
+                    .collect();
+                typst_src.push_str(&numbered_markers(&map, &positions));
                 typst_src.push_str("]\n#v(1cm)\n");
             }
             Err(e) => {
@@ -487,26 +543,19 @@ pub async fn create_team_mission_pdf(
     }
 
     // --- TABLEAU PLANNING ---
+    let parcours = race::load_race_parcours(&pool, &event_id).await?;
+
     typst_src.push_str("== Planning des Missions\n#v(0.5em)\n");
     typst_src.push_str(
         r#"#table(
-        columns: (auto, auto, auto, 1fr, 40pt),
+        columns: (auto, auto, auto, auto, 1fr, auto, 40pt),
         inset: 7pt,
         align: (col, row) => if row == 0 { center } else { left },
         fill: (x, y) => if y == 0 { luma(240) },
-        [*Numéro*], [*Date/Heure*], [*Action*], [*Équipement*], [*Fait*],
+        [*Numéro*], [*Date/Heure*], [*Arrivée estimée*], [*Action*], [*Équipement*], [*Course*], [*Fait*],
     "#,
     );
 
-    // Tri chronologique
-    mission_data.sort_by_key(|m| {
-        if m.action_type.as_deref() == Some("pose") {
-            m.equipement.date_pose.clone()
-        } else {
-            m.equipement.date_depose.clone()
-        }
-    });
-
     for (index, m) in mission_data.iter().enumerate() {
         let point_number = index + 1; // 1, 2, 3...
         let is_pose = m.action_type.as_deref() == Some("pose");
@@ -522,11 +571,7 @@ pub async fn create_team_mission_pdf(
             None => "Non planifié".to_string(),
         };
 
-        let action_label = if is_pose {
-            r#"#text(fill: green, weight: "bold")[POSE]"#
-        } else {
-            r#"#text(fill: red, weight: "bold")[RETRAIT]"#
-        };
+        let action_label = action_type_cell(m.action_type.as_deref());
 
         let type_name = m.equipement.type_name.as_deref().unwrap_or("Inconnu");
         let length = m.equipement.length.unwrap_or(0);
@@ -538,15 +583,64 @@ pub async fn create_team_mission_pdf(
             type_name.to_string()
         };
 
+        // Arrivée estimée et distance cumulée depuis le départ de la tournée
+        let stop = itinerary
+            .stops
+            .iter()
+            .find(|s| Some(&s.action_id) == m.action_id.as_ref());
+        let eta_str = match stop {
+            Some(s) => format!(
+                "{} ({:.1} km)",
+                s.eta
+                    .as_deref()
+                    .map(format_date)
+                    .unwrap_or_else(|| "-".to_string()),
+                s.cumulative_distance_m / 1000.0
+            ),
+            None => "-".to_string(),
+        };
+
+        // Passage de la course au niveau de l'équipement
+        let points: Vec<(f64, f64)> = m
+            .equipement
+            .coordinates
+            .iter()
+            .map(|c| (c.x, c.y))
+            .collect();
+        let passage_str = race::passage_time_on_parcours(&parcours, "equipement", None, &points)
+            .map(|p| format_passage(&p))
+            .unwrap_or_else(|| "-".to_string());
+
         writeln!(
             typst_src,
-            "[{}], [{}], [{}], [{}], [ ],",
-            point_number, display_date, action_label, equip_str
+            "[{}], [{}], [{}], [{}], [{}], [{}], [ ],",
+            point_number, display_date, eta_str, action_label, equip_str, passage_str
         )
         .unwrap();
     }
     typst_src.push_str(")\n");
 
+    // --- CONFLITS DE PLANNING DE L'ÉQUIPE ---
+    let actions = db::fetch_scheduled_actions(&pool, &event_id).await?;
+    let conflicts: Vec<_> = detect_conflicts(&actions, DEFAULT_TRAVEL_SPEED_KMH)
+        .into_iter()
+        .filter(|c| c.team_id == team_id)
+        .collect();
+
+    if !conflicts.is_empty() {
+        typst_src.push_str("#v(1cm)\n== Conflits de planning\n#v(0.5em)\n");
+        for conflict in &conflicts {
+            writeln!(
+                typst_src,
+                "- #text(fill: red)[{} → {}] : {}",
+                format_date(&conflict.first_time),
+                format_date(&conflict.second_time),
+                conflict.message
+            )
+            .unwrap();
+        }
+    }
+
     typst_src.push_str(
         r#"
         #v(2cm)
@@ -596,6 +690,604 @@ pub async fn create_team_mission_pdf(
     Ok(())
 }
 
+// =============================================================================
+// 3. DIAGRAMME DE GANTT DU PLANNING
+// =============================================================================
+#[tauri::command]
+pub async fn create_gantt_pdf(app: AppHandle, event_id: String) -> Result<(), String> {
+    let pool = db::get_db_pool(&app).await?;
+    let chart = gantt::load_gantt_chart(&pool, &event_id).await?;
+
+    let temp_dir = std::env::temp_dir().join(format!("gantt_gen_{}", event_id));
+    if temp_dir.exists() {
+        fs::remove_dir_all(&temp_dir).ok();
+    }
+    fs::create_dir_all(&temp_dir).map_err(|e| e.to_string())?;
+
+    // Le diagramme est rendu en SVG puis intégré tel quel dans la page
+    fs::write(temp_dir.join("gantt.svg"), gantt::render_gantt_svg(&chart))
+        .map_err(|e| e.to_string())?;
+
+    let font_bytes = load_fonts_from_directory(Path::new("./fonts"))?;
+
+    let typst_src = r#"
+        #set page(paper: "a4", flipped: true, margin: 1cm)
+        #set text(font: "Liberation Sans", size: 10pt)
+
+        #align(center)[#image("gantt.svg", width: 100%)]
+
+        #v(1fr)
+        #line(length: 100%, stroke: 0.5pt + gray)
+        #text(8pt, gray)[Document généré le #datetime.today().display()]
+    "#
+    .to_string();
+
+    let template = TypstEngine::builder()
+        .main_file(typst_src)
+        .fonts(font_bytes)
+        .with_file_system_resolver(temp_dir.clone())
+        .build();
+
+    let doc = template
+        .compile()
+        .output
+        .map_err(|e| format!("Typst failed: {:?}", e))?;
+
+    let pdf_bytes = typst_pdf::pdf(&doc, &PdfOptions::default())
+        .map_err(|e| format!("PDF export failed: {:?}", e))?;
+
+    let (dir_path, file_name) =
+        utils::create_file_name(format!("Gantt_{}", chart.event_name), "pdf".to_string());
+    if let Some(save_path) = utils::show_save_dialog(&file_name, &dir_path, "pdf".to_string()) {
+        fs::write(save_path, pdf_bytes).map_err(|e| e.to_string())?;
+    }
+
+    let _ = fs::remove_dir_all(&temp_dir);
+    Ok(())
+}
+
+// =============================================================================
+// 4. PLANNING COMPLET (une section par équipe)
+// =============================================================================
+#[tauri::command]
+pub async fn create_planning_pdf(app: AppHandle, event_id: Option<String>) -> Result<(), String> {
+    let pool = db::get_db_pool(&app).await?;
+
+    let event_name: Option<String> = match &event_id {
+        Some(eid) => sqlx::query_scalar("SELECT name FROM event WHERE id = ?")
+            .bind(eid)
+            .fetch_optional(&pool)
+            .await
+            .map_err(|e| e.to_string())?,
+        None => None,
+    };
+
+    let team_rows = sqlx::query(
+        "SELECT id, name FROM team WHERE (? IS NULL OR event_id = ?) ORDER BY name ASC",
+    )
+    .bind(&event_id)
+    .bind(&event_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let durations = durations::load_action_durations(&pool, event_id.as_deref(), None).await?;
+    let passages = match &event_id {
+        Some(eid) => race::passages_by_target(race::load_event_passage_times(&pool, eid).await?),
+        None => Default::default(),
+    };
+    let km_markers = match &event_id {
+        Some(eid) => markers::load_km_markers(&pool, eid).await?,
+        None => vec![],
+    };
+
+    let temp_dir = std::env::temp_dir().join("planning_pdf_gen");
+    if temp_dir.exists() {
+        fs::remove_dir_all(&temp_dir).ok();
+    }
+    fs::create_dir_all(&temp_dir).map_err(|e| e.to_string())?;
+
+    let font_bytes = load_fonts_from_directory(Path::new("./fonts"))?;
+
+    let mut typst_src = String::new();
+    typst_src.push_str(
+        r#"
+        #set page(paper: "a4", margin: 1.5cm, numbering: "1 / 1")
+        #set text(font: "Liberation Sans", size: 10pt)
+        #set table(stroke: 0.5pt + gray)
+        "#,
+    );
+    writeln!(
+        typst_src,
+        r#"#align(center, text(18pt, weight: "bold")[PLANNING {}])"#,
+        utils::escape_typst(&event_name.clone().unwrap_or_default().to_uppercase())
+    )
+    .unwrap();
+    typst_src.push_str("#v(0.5cm)\n");
+
+    // Sections par équipe (construites avant le récapitulatif, qui en reprend les totaux)
+    let mut summary = Vec::new();
+    let mut sections = String::new();
+
+    for (team_index, row) in team_rows.iter().enumerate() {
+        let team_id: String = row.get("id");
+        let team_name: String = row.get::<Option<String>, _>("name").unwrap_or_default();
+
+        let members = sqlx::query(
+            r#"
+            SELECT p.firstname, p.lastname, p.phone_number
+            FROM member m
+            JOIN person p ON m.person_id = p.id
+            WHERE m.team_id = ?
+            ORDER BY p.lastname ASC, p.firstname ASC
+        "#,
+        )
+        .bind(&team_id)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let itinerary =
+            db::compute_team_itinerary(&pool, &team_id, event_id.as_deref(), None, None).await?;
+
+        let detail_rows = sqlx::query(
+            r#"
+            SELECT
+                a.id, e.quantity, e.length_per_unit, e.description, t.name as type_name,
+                COALESCE(a.status, CASE WHEN a.is_done THEN 'done' ELSE 'planned' END) as status
+            FROM action a
+            JOIN equipement e ON a.equipement_id = e.id
+            LEFT JOIN type t ON e.type_id = t.id
+            WHERE a.team_id = ? AND (? IS NULL OR e.event_id = ?)
+        "#,
+        )
+        .bind(&team_id)
+        .bind(&event_id)
+        .bind(&event_id)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        // action_id -> (type, quantité, longueur unitaire, description, statut)
+        let details: std::collections::HashMap<String, _> = detail_rows
+            .into_iter()
+            .map(|r| {
+                (
+                    r.get::<String, _>("id"),
+                    (
+                        r.get::<Option<String>, _>("type_name"),
+                        r.get::<Option<i64>, _>("quantity"),
+                        r.get::<Option<i64>, _>("length_per_unit"),
+                        r.get::<Option<String>, _>("description"),
+                        r.get::<String, _>("status"),
+                    ),
+                )
+            })
+            .collect();
+
+        if team_index > 0 {
+            sections.push_str("#pagebreak()\n");
+        }
+        writeln!(sections, "== Équipe {}\n", utils::escape_typst(&team_name)).unwrap();
+
+        // --- MEMBRES ---
+        if members.is_empty() {
+            sections.push_str("*Membres :* aucun membre affecté\n\n");
+        } else {
+            let names: Vec<String> = members
+                .iter()
+                .map(|m| {
+                    let firstname: Option<String> = m.get("firstname");
+                    let lastname: Option<String> = m.get("lastname");
+                    let phone: Option<String> = m.get("phone_number");
+                    let name = format!(
+                        "{} {}",
+                        firstname.unwrap_or_default(),
+                        lastname.unwrap_or_default()
+                    );
+                    match phone.filter(|p| !p.is_empty()) {
+                        Some(phone) => format!("{} ({})", name.trim(), phone),
+                        None => name.trim().to_string(),
+                    }
+                })
+                .collect();
+            writeln!(
+                sections,
+                "*Membres ({}) :* {}\n",
+                names.len(),
+                utils::escape_typst(&names.join(", "))
+            )
+            .unwrap();
+        }
+
+        if itinerary.stops.is_empty() {
+            sections.push_str("#text(gray)[Aucune action planifiée]\n");
+            summary.push(PlanningTeamSummary {
+                team_name,
+                member_count: members.len(),
+                action_count: 0,
+                distance_m: 0.0,
+                first: None,
+                last: None,
+            });
+            continue;
+        }
+
+        // --- MINI-CARTE DE LA TOURNÉE ---
+        let positions: Vec<Option<(f64, f64)>> =
+            itinerary.stops.iter().map(|s| s.x.zip(s.y)).collect();
+        let map_points: Vec<db::PointWithDetails> = positions
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(i, (x, y))| db::PointWithDetails {
+                id: i.to_string(),
+                x: *x,
+                y: *y,
+                name: None,
+                event_id: None,
+                status: None,
+                comment: None,
+                r#type: None,
+                pictures: vec![],
+                parcours_id: None,
+                chainage_m: None,
+            })
+            .collect();
+
+        // Chaque carte dans son dossier : le générateur utilise un nom de fichier fixe
+        let map_dir_name = format!("team_{}", team_index);
+        let map_dir = temp_dir.join(&map_dir_name);
+        fs::create_dir_all(&map_dir).map_err(|e| e.to_string())?;
+
+        if !map_points.is_empty() {
+            match map_pdf::generate_cropped_map(&map_dir, &map_points).await {
+                Ok(map) => {
+                    writeln!(
+                        sections,
+                        r#"#block(width: 100%, height: 7cm, clip: true, stroke: 1pt + gray)[#image("{}/{}", width: 100%)"#,
+                        map_dir_name, map.image_path
+                    )
+                    .unwrap();
+                    sections.push_str(&km_marker_labels(&map, &km_markers));
+                    sections.push_str(&numbered_markers(&map, &positions));
+                    sections.push_str("]\n#v(0.3cm)\n");
+                }
+                Err(e) => eprintln!("⚠️ Erreur carte équipe {} : {}", team_name, e),
+            }
+        }
+
+        // --- ACTIONS DANS L'ORDRE DE LA TOURNÉE ---
+        sections.push_str(
+            r#"#table(
+            columns: (auto, auto, auto, auto, auto, 1fr, auto, auto, auto, auto),
+            inset: 5pt,
+            align: (col, row) => if row == 0 { center } else { left },
+            fill: (x, y) => if y == 0 { luma(240) },
+            [*N°*], [*Heure prévue*], [*Arrivée estimée*], [*Durée*], [*Action*], [*Équipement*], [*Qté*], [*Longueur*], [*Course*], [*Statut*],
+        "#,
+        );
+
+        let mut total_length = 0.0;
+        for stop in &itinerary.stops {
+            let (type_name, quantity, length_per_unit, description, status) = details
+                .get(&stop.action_id)
+                .cloned()
+                .unwrap_or((None, None, None, None, "planned".to_string()));
+
+            let length = quantity.unwrap_or(0) * length_per_unit.unwrap_or(0);
+            total_length += length as f64;
+
+            let equipement = match description.filter(|d| !d.is_empty()) {
+                Some(d) => format!("{} - {}", type_name.as_deref().unwrap_or("Inconnu"), d),
+                None => type_name.unwrap_or_else(|| "Inconnu".to_string()),
+            };
+            let action_label = action_type_cell(stop.action_type.as_deref());
+            let status_label = ActionStatus::parse(&status)
+                .map(|s| s.label())
+                .unwrap_or("-");
+            let passage_label = passages
+                .get(&("equipement".to_string(), stop.equipement_id.clone()))
+                .map(format_passage)
+                .unwrap_or_else(|| "-".to_string());
+
+            writeln!(
+                sections,
+                "[{}], [{}], [{}], [{} min], [{}], [{}], [{}], [{}], [{}], [{}],",
+                stop.order,
+                stop.scheduled_time
+                    .as_deref()
+                    .map(format_date)
+                    .unwrap_or_else(|| "Non planifié".to_string()),
+                stop.eta
+                    .as_deref()
+                    .map(format_date)
+                    .unwrap_or_else(|| "-".to_string()),
+                durations::duration_of(&durations, &stop.action_id),
+                action_label,
+                utils::escape_typst(&equipement),
+                quantity
+                    .map(|q| q.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                if length > 0 {
+                    format!("{} m", length)
+                } else {
+                    "-".to_string()
+                },
+                passage_label,
+                status_label
+            )
+            .unwrap();
+        }
+        sections.push_str(")\n");
+
+        writeln!(
+            sections,
+            "#v(0.3cm)\n*Trajet total :* {:.1} km — *Longueur d'équipement :* {:.0} m\n",
+            itinerary.total_distance_m / 1000.0,
+            total_length
+        )
+        .unwrap();
+
+        let first = itinerary
+            .stops
+            .iter()
+            .find_map(|s| s.scheduled_time.clone());
+        let last = itinerary
+            .stops
+            .iter()
+            .rev()
+            .find_map(|s| s.scheduled_time.clone());
+        summary.push(PlanningTeamSummary {
+            team_name,
+            member_count: members.len(),
+            action_count: itinerary.stops.len(),
+            distance_m: itinerary.total_distance_m,
+            first,
+            last,
+        });
+    }
+
+    // --- RÉCAPITULATIF ---
+    typst_src.push_str(&planning_summary_table(&summary));
+    typst_src.push_str("#pagebreak()\n");
+    typst_src.push_str(&sections);
+
+    typst_src.push_str(
+        r#"
+        #v(1fr)
+        #line(length: 100%, stroke: 0.5pt + gray)
+        #text(8pt, gray)[Document généré le #datetime.today().display()]
+    "#,
+    );
+
+    let template = TypstEngine::builder()
+        .main_file(typst_src)
+        .fonts(font_bytes)
+        .with_file_system_resolver(temp_dir.clone())
+        .build();
+
+    let doc = template
+        .compile()
+        .output
+        .map_err(|e| format!("Typst failed: {:?}", e))?;
+
+    let pdf_bytes = typst_pdf::pdf(&doc, &PdfOptions::default())
+        .map_err(|e| format!("PDF export failed: {:?}", e))?;
+
+    let (dir_path, file_name) = utils::create_file_name(
+        format!("Planning_{}", event_name.unwrap_or_default()),
+        "pdf".to_string(),
+    );
+    if let Some(save_path) = utils::show_save_dialog(&file_name, &dir_path, "pdf".to_string()) {
+        fs::write(save_path, pdf_bytes).map_err(|e| e.to_string())?;
+    }
+
+    println!("[PDF] 📄 Planning exporté : {} équipe(s)", summary.len());
+
+    let _ = fs::remove_dir_all(&temp_dir);
+    Ok(())
+}
+
+// =============================================================================
+// 5. CALENDRIER DE FERMETURE DES RUES (arrêté de circulation)
+// =============================================================================
+#[tauri::command]
+pub async fn create_street_closures_pdf(
+    app: AppHandle,
+    event_id: String,
+    close_before_minutes: Option<i64>,
+    reopen_after_minutes: Option<i64>,
+) -> Result<(), String> {
+    let pool = db::get_db_pool(&app).await?;
+    let closures = closures::load_street_closures(
+        &pool,
+        &event_id,
+        close_before_minutes,
+        reopen_after_minutes,
+    )
+    .await?;
+
+    let event_name: String = sqlx::query_scalar("SELECT name FROM event WHERE id = ?")
+        .bind(&event_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .unwrap_or_default();
+
+    let font_bytes = load_fonts_from_directory(Path::new("./fonts"))?;
+
+    let mut typst_src = String::new();
+    typst_src.push_str(
+        r#"
+        #set page(paper: "a4", flipped: true, margin: 1.5cm, numbering: "1 / 1")
+        #set text(font: "Liberation Sans", size: 10pt)
+        #set table(stroke: 0.5pt + gray)
+        "#,
+    );
+    writeln!(
+        typst_src,
+        r#"#align(center, text(18pt, weight: "bold")[FERMETURE DES RUES — {}])"#,
+        utils::escape_typst(&event_name.to_uppercase())
+    )
+    .unwrap();
+    writeln!(
+        typst_src,
+        "#align(center)[Fermeture {} min avant le premier coureur, réouverture {} min après le dernier]\n#v(0.5cm)",
+        close_before_minutes.unwrap_or(closures::DEFAULT_CLOSE_BEFORE_MINUTES),
+        reopen_after_minutes.unwrap_or(closures::DEFAULT_REOPEN_AFTER_MINUTES)
+    )
+    .unwrap();
+
+    if closures.is_empty() {
+        typst_src.push_str("#text(gray)[Aucune rue empruntée par les parcours de l'événement]\n");
+    } else {
+        typst_src.push_str(
+            r#"#table(
+            columns: (auto, 1fr, auto, auto, auto, auto, auto, auto),
+            inset: 5pt,
+            align: (col, row) => if row == 0 { center } else { left },
+            fill: (x, y) => if y == 0 { luma(240) },
+            [*Parcours*], [*Rue*], [*Du km*], [*Au km*], [*Fermeture*], [*Premier coureur*], [*Dernier coureur*], [*Réouverture*],
+        "#,
+        );
+        for closure in &closures {
+            writeln!(
+                typst_src,
+                "[{}], [{}], [{:.2}], [{:.2}], [*{}*], [{}], [{}], [*{}*],",
+                utils::escape_typst(closure.parcours_name.as_deref().unwrap_or("-")),
+                utils::escape_typst(&closure.street),
+                closure.from_m / 1000.0,
+                closure.to_m / 1000.0,
+                format_date(&closure.closes_at),
+                format_date(&closure.first_passage),
+                format_date(&closure.last_passage),
+                format_date(&closure.reopens_at)
+            )
+            .unwrap();
+        }
+        typst_src.push_str(")\n");
+    }
+
+    typst_src.push_str(
+        r#"
+        #v(1fr)
+        #line(length: 100%, stroke: 0.5pt + gray)
+        #text(8pt, gray)[Document généré le #datetime.today().display()]
+    "#,
+    );
+
+    let template = TypstEngine::builder()
+        .main_file(typst_src)
+        .fonts(font_bytes)
+        .build();
+
+    let doc = template
+        .compile()
+        .output
+        .map_err(|e| format!("Typst failed: {:?}", e))?;
+
+    let pdf_bytes = typst_pdf::pdf(&doc, &PdfOptions::default())
+        .map_err(|e| format!("PDF export failed: {:?}", e))?;
+
+    let (dir_path, file_name) =
+        utils::create_file_name(format!("Fermeture_rues_{}", event_name), "pdf".to_string());
+    if let Some(save_path) = utils::show_save_dialog(&file_name, &dir_path, "pdf".to_string()) {
+        fs::write(save_path, pdf_bytes).map_err(|e| e.to_string())?;
+    }
+
+    println!(
+        "[PDF] 🚧 Fermeture des rues exportée : {} portion(s)",
+        closures.len()
+    );
+    Ok(())
+}
+
+/// Bornes kilométriques visibles sur une carte (carré bleu et distance en km)
+fn km_marker_labels(map: &map_pdf::CroppedMap, km_markers: &[Interest]) -> String {
+    let mut labels = String::new();
+
+    for marker in km_markers {
+        let Some(km) = marker.marker_km else {
+            continue;
+        };
+        let (pct_x, pct_y) = map.get_percent_pos(marker.x, marker.y);
+        if !(0.0..=1.0).contains(&pct_x) || !(0.0..=1.0).contains(&pct_y) {
+            continue;
+        }
+
+        labels.push_str(&format!(
+            r#"#place(top + left, dx: {}% - 4pt, dy: {}% - 4pt)[#box(fill: blue, radius: 1pt, inset: 1.5pt)[#text(size: 5pt, fill: white, weight: "bold")[{}]]]"#,
+            pct_x * 100.0,
+            pct_y * 100.0,
+            km.to_string().replace('.', ",")
+        ));
+    }
+    labels
+}
+
+/// Marqueurs numérotés (1, 2, 3...) à placer sur une carte, dans l'ordre de `positions`.
+/// Les points superposés voient leur numéro décalé pour rester lisibles.
+fn numbered_markers(map: &map_pdf::CroppedMap, positions: &[Option<(f64, f64)>]) -> String {
+    let mut markers = String::new();
+
+    // Pour gérer les superpositions, on compte combien de fois une coordonnée (discrétisée) a été utilisée
+    let mut coord_counts: std::collections::HashMap<(i64, i64), usize> =
+        std::collections::HashMap::new();
+
+    // Liste d'offsets prédéfinis pour éviter les chevauchements
+    // (dx, dy) en points (pt)
+    let offsets = [
+        (8, -8),    // 0: haut-droite
+        (8, 8),     // 1: bas-droite
+        (-20, -8),  // 2: haut-gauche
+        (-20, 8),   // 3: bas-gauche
+        (8, -20),   // 4: plus haut-droite
+        (8, 20),    // 5: plus bas-droite
+        (-20, -20), // 6: plus haut-gauche
+        (-20, 20),  // 7: plus bas-gauche
+    ];
+
+    for (index, position) in positions.iter().enumerate() {
+        let Some((lon, lat)) = position else {
+            continue;
+        };
+        let (pct_x, pct_y) = map.get_percent_pos(*lon, *lat);
+        if !(0.0..=1.0).contains(&pct_x) || !(0.0..=1.0).contains(&pct_y) {
+            continue;
+        }
+
+        let marker = r#"#circle(radius: 3pt, fill: red, stroke: 1pt + white)"#;
+        let number_label = format!(
+            r#"#box(fill: white, stroke: 0.5pt + gray, radius: 2pt, inset: 2pt)[#text(size: 8pt, weight: "bold")[{}]]"#,
+            index + 1
+        );
+
+        // Discrétisation pour détecter les superpositions (précision ~0.1%)
+        let x_key = (pct_x * 1000.0) as i64;
+        let y_key = (pct_y * 1000.0) as i64;
+        let count = *coord_counts.get(&(x_key, y_key)).unwrap_or(&0);
+        coord_counts.insert((x_key, y_key), count + 1);
+
+        // On utilise le compteur pour choisir l'offset, modulo la taille de la liste
+        let (dx_offset, dy_offset) = offsets[count % offsets.len()];
+
+        markers.push_str(&format!(
+            r#"#place(top + left, dx: {}% - 1.5pt, dy: {}% - 1.5pt)[ {} ]#place(top + left, dx: {}% + {}pt, dy: {}% {}pt)[ {} ]"#,
+            pct_x * 100.0,
+            pct_y * 100.0,
+            marker,
+            pct_x * 100.0,
+            dx_offset,
+            pct_y * 100.0,
+            if dy_offset >= 0 { format!("+ {}", dy_offset) } else { format!("- {}", -dy_offset) },
+            number_label
+        ));
+    }
+
+    markers
+}
+
 fn load_fonts_from_directory(fonts_dir: &Path) -> Result<Vec<Vec<u8>>, String> {
     let mut fonts = Vec::new();
 
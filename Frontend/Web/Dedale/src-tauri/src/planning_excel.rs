@@ -0,0 +1,814 @@
+// Planning au format Excel, dans les deux sens
+// L'export écrit une feuille « Planning » (une ligne par action, identifiée par son ID).
+// Les superviseurs y modifient équipes et horaires ; l'import relit le classeur, compare
+// chaque ligne à la base (aperçu des différences) puis applique le tout en une transaction.
+
+use crate::db;
+use crate::progress::emit_event_progress;
+use crate::race::{
+    load_equipement_points, load_race_parcours, passage_time_on_parcours, PassageTime,
+};
+use crate::types::ActionStatus;
+use crate::utils;
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use rust_xlsxwriter::{Color, Format, Workbook};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, Sqlite, SqlitePool, Transaction};
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Read};
+use tauri::AppHandle;
+
+/// Nom de la feuille écrite à l'export et cherchée en priorité à l'import
+pub const PLANNING_SHEET_NAME: &str = "Planning";
+
+/// Colonnes de la feuille Planning. Seules l'ID, l'équipe et l'heure sont relues à l'import.
+pub const PLANNING_HEADERS: [&str; 9] = [
+    "ID action",
+    "Équipe",
+    "Action",
+    "Type",
+    "Description",
+    "Heure prévue",
+    "Statut",
+    "Premier coureur",
+    "Dernier coureur",
+];
+
+const ID_COLUMN: &str = "ID action";
+const TEAM_COLUMN: &str = "Équipe";
+const TIME_COLUMN: &str = "Heure prévue";
+
+/// Ligne exportée dans la feuille Planning
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanningSheetRow {
+    pub action_id: String,
+    pub team_name: String,
+    pub action_type: String,
+    pub type_name: String,
+    pub description: String,
+    pub scheduled_time: Option<String>,
+    pub status: String,
+    /// Passage de la course au niveau de l'équipement
+    pub first_passage: Option<String>,
+    pub last_passage: Option<String>,
+}
+
+/// Cellule lue dans un classeur xlsx
+#[derive(Debug, Clone, PartialEq)]
+pub enum XlsxCell {
+    Empty,
+    Text(String),
+    Number(f64),
+}
+
+/// Ligne d'un classeur xlsx (numéro de ligne Excel, à partir de 1)
+#[derive(Debug, Clone, PartialEq)]
+pub struct XlsxRow {
+    pub number: u32,
+    pub cells: Vec<XlsxCell>,
+}
+
+/// Ligne de planning relue dans le classeur
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedRow {
+    pub row: u32,
+    pub action_id: String,
+    pub team_name: String,
+    /// Texte de la cellule (les dates saisies dans Excel sont converties en "YYYY-MM-DDTHH:MM")
+    pub scheduled_time: Option<String>,
+}
+
+/// Action telle qu'en base, pour la comparaison
+#[derive(Debug, Clone)]
+pub struct CurrentAction {
+    pub action_id: String,
+    pub label: String,
+    pub team_id: String,
+    pub team_name: Option<String>,
+    pub scheduled_time: Option<String>,
+}
+
+/// Modification proposée par le classeur pour une action
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlanningImportChange {
+    pub row: u32,
+    pub action_id: String,
+    pub label: String,
+    pub previous_team_id: String,
+    pub previous_team_name: Option<String>,
+    pub team_id: String,
+    pub team_name: Option<String>,
+    pub previous_scheduled_time: Option<String>,
+    pub scheduled_time: Option<String>,
+}
+
+/// Ligne du classeur refusée à la validation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlanningImportIssue {
+    pub row: u32,
+    pub action_id: Option<String>,
+    pub message: String,
+}
+
+/// Aperçu de l'import : rien n'est écrit tant que `apply_planning_import` n'est pas appelé
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlanningImportPreview {
+    pub event_id: String,
+    pub changes: Vec<PlanningImportChange>,
+    pub errors: Vec<PlanningImportIssue>,
+    /// Lignes valides identiques à la base
+    pub unchanged: usize,
+}
+
+// ==================== Fonctions helper publiques et testables ====================
+
+/// Classeur d'export du planning (feuille unique « Planning »)
+pub fn build_planning_workbook(rows: &[PlanningSheetRow]) -> Result<Workbook, String> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet
+        .set_name(PLANNING_SHEET_NAME)
+        .map_err(|e| e.to_string())?;
+
+    let header_format = Format::new()
+        .set_bold()
+        .set_background_color(Color::RGB(0xE0E0E0))
+        .set_border(rust_xlsxwriter::FormatBorder::Thin);
+    // L'ID sert à retrouver l'action à l'import : on le grise pour décourager sa modification
+    let id_format = Format::new().set_font_color(Color::Gray);
+
+    for (col, header) in PLANNING_HEADERS.iter().enumerate() {
+        worksheet
+            .write_string_with_format(0, col as u16, *header, &header_format)
+            .map_err(|e| e.to_string())?;
+        worksheet
+            .set_column_width(col as u16, if col == 0 { 38 } else { 18 })
+            .map_err(|e| e.to_string())?;
+    }
+    worksheet
+        .set_freeze_panes(1, 0)
+        .map_err(|e| e.to_string())?;
+
+    for (index, row) in rows.iter().enumerate() {
+        let r = index as u32 + 1;
+        worksheet
+            .write_string_with_format(r, 0, &row.action_id, &id_format)
+            .map_err(|e| e.to_string())?;
+        for (col, value) in [
+            &row.team_name,
+            &row.action_type,
+            &row.type_name,
+            &row.description,
+            row.scheduled_time.as_deref().unwrap_or(""),
+            &row.status,
+            row.first_passage.as_deref().unwrap_or(""),
+            row.last_passage.as_deref().unwrap_or(""),
+        ]
+        .iter()
+        .enumerate()
+        {
+            worksheet
+                .write_string(r, col as u16 + 1, *value)
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(workbook)
+}
+
+/// Index de colonne (0 pour A) d'une référence de cellule ("C12" -> 2)
+pub fn column_index(reference: &str) -> Option<usize> {
+    let letters: String = reference
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect();
+    if letters.is_empty() {
+        return None;
+    }
+    letters
+        .chars()
+        .try_fold(0usize, |acc, c| {
+            let value = c.to_ascii_uppercase() as usize - 'A' as usize + 1;
+            acc.checked_mul(26)?.checked_add(value)
+        })
+        .map(|n| n - 1)
+}
+
+/// Date Excel (jours depuis le 30/12/1899, fraction = heure) arrondie à la minute
+pub fn excel_serial_to_datetime(serial: f64) -> Option<NaiveDateTime> {
+    if !serial.is_finite() || serial < 0.0 {
+        return None;
+    }
+    let base = NaiveDate::from_ymd_opt(1899, 12, 30)?.and_hms_opt(0, 0, 0)?;
+    let minutes = (serial * 24.0 * 60.0).round() as i64;
+    base.checked_add_signed(Duration::minutes(minutes))
+}
+
+/// Lit un horaire saisi dans le classeur : formats du planning, ou saisie française
+/// "JJ/MM/AAAA HH:MM"
+pub fn parse_import_time(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    utils::parse_datetime(value).or_else(|| {
+        ["%d/%m/%Y %H:%M", "%d/%m/%Y %H:%M:%S"]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    })
+}
+
+fn zip_entry(
+    archive: &mut zip::ZipArchive<Cursor<&[u8]>>,
+    name: &str,
+) -> Result<Option<String>, String> {
+    let mut file = match archive.by_name(name) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.to_string()),
+    };
+    let mut content = String::new();
+    file.read_to_string(&mut content)
+        .map_err(|e| e.to_string())?;
+    Ok(Some(content))
+}
+
+/// Texte d'un nœud <si> / <is> (concatène les <t>, y compris en texte enrichi)
+fn rich_text(node: roxmltree::Node) -> String {
+    node.descendants()
+        .filter(|n| n.has_tag_name("t") && !n.ancestors().any(|a| a.has_tag_name("rPh")))
+        .filter_map(|n| n.text())
+        .collect()
+}
+
+/// Chemin de la feuille à lire : « Planning » si elle existe, sinon la première
+fn worksheet_path(workbook_xml: &str, rels_xml: &str) -> Result<String, String> {
+    let workbook = roxmltree::Document::parse(workbook_xml).map_err(|e| e.to_string())?;
+    let sheets: Vec<_> = workbook
+        .descendants()
+        .filter(|n| n.has_tag_name("sheet"))
+        .collect();
+    let sheet = sheets
+        .iter()
+        .find(|s| s.attribute("name") == Some(PLANNING_SHEET_NAME))
+        .or_else(|| sheets.first())
+        .ok_or("Le classeur ne contient aucune feuille")?;
+    let rel_id = sheet
+        .attributes()
+        .find(|a| a.name() == "id")
+        .map(|a| a.value().to_string())
+        .ok_or("Feuille sans relation dans workbook.xml")?;
+
+    let rels = roxmltree::Document::parse(rels_xml).map_err(|e| e.to_string())?;
+    let target = rels
+        .descendants()
+        .find(|n| n.has_tag_name("Relationship") && n.attribute("Id") == Some(rel_id.as_str()))
+        .and_then(|n| n.attribute("Target"))
+        .ok_or_else(|| format!("Relation {} introuvable", rel_id))?;
+
+    Ok(match target.strip_prefix('/') {
+        Some(absolute) => absolute.to_string(),
+        None => format!("xl/{}", target),
+    })
+}
+
+/// Lit la feuille Planning (ou la première feuille) d'un classeur xlsx
+pub fn read_xlsx_rows(bytes: &[u8]) -> Result<Vec<XlsxRow>, String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+        .map_err(|e| format!("Fichier xlsx invalide : {}", e))?;
+
+    let shared_strings: Vec<String> = match zip_entry(&mut archive, "xl/sharedStrings.xml")? {
+        Some(xml) => {
+            let doc = roxmltree::Document::parse(&xml).map_err(|e| e.to_string())?;
+            doc.root_element()
+                .children()
+                .filter(|n| n.has_tag_name("si"))
+                .map(rich_text)
+                .collect()
+        }
+        None => vec![],
+    };
+
+    let sheet_path = match (
+        zip_entry(&mut archive, "xl/workbook.xml")?,
+        zip_entry(&mut archive, "xl/_rels/workbook.xml.rels")?,
+    ) {
+        (Some(workbook), Some(rels)) => worksheet_path(&workbook, &rels)?,
+        _ => "xl/worksheets/sheet1.xml".to_string(),
+    };
+    let sheet_xml = zip_entry(&mut archive, &sheet_path)?
+        .ok_or_else(|| format!("Feuille {} absente du classeur", sheet_path))?;
+    let sheet = roxmltree::Document::parse(&sheet_xml).map_err(|e| e.to_string())?;
+
+    let mut rows = Vec::new();
+    for (index, row) in sheet
+        .descendants()
+        .filter(|n| n.has_tag_name("row"))
+        .enumerate()
+    {
+        let number = row
+            .attribute("r")
+            .and_then(|r| r.parse().ok())
+            .unwrap_or(index as u32 + 1);
+        let mut cells = Vec::new();
+
+        for cell in row.children().filter(|n| n.has_tag_name("c")) {
+            let column = cell
+                .attribute("r")
+                .and_then(column_index)
+                .unwrap_or(cells.len());
+            let raw = cell
+                .children()
+                .find(|n| n.has_tag_name("v"))
+                .and_then(|v| v.text())
+                .unwrap_or("");
+
+            let value = match cell.attribute("t") {
+                Some("s") => raw
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| shared_strings.get(i).cloned())
+                    .map(XlsxCell::Text)
+                    .unwrap_or(XlsxCell::Empty),
+                Some("inlineStr") => cell
+                    .children()
+                    .find(|n| n.has_tag_name("is"))
+                    .map(|n| XlsxCell::Text(rich_text(n)))
+                    .unwrap_or(XlsxCell::Empty),
+                Some("str") => XlsxCell::Text(raw.to_string()),
+                Some("b") | Some("e") => XlsxCell::Empty,
+                _ => raw.parse().map(XlsxCell::Number).unwrap_or(XlsxCell::Empty),
+            };
+
+            if cells.len() <= column {
+                cells.resize(column + 1, XlsxCell::Empty);
+            }
+            cells[column] = value;
+        }
+
+        rows.push(XlsxRow { number, cells });
+    }
+
+    Ok(rows)
+}
+
+fn cell_text(cell: Option<&XlsxCell>) -> Option<String> {
+    match cell {
+        Some(XlsxCell::Text(text)) if !text.trim().is_empty() => Some(text.trim().to_string()),
+        Some(XlsxCell::Number(number)) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+/// Extrait les lignes de planning d'une feuille. La ligne d'en-tête (celle qui contient
+/// « ID action ») donne la position des colonnes ; les lignes sans ID sont ignorées.
+pub fn sheet_to_imported_rows(rows: &[XlsxRow]) -> Result<Vec<ImportedRow>, String> {
+    let header_index = rows
+        .iter()
+        .position(|r| {
+            r.cells
+                .iter()
+                .any(|c| matches!(c, XlsxCell::Text(t) if t.trim() == ID_COLUMN))
+        })
+        .ok_or_else(|| format!("Colonne « {} » introuvable dans le classeur", ID_COLUMN))?;
+
+    let headers = &rows[header_index].cells;
+    let find_column = |name: &str| {
+        headers
+            .iter()
+            .position(|c| matches!(c, XlsxCell::Text(t) if t.trim() == name))
+            .ok_or_else(|| format!("Colonne « {} » introuvable dans le classeur", name))
+    };
+    let id_col = find_column(ID_COLUMN)?;
+    let team_col = find_column(TEAM_COLUMN)?;
+    let time_col = find_column(TIME_COLUMN)?;
+
+    Ok(rows[header_index + 1..]
+        .iter()
+        .filter_map(|r| {
+            let action_id = cell_text(r.cells.get(id_col))?;
+            let scheduled_time = match r.cells.get(time_col) {
+                // Date retapée dans Excel : convertie en date série par le tableur
+                Some(XlsxCell::Number(serial)) => Some(
+                    excel_serial_to_datetime(*serial)
+                        .map(|dt| utils::format_datetime(&dt))
+                        .unwrap_or_else(|| serial.to_string()),
+                ),
+                cell => cell_text(cell),
+            };
+            Some(ImportedRow {
+                row: r.number,
+                action_id,
+                team_name: cell_text(r.cells.get(team_col)).unwrap_or_default(),
+                scheduled_time,
+            })
+        })
+        .collect())
+}
+
+fn same_time(a: Option<&str>, b: Option<&NaiveDateTime>) -> bool {
+    match (a.and_then(utils::parse_datetime), b) {
+        (Some(a), Some(b)) => a == *b,
+        (None, None) => a.is_none_or(|s| s.trim().is_empty()),
+        _ => false,
+    }
+}
+
+/// Compare les lignes du classeur aux actions de l'événement.
+/// `teams` : (id, nom) des équipes de l'événement, reconnues par leur nom (sans casse).
+pub fn diff_planning_rows(
+    event_id: &str,
+    rows: &[ImportedRow],
+    current: &HashMap<String, CurrentAction>,
+    teams: &[(String, Option<String>)],
+) -> PlanningImportPreview {
+    let mut preview = PlanningImportPreview {
+        event_id: event_id.to_string(),
+        changes: vec![],
+        errors: vec![],
+        unchanged: 0,
+    };
+    let mut seen = HashSet::new();
+
+    for row in rows {
+        let issue = |message: String| PlanningImportIssue {
+            row: row.row,
+            action_id: Some(row.action_id.clone()),
+            message,
+        };
+
+        if !seen.insert(row.action_id.as_str()) {
+            preview.errors.push(issue(
+                "Action présente plusieurs fois dans le classeur".to_string(),
+            ));
+            continue;
+        }
+        let Some(action) = current.get(&row.action_id) else {
+            preview
+                .errors
+                .push(issue("Action inconnue pour cet événement".to_string()));
+            continue;
+        };
+
+        let wanted = row.team_name.trim().to_lowercase();
+        let matching: Vec<&(String, Option<String>)> = teams
+            .iter()
+            .filter(|(_, name)| {
+                name.as_deref().map(|n| n.trim().to_lowercase()) == Some(wanted.clone())
+            })
+            .collect();
+        let (team_id, team_name) = match matching.as_slice() {
+            [team] => (*team).clone(),
+            [] if wanted.is_empty() => {
+                preview.errors.push(issue(
+                    "Équipe manquante (une action doit être affectée)".to_string(),
+                ));
+                continue;
+            }
+            [] => {
+                preview.errors.push(issue(format!(
+                    "Équipe « {} » inconnue pour cet événement",
+                    row.team_name
+                )));
+                continue;
+            }
+            _ => {
+                preview.errors.push(issue(format!(
+                    "Plusieurs équipes s'appellent « {} »",
+                    row.team_name
+                )));
+                continue;
+            }
+        };
+
+        let scheduled = match row.scheduled_time.as_deref() {
+            None => None,
+            Some(value) => match parse_import_time(value) {
+                Some(dt) => Some(dt),
+                None => {
+                    preview
+                        .errors
+                        .push(issue(format!("Horaire illisible : « {} »", value)));
+                    continue;
+                }
+            },
+        };
+
+        if team_id == action.team_id
+            && same_time(action.scheduled_time.as_deref(), scheduled.as_ref())
+        {
+            preview.unchanged += 1;
+            continue;
+        }
+
+        preview.changes.push(PlanningImportChange {
+            row: row.row,
+            action_id: action.action_id.clone(),
+            label: action.label.clone(),
+            previous_team_id: action.team_id.clone(),
+            previous_team_name: action.team_name.clone(),
+            team_id,
+            team_name,
+            previous_scheduled_time: action.scheduled_time.clone(),
+            scheduled_time: scheduled.map(|dt| utils::format_datetime(&dt)),
+        });
+    }
+
+    preview
+}
+
+/// Vrai si l'action a été modifiée en base depuis l'aperçu : l'équipe ou l'horaire
+/// actuels ne sont plus ceux relevés dans `previous_team_id` / `previous_scheduled_time`
+pub fn import_change_is_stale(
+    change: &PlanningImportChange,
+    team_id: Option<&str>,
+    scheduled_time: Option<&str>,
+) -> bool {
+    if team_id.unwrap_or_default() != change.previous_team_id {
+        return true;
+    }
+    let previous = change
+        .previous_scheduled_time
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+    match previous {
+        None => !same_time(scheduled_time, None),
+        Some(previous) => match utils::parse_datetime(previous) {
+            Some(dt) => !same_time(scheduled_time, Some(&dt)),
+            None => scheduled_time.map(str::trim) != Some(previous),
+        },
+    }
+}
+
+// ==================== Chargement des données ====================
+
+async fn load_planning_sheet_rows(
+    pool: &SqlitePool,
+    event_id: Option<&str>,
+) -> Result<Vec<PlanningSheetRow>, String> {
+    let rows = sqlx::query(
+        r#"
+        SELECT
+            a.id, a.type, a.scheduled_time, a.equipement_id, e.event_id,
+            COALESCE(a.status, CASE WHEN a.is_done THEN 'done' ELSE 'planned' END) as status,
+            tm.name as team_name, t.name as type_name, e.description
+        FROM action a
+        JOIN equipement e ON a.equipement_id = e.id
+        LEFT JOIN team tm ON a.team_id = tm.id
+        LEFT JOIN type t ON e.type_id = t.id
+        WHERE (? IS NULL OR e.event_id = ?)
+        ORDER BY tm.name ASC, a.scheduled_time ASC
+    "#,
+    )
+    .bind(event_id)
+    .bind(event_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    // Passage de la course par équipement, événement par événement
+    let event_ids: HashSet<String> = rows.iter().map(|r| r.get("event_id")).collect();
+    let mut passages: HashMap<String, PassageTime> = HashMap::new();
+    for eid in &event_ids {
+        let parcours = load_race_parcours(pool, eid).await?;
+        for (equipement_id, points) in load_equipement_points(pool, eid).await? {
+            if let Some(passage) = passage_time_on_parcours(&parcours, "equipement", None, &points)
+            {
+                passages.insert(equipement_id, passage);
+            }
+        }
+    }
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let status: String = row.get("status");
+            let passage = passages.get(&row.get::<String, _>("equipement_id"));
+            PlanningSheetRow {
+                action_id: row.get("id"),
+                team_name: row
+                    .get::<Option<String>, _>("team_name")
+                    .unwrap_or_default(),
+                action_type: utils::action_label(
+                    &row.get::<Option<String>, _>("type").unwrap_or_default(),
+                )
+                .to_string(),
+                type_name: row
+                    .get::<Option<String>, _>("type_name")
+                    .unwrap_or_default(),
+                description: row
+                    .get::<Option<String>, _>("description")
+                    .unwrap_or_default(),
+                scheduled_time: row.get("scheduled_time"),
+                status: ActionStatus::parse(&status)
+                    .map(|s| s.label().to_string())
+                    .unwrap_or(status),
+                first_passage: passage.map(|p| p.first_passage.clone()),
+                last_passage: passage.map(|p| p.last_passage.clone()),
+            }
+        })
+        .collect())
+}
+
+async fn load_current_actions(
+    pool: &SqlitePool,
+    event_id: &str,
+) -> Result<HashMap<String, CurrentAction>, String> {
+    let rows = sqlx::query(
+        r#"
+        SELECT a.id, a.type, a.team_id, a.scheduled_time, tm.name as team_name,
+               t.name as type_name, e.description
+        FROM action a
+        JOIN equipement e ON a.equipement_id = e.id
+        LEFT JOIN team tm ON a.team_id = tm.id
+        LEFT JOIN type t ON e.type_id = t.id
+        WHERE e.event_id = ?
+    "#,
+    )
+    .bind(event_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let action_type: Option<String> = row.get("type");
+            let type_name: Option<String> = row.get("type_name");
+            let description: Option<String> = row.get("description");
+            let mut label = format!(
+                "{} {}",
+                action_type.unwrap_or_default(),
+                type_name.unwrap_or_else(|| "Inconnu".to_string())
+            );
+            if let Some(description) = description.filter(|d| !d.is_empty()) {
+                label.push_str(&format!(" - {}", description));
+            }
+
+            let action = CurrentAction {
+                action_id: row.get("id"),
+                label: label.trim().to_string(),
+                team_id: row.get("team_id"),
+                team_name: row.get("team_name"),
+                scheduled_time: row.get("scheduled_time"),
+            };
+            (action.action_id.clone(), action)
+        })
+        .collect())
+}
+
+async fn load_event_teams(
+    pool: &SqlitePool,
+    event_id: &str,
+) -> Result<Vec<(String, Option<String>)>, String> {
+    sqlx::query_as("SELECT id, name FROM team WHERE event_id = ?")
+        .bind(event_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// ==================== Commandes Tauri ====================
+
+/// Exporte le planning (une ligne par action) dans un classeur réimportable
+#[tauri::command]
+pub async fn export_planning_excel(app: AppHandle, event_id: Option<String>) -> Result<(), String> {
+    let pool = db::get_db_pool(&app).await?;
+    let rows = load_planning_sheet_rows(&pool, event_id.as_deref()).await?;
+    println!("📊 Export Excel planning : {} action(s)", rows.len());
+
+    let mut workbook = build_planning_workbook(&rows)?;
+
+    let (dir_path, file_name) = utils::create_file_name("planning".to_string(), "xlsx".to_string());
+
+    if let Some(file_path) = utils::show_save_dialog(&file_name, &dir_path, "xlsx".to_string()) {
+        println!("💾 Saving workbook... {}", file_path.display());
+        workbook.save(file_path).map_err(|e| e.to_string())?;
+        println!("✅ Excel saved successfully!");
+    } else {
+        println!("Save cancelled by user");
+    }
+
+    Ok(())
+}
+
+/// Relit un classeur de planning modifié et liste les changements, sans rien écrire
+#[tauri::command]
+pub async fn preview_planning_import(
+    app: AppHandle,
+    event_id: String,
+    file_path: String,
+) -> Result<PlanningImportPreview, String> {
+    let pool = db::get_db_pool(&app).await?;
+
+    let bytes = std::fs::read(&file_path).map_err(|e| e.to_string())?;
+    let rows = sheet_to_imported_rows(&read_xlsx_rows(&bytes)?)?;
+    let current = load_current_actions(&pool, &event_id).await?;
+    let teams = load_event_teams(&pool, &event_id).await?;
+
+    let preview = diff_planning_rows(&event_id, &rows, &current, &teams);
+
+    println!(
+        "[DB] 📥 Import planning {} : {} modification(s), {} inchangée(s), {} erreur(s)",
+        file_path,
+        preview.changes.len(),
+        preview.unchanged,
+        preview.errors.len()
+    );
+    Ok(preview)
+}
+
+/// Applique les modifications validées dans l'aperçu, en une seule transaction
+#[tauri::command]
+pub async fn apply_planning_import(
+    app: AppHandle,
+    event_id: String,
+    changes: Vec<PlanningImportChange>,
+) -> Result<usize, String> {
+    let pool = db::get_db_pool(&app).await?;
+
+    let current = load_current_actions(&pool, &event_id).await?;
+    let team_ids: HashSet<String> = load_event_teams(&pool, &event_id)
+        .await?
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+
+    // Validation complète avant toute écriture
+    for change in &changes {
+        if !current.contains_key(&change.action_id) {
+            return Err(format!(
+                "Ligne {} : l'action {} n'appartient pas à l'événement",
+                change.row, change.action_id
+            ));
+        }
+        if !team_ids.contains(&change.team_id) {
+            return Err(format!(
+                "Ligne {} : l'équipe {} n'appartient pas à l'événement",
+                change.row, change.team_id
+            ));
+        }
+        if let Some(time) = &change.scheduled_time {
+            if utils::parse_datetime(time).is_none() {
+                return Err(format!("Ligne {} : horaire invalide {}", change.row, time));
+            }
+        }
+    }
+
+    let mut tx: Transaction<Sqlite> = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    // L'aperçu a pu vieillir : on refuse les actions modifiées depuis (mobile, autre poste)
+    let mut conflicts = Vec::new();
+    for change in &changes {
+        let row = sqlx::query("SELECT team_id, scheduled_time FROM action WHERE id = ?")
+            .bind(&change.action_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        let stale = match &row {
+            None => true,
+            Some(row) => {
+                let team_id: Option<String> = row.get("team_id");
+                let scheduled_time: Option<String> = row.get("scheduled_time");
+                import_change_is_stale(change, team_id.as_deref(), scheduled_time.as_deref())
+            }
+        };
+        if stale {
+            conflicts.push(format!("ligne {} ({})", change.row, change.label));
+        }
+    }
+    if !conflicts.is_empty() {
+        println!(
+            "[DB] ⚠️ Import planning refusé : {} action(s) modifiée(s) depuis l'aperçu",
+            conflicts.len()
+        );
+        return Err(format!(
+            "Actions modifiées depuis l'aperçu, relancez l'import : {}",
+            conflicts.join(", ")
+        ));
+    }
+
+    for change in &changes {
+        sqlx::query("UPDATE action SET team_id = ?, scheduled_time = ? WHERE id = ?")
+            .bind(&change.team_id)
+            .bind(&change.scheduled_time)
+            .bind(&change.action_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    println!(
+        "[DB] ✅ Import planning appliqué : {} action(s) pour l'événement {}",
+        changes.len(),
+        event_id
+    );
+    emit_event_progress(&app, &pool, &event_id).await;
+
+    Ok(changes.len())
+}
@@ -0,0 +1,268 @@
+// Avancement d'un événement pour la supervision (écran du PC course)
+// Compte les actions prévues, faites et en retard par équipe et par type, et les mètres
+// de barrières posés / déposés. Recalculé et diffusé à chaque changement de statut.
+
+use crate::db;
+use crate::types::ActionStatus;
+use crate::utils;
+use chrono::{Local, NaiveDateTime};
+use serde::Serialize;
+use sqlx::{Row, SqlitePool};
+use tauri::{AppHandle, Emitter};
+
+/// Événement Tauri émis quand l'avancement change
+pub const PROGRESS_EVENT: &str = "progress-updated";
+
+/// Action prise en compte dans l'avancement
+#[derive(Debug, Clone)]
+pub struct ProgressInput {
+    pub action_id: String,
+    pub team_id: String,
+    pub team_name: Option<String>,
+    pub action_type: String,
+    pub status: ActionStatus,
+    pub scheduled_time: Option<NaiveDateTime>,
+    /// quantity * length_per_unit de l'équipement, en mètres
+    pub length_m: f64,
+}
+
+/// Compteurs pour un type d'action (pose ou dépose)
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct ActionTypeProgress {
+    pub planned: usize,
+    pub done: usize,
+    pub late: usize,
+    pub planned_length_m: f64,
+    pub done_length_m: f64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TeamProgress {
+    pub team_id: String,
+    pub team_name: Option<String>,
+    pub pose: ActionTypeProgress,
+    pub depose: ActionTypeProgress,
+}
+
+/// Action dont l'heure prévue est dépassée sans qu'elle ait commencé
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct OverdueAction {
+    pub action_id: String,
+    pub team_id: String,
+    pub team_name: Option<String>,
+    pub action_type: String,
+    pub status: ActionStatus,
+    pub scheduled_time: String,
+    pub late_minutes: i64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct EventProgress {
+    pub event_id: String,
+    pub computed_at: String,
+    pub pose: ActionTypeProgress,
+    pub depose: ActionTypeProgress,
+    pub teams: Vec<TeamProgress>,
+    /// Du plus en retard au moins en retard
+    pub overdue: Vec<OverdueAction>,
+}
+
+// ==================== Fonctions helper publiques et testables ====================
+
+/// Vrai si l'action aurait dû commencer : heure prévue dépassée et action ni commencée,
+/// ni terminée, ni annulée
+pub fn is_overdue(
+    status: ActionStatus,
+    scheduled_time: Option<NaiveDateTime>,
+    now: NaiveDateTime,
+) -> bool {
+    let waiting = matches!(
+        status,
+        ActionStatus::Planned | ActionStatus::EnRoute | ActionStatus::Blocked
+    );
+    waiting && scheduled_time.is_some_and(|t| t < now)
+}
+
+fn add_to(progress: &mut ActionTypeProgress, input: &ProgressInput, late: bool) {
+    progress.planned += 1;
+    progress.planned_length_m += input.length_m;
+    if input.status == ActionStatus::Done {
+        progress.done += 1;
+        progress.done_length_m += input.length_m;
+    }
+    if late {
+        progress.late += 1;
+    }
+}
+
+/// Calcule l'avancement d'un événement. Les actions annulées ne sont pas comptées.
+pub fn compute_progress(
+    event_id: &str,
+    inputs: &[ProgressInput],
+    now: NaiveDateTime,
+) -> EventProgress {
+    let mut progress = EventProgress {
+        event_id: event_id.to_string(),
+        computed_at: now.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        pose: ActionTypeProgress::default(),
+        depose: ActionTypeProgress::default(),
+        teams: vec![],
+        overdue: vec![],
+    };
+
+    for input in inputs
+        .iter()
+        .filter(|i| i.status != ActionStatus::Cancelled)
+    {
+        let late = is_overdue(input.status, input.scheduled_time, now);
+        let is_pose = input.action_type == "pose";
+
+        let team_index = match progress
+            .teams
+            .iter()
+            .position(|t| t.team_id == input.team_id)
+        {
+            Some(index) => index,
+            None => {
+                progress.teams.push(TeamProgress {
+                    team_id: input.team_id.clone(),
+                    team_name: input.team_name.clone(),
+                    pose: ActionTypeProgress::default(),
+                    depose: ActionTypeProgress::default(),
+                });
+                progress.teams.len() - 1
+            }
+        };
+        let team = &mut progress.teams[team_index];
+
+        if is_pose {
+            add_to(&mut progress.pose, input, late);
+            add_to(&mut team.pose, input, late);
+        } else {
+            add_to(&mut progress.depose, input, late);
+            add_to(&mut team.depose, input, late);
+        }
+
+        if let (true, Some(scheduled)) = (late, input.scheduled_time) {
+            progress.overdue.push(OverdueAction {
+                action_id: input.action_id.clone(),
+                team_id: input.team_id.clone(),
+                team_name: input.team_name.clone(),
+                action_type: input.action_type.clone(),
+                status: input.status,
+                scheduled_time: utils::format_datetime(&scheduled),
+                late_minutes: (now - scheduled).num_minutes(),
+            });
+        }
+    }
+
+    progress.teams.sort_by(|a, b| a.team_name.cmp(&b.team_name));
+    progress
+        .overdue
+        .sort_by_key(|a| std::cmp::Reverse(a.late_minutes));
+    progress
+}
+
+// ==================== Chargement des données ====================
+
+async fn load_progress_inputs(
+    pool: &SqlitePool,
+    event_id: &str,
+) -> Result<Vec<ProgressInput>, String> {
+    let rows = sqlx::query(
+        r#"
+        SELECT
+            a.id, a.team_id, a.type, a.scheduled_time,
+            COALESCE(a.status, CASE WHEN a.is_done THEN 'done' ELSE 'planned' END) as status,
+            tm.name as team_name,
+            COALESCE(e.quantity, 0) * COALESCE(e.length_per_unit, 0) as length_m
+        FROM action a
+        JOIN equipement e ON a.equipement_id = e.id
+        LEFT JOIN team tm ON a.team_id = tm.id
+        WHERE e.event_id = ?
+    "#,
+    )
+    .bind(event_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let scheduled_time: Option<String> = row.get("scheduled_time");
+            let status: String = row.get("status");
+            ProgressInput {
+                action_id: row.get("id"),
+                team_id: row.get("team_id"),
+                team_name: row.get("team_name"),
+                action_type: row.get::<Option<String>, _>("type").unwrap_or_default(),
+                status: ActionStatus::parse(&status).unwrap_or(ActionStatus::Planned),
+                scheduled_time: scheduled_time.as_deref().and_then(utils::parse_datetime),
+                length_m: row.get::<i64, _>("length_m") as f64,
+            }
+        })
+        .collect())
+}
+
+pub async fn load_event_progress(
+    pool: &SqlitePool,
+    event_id: &str,
+) -> Result<EventProgress, String> {
+    let inputs = load_progress_inputs(pool, event_id).await?;
+    Ok(compute_progress(
+        event_id,
+        &inputs,
+        Local::now().naive_local(),
+    ))
+}
+
+/// Recalcule l'avancement de l'événement et le diffuse au frontend (écran de supervision)
+pub async fn emit_event_progress(app: &AppHandle, pool: &SqlitePool, event_id: &str) {
+    match load_event_progress(pool, event_id).await {
+        Ok(progress) => {
+            if let Err(e) = app.emit(PROGRESS_EVENT, &progress) {
+                eprintln!("⚠️ Erreur émission événement {}: {}", PROGRESS_EVENT, e);
+            }
+        }
+        Err(e) => eprintln!("⚠️ Erreur calcul de l'avancement: {}", e),
+    }
+}
+
+/// Comme `emit_event_progress`, à partir d'une action de l'événement
+pub async fn emit_action_progress(app: &AppHandle, pool: &SqlitePool, action_id: &str) {
+    let event_id: Option<String> = sqlx::query_scalar(
+        "SELECT e.event_id FROM action a JOIN equipement e ON a.equipement_id = e.id WHERE a.id = ?",
+    )
+    .bind(action_id)
+    .fetch_optional(pool)
+    .await
+    .unwrap_or_default();
+
+    if let Some(event_id) = event_id {
+        emit_event_progress(app, pool, &event_id).await;
+    }
+}
+
+// ==================== Commandes Tauri ====================
+
+/// Avancement d'un événement : actions prévues / faites / en retard, mètres posés
+#[tauri::command]
+pub async fn fetch_event_progress(
+    app: AppHandle,
+    event_id: String,
+) -> Result<EventProgress, String> {
+    let pool = db::get_db_pool(&app).await?;
+    let progress = load_event_progress(&pool, &event_id).await?;
+
+    println!(
+        "[PROGRESS] 📊 Événement {} : pose {}/{}, dépose {}/{}, {} action(s) en retard",
+        event_id,
+        progress.pose.done,
+        progress.pose.planned,
+        progress.depose.done,
+        progress.depose.planned,
+        progress.overdue.len()
+    );
+    Ok(progress)
+}
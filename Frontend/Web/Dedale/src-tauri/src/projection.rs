@@ -0,0 +1,302 @@
+// Systèmes de coordonnées projetés (RGF93 / Lambert-93 et RGF93 / CC48)
+// Les données restent stockées en WGS84 (lon, lat) ; ce module convertit vers et depuis les
+// projections coniques conformes de Lambert utilisées par le SIG de Strasbourg et les plans
+// techniques. RGF93 et WGS84 coïncident à quelques centimètres près : seule la projection
+// est appliquée (ellipsoïde GRS 1980), sans changement de datum.
+
+use crate::map::{format_coordinates, is_valid_coordinate};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+/// Demi-grand axe de l'ellipsoïde GRS 1980 (m)
+const GRS80_A: f64 = 6_378_137.0;
+/// Aplatissement de l'ellipsoïde GRS 1980
+const GRS80_F: f64 = 1.0 / 298.257_222_101;
+
+/// Système de coordonnées d'échange
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Crs {
+    /// WGS 84, degrés (EPSG:4326) : système de stockage
+    Wgs84,
+    /// RGF93 / Lambert-93, mètres (EPSG:2154)
+    Lambert93,
+    /// RGF93 / CC48 (conique conforme zone 7), mètres (EPSG:3948)
+    Cc48,
+}
+
+/// Paramètres d'une projection conique conforme de Lambert à deux parallèles (degrés, mètres)
+struct LambertParams {
+    lat0: f64,
+    lat1: f64,
+    lat2: f64,
+    lon0: f64,
+    x0: f64,
+    y0: f64,
+}
+
+/// Constantes dérivées de la projection
+struct LambertConstants {
+    e: f64,
+    n: f64,
+    af: f64,
+    rho0: f64,
+    lon0: f64,
+    x0: f64,
+    y0: f64,
+}
+
+/// Coordonnées à afficher : DMS et, le cas échéant, mètres projetés
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CoordinateDisplay {
+    pub dms: String,
+    pub epsg: u32,
+    pub x: f64,
+    pub y: f64,
+    /// "X 1 050 512,34 m  Y 7 172 081,56 m" (absent en WGS84)
+    pub projected: Option<String>,
+}
+
+// ==================== Fonctions helper publiques et testables ====================
+
+impl Crs {
+    /// Tous les systèmes proposés dans l'interface
+    pub const ALL: [Crs; 3] = [Crs::Wgs84, Crs::Lambert93, Crs::Cc48];
+
+    /// Code EPSG
+    pub fn epsg(self) -> u32 {
+        match self {
+            Crs::Wgs84 => 4326,
+            Crs::Lambert93 => 2154,
+            Crs::Cc48 => 3948,
+        }
+    }
+
+    /// Nom usuel
+    pub fn name(self) -> &'static str {
+        match self {
+            Crs::Wgs84 => "WGS 84",
+            Crs::Lambert93 => "RGF93 / Lambert-93",
+            Crs::Cc48 => "RGF93 / CC48",
+        }
+    }
+
+    /// Identifiant URN (membre « crs » GeoJSON)
+    pub fn urn(self) -> String {
+        format!("urn:ogc:def:crs:EPSG::{}", self.epsg())
+    }
+
+    /// Vrai pour les systèmes en mètres
+    pub fn is_projected(self) -> bool {
+        self != Crs::Wgs84
+    }
+
+    /// Reconnaît "EPSG:2154", "2154", "urn:ogc:def:crs:EPSG::3948", "lambert93", "cc48",
+    /// "wgs84", "CRS84"...
+    pub fn from_code(code: &str) -> Option<Crs> {
+        let normalized = code.trim().to_lowercase().replace(['-', '_', ' '], "");
+        if normalized.ends_with("crs84") {
+            return Some(Crs::Wgs84);
+        }
+        let digits: String = normalized
+            .rsplit(':')
+            .next()
+            .unwrap_or("")
+            .chars()
+            .filter(char::is_ascii_digit)
+            .collect();
+
+        match (normalized.as_str(), digits.as_str()) {
+            (_, "4326") | ("wgs84", _) => Some(Crs::Wgs84),
+            (_, "2154") | ("lambert93", _) | ("l93", _) => Some(Crs::Lambert93),
+            (_, "3948") | ("cc48", _) => Some(Crs::Cc48),
+            _ => None,
+        }
+    }
+
+    /// Système choisi dans l'interface (WGS84 par défaut)
+    pub fn parse(code: Option<&str>) -> Result<Crs, String> {
+        match code.map(str::trim).filter(|c| !c.is_empty()) {
+            None => Ok(Crs::Wgs84),
+            Some(code) => Crs::from_code(code)
+                .ok_or_else(|| format!("Système de coordonnées inconnu : {}", code)),
+        }
+    }
+
+    /// Définition WKT (OGC 01-009), utilisée par le GeoPackage
+    pub fn wkt(self) -> &'static str {
+        match self {
+            Crs::Wgs84 => {
+                r#"GEOGCS["WGS 84",DATUM["WGS_1984",SPHEROID["WGS 84",6378137,298.257223563,AUTHORITY["EPSG","7030"]],AUTHORITY["EPSG","6326"]],PRIMEM["Greenwich",0,AUTHORITY["EPSG","8901"]],UNIT["degree",0.0174532925199433,AUTHORITY["EPSG","9122"]],AUTHORITY["EPSG","4326"]]"#
+            }
+            Crs::Lambert93 => {
+                r#"PROJCS["RGF93 / Lambert-93",GEOGCS["RGF93",DATUM["Reseau_Geodesique_Francais_1993",SPHEROID["GRS 1980",6378137,298.257222101,AUTHORITY["EPSG","7019"]],TOWGS84[0,0,0,0,0,0,0],AUTHORITY["EPSG","6171"]],PRIMEM["Greenwich",0,AUTHORITY["EPSG","8901"]],UNIT["degree",0.0174532925199433,AUTHORITY["EPSG","9122"]],AUTHORITY["EPSG","4171"]],PROJECTION["Lambert_Conformal_Conic_2SP"],PARAMETER["standard_parallel_1",49],PARAMETER["standard_parallel_2",44],PARAMETER["latitude_of_origin",46.5],PARAMETER["central_meridian",3],PARAMETER["false_easting",700000],PARAMETER["false_northing",6600000],UNIT["metre",1,AUTHORITY["EPSG","9001"]],AXIS["X",EAST],AXIS["Y",NORTH],AUTHORITY["EPSG","2154"]]"#
+            }
+            Crs::Cc48 => {
+                r#"PROJCS["RGF93 / CC48",GEOGCS["RGF93",DATUM["Reseau_Geodesique_Francais_1993",SPHEROID["GRS 1980",6378137,298.257222101,AUTHORITY["EPSG","7019"]],TOWGS84[0,0,0,0,0,0,0],AUTHORITY["EPSG","6171"]],PRIMEM["Greenwich",0,AUTHORITY["EPSG","8901"]],UNIT["degree",0.0174532925199433,AUTHORITY["EPSG","9122"]],AUTHORITY["EPSG","4171"]],PROJECTION["Lambert_Conformal_Conic_2SP"],PARAMETER["standard_parallel_1",47.25],PARAMETER["standard_parallel_2",48.75],PARAMETER["latitude_of_origin",48],PARAMETER["central_meridian",3],PARAMETER["false_easting",1700000],PARAMETER["false_northing",7200000],UNIT["metre",1,AUTHORITY["EPSG","9001"]],AXIS["X",EAST],AXIS["Y",NORTH],AUTHORITY["EPSG","3948"]]"#
+            }
+        }
+    }
+
+    fn lambert(self) -> Option<LambertParams> {
+        match self {
+            Crs::Wgs84 => None,
+            Crs::Lambert93 => Some(LambertParams {
+                lat0: 46.5,
+                lat1: 49.0,
+                lat2: 44.0,
+                lon0: 3.0,
+                x0: 700_000.0,
+                y0: 6_600_000.0,
+            }),
+            Crs::Cc48 => Some(LambertParams {
+                lat0: 48.0,
+                lat1: 47.25,
+                lat2: 48.75,
+                lon0: 3.0,
+                x0: 1_700_000.0,
+                y0: 7_200_000.0,
+            }),
+        }
+    }
+
+    /// Convertit une position WGS84 (lon, lat) dans ce système
+    pub fn project(self, point: (f64, f64)) -> (f64, f64) {
+        match self.lambert() {
+            None => point,
+            Some(params) => lambert_forward(&lambert_constants(&params), point),
+        }
+    }
+
+    /// Convertit une position de ce système en WGS84 (lon, lat)
+    pub fn unproject(self, point: (f64, f64)) -> (f64, f64) {
+        match self.lambert() {
+            None => point,
+            Some(params) => lambert_inverse(&lambert_constants(&params), point),
+        }
+    }
+}
+
+fn eccentricity() -> f64 {
+    (GRS80_F * (2.0 - GRS80_F)).sqrt()
+}
+
+fn snyder_m(phi: f64, e: f64) -> f64 {
+    phi.cos() / (1.0 - (e * phi.sin()).powi(2)).sqrt()
+}
+
+/// Latitude isométrique sous forme exponentielle (t de Snyder)
+fn snyder_t(phi: f64, e: f64) -> f64 {
+    let es = e * phi.sin();
+    (FRAC_PI_4 - phi / 2.0).tan() / ((1.0 - es) / (1.0 + es)).powf(e / 2.0)
+}
+
+fn lambert_constants(params: &LambertParams) -> LambertConstants {
+    let e = eccentricity();
+    let (phi0, phi1, phi2) = (
+        params.lat0.to_radians(),
+        params.lat1.to_radians(),
+        params.lat2.to_radians(),
+    );
+    let (m1, m2) = (snyder_m(phi1, e), snyder_m(phi2, e));
+    let (t0, t1, t2) = (snyder_t(phi0, e), snyder_t(phi1, e), snyder_t(phi2, e));
+
+    let n = (m1.ln() - m2.ln()) / (t1.ln() - t2.ln());
+    let af = GRS80_A * m1 / (n * t1.powf(n));
+
+    LambertConstants {
+        e,
+        n,
+        af,
+        rho0: af * t0.powf(n),
+        lon0: params.lon0.to_radians(),
+        x0: params.x0,
+        y0: params.y0,
+    }
+}
+
+fn lambert_forward(c: &LambertConstants, (lon, lat): (f64, f64)) -> (f64, f64) {
+    let rho = c.af * snyder_t(lat.to_radians(), c.e).powf(c.n);
+    let theta = c.n * (lon.to_radians() - c.lon0);
+    (c.x0 + rho * theta.sin(), c.y0 + c.rho0 - rho * theta.cos())
+}
+
+fn lambert_inverse(c: &LambertConstants, (x, y): (f64, f64)) -> (f64, f64) {
+    let dx = x - c.x0;
+    let dy = c.rho0 - (y - c.y0);
+    let rho = c.n.signum() * (dx * dx + dy * dy).sqrt();
+    let theta = (c.n.signum() * dx).atan2(c.n.signum() * dy);
+    let ts = (rho / c.af).powf(1.0 / c.n);
+
+    // Latitude par itération (converge en quelques tours)
+    let mut phi = FRAC_PI_2 - 2.0 * ts.atan();
+    for _ in 0..15 {
+        let es = c.e * phi.sin();
+        let next = FRAC_PI_2 - 2.0 * (ts * ((1.0 - es) / (1.0 + es)).powf(c.e / 2.0)).atan();
+        if (next - phi).abs() < 1e-12 {
+            phi = next;
+            break;
+        }
+        phi = next;
+    }
+
+    ((theta / c.n + c.lon0).to_degrees(), phi.to_degrees())
+}
+
+/// Mètres avec séparateur de milliers et virgule décimale ("1 050 512,34")
+pub fn format_meters(value: f64) -> String {
+    let formatted = format!("{:.2}", value.abs());
+    let (integer, decimals) = formatted.split_once('.').unwrap_or((&formatted, "00"));
+
+    let mut grouped = String::new();
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push(' ');
+        }
+        grouped.push(digit);
+    }
+
+    let sign = if value < 0.0 { "-" } else { "" };
+    format!("{}{},{}", sign, grouped, decimals)
+}
+
+/// Coordonnées projetées lisibles ("X 1 050 512,34 m  Y 7 172 081,56 m")
+pub fn format_projected(crs: Crs, lon: f64, lat: f64) -> String {
+    let (x, y) = crs.project((lon, lat));
+    format!("X {} m  Y {} m", format_meters(x), format_meters(y))
+}
+
+/// Affichage d'une position : DMS (comme `format_coordinates`) et mètres projetés
+pub fn coordinate_display(crs: Crs, lon: f64, lat: f64) -> CoordinateDisplay {
+    let (x, y) = crs.project((lon, lat));
+    CoordinateDisplay {
+        dms: format_coordinates(lon, lat),
+        epsg: crs.epsg(),
+        x,
+        y,
+        projected: crs.is_projected().then(|| format_projected(crs, lon, lat)),
+    }
+}
+
+// ==================== Commandes Tauri ====================
+
+/// Coordonnées d'une position pour l'affichage (DMS + mètres dans le système choisi)
+#[tauri::command]
+pub fn display_coordinates(
+    lon: f64,
+    lat: f64,
+    crs: Option<String>,
+) -> Result<CoordinateDisplay, String> {
+    let crs = Crs::parse(crs.as_deref())?;
+    Ok(coordinate_display(crs, lon, lat))
+}
+
+/// Convertit une position saisie dans un système projeté en WGS84 (lon, lat)
+#[tauri::command]
+pub fn convert_to_wgs84(x: f64, y: f64, crs: String) -> Result<(f64, f64), String> {
+    let crs = Crs::parse(Some(&crs))?;
+    let (lon, lat) = crs.unproject((x, y));
+    if !is_valid_coordinate(lat, lon) {
+        return Err(format!("Coordonnées hors limites ({}, {})", x, y));
+    }
+    Ok((lon, lat))
+}
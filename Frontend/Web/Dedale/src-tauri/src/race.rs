@@ -0,0 +1,672 @@
+// Passage de la course le long d'un parcours
+// Référencement linéaire (position d'un point le long du tracé) et fenêtres de passage
+// calculées à partir de l'heure de départ et des vitesses du parcours.
+
+use crate::db;
+use crate::map::{calculate_distance, parse_linestring_wkt};
+use crate::utils::{self, format_datetime};
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+/// Distance maximale (m) entre un équipement et le tracé pour le considérer sur le parcours
+pub const PARCOURS_TOLERANCE_METERS: f64 = 25.0;
+
+/// Rayon terrestre utilisé par map::calculate_distance
+const EARTH_RADIUS_METERS: f64 = 6_371_000.0;
+
+/// Position d'un point le long d'un parcours
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoutePosition {
+    /// Distance depuis le départ, mesurée le long du tracé (m)
+    pub chainage_m: f64,
+    /// Distance entre le point et le tracé (m)
+    pub offset_m: f64,
+}
+
+/// Fenêtre pendant laquelle la course occupe un endroit du parcours
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PassageWindow {
+    /// Arrivée du premier coureur (vitesse haute)
+    pub first_passage: NaiveDateTime,
+    /// Passage du dernier coureur (vitesse basse)
+    pub last_passage: NaiveDateTime,
+}
+
+impl PassageWindow {
+    /// Union de deux fenêtres (plusieurs points d'un même équipement)
+    pub fn union(&self, other: &PassageWindow) -> PassageWindow {
+        PassageWindow {
+            first_passage: self.first_passage.min(other.first_passage),
+            last_passage: self.last_passage.max(other.last_passage),
+        }
+    }
+}
+
+/// Action dont l'horaire tombe pendant l'occupation du parcours
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RaceViolation {
+    pub action_id: String,
+    pub equipement_id: String,
+    pub action_type: String,
+    pub scheduled_time: String,
+    pub parcours_id: String,
+    pub parcours_name: Option<String>,
+    pub chainage_m: f64,
+    pub first_passage: String,
+    pub last_passage: String,
+    pub message: String,
+}
+
+/// Violations regroupées par équipe
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamRaceViolations {
+    pub team_id: String,
+    pub team_name: Option<String>,
+    pub violations: Vec<RaceViolation>,
+}
+
+/// Vague de départ (élite, masse, relais...) avec son heure de départ et ses vitesses
+#[derive(Debug, Clone, PartialEq)]
+pub struct RaceWave {
+    pub name: Option<String>,
+    pub start: NaiveDateTime,
+    pub speed_low: Option<f64>,
+    pub speed_high: Option<f64>,
+}
+
+/// Parcours avec ses vagues de départ, prêt pour le calcul des passages
+#[derive(Debug, Clone)]
+pub struct RaceParcours {
+    pub id: String,
+    pub name: Option<String>,
+    pub waves: Vec<RaceWave>,
+    pub line: Vec<(f64, f64)>,
+}
+
+impl RaceParcours {
+    /// Fenêtre de passage à une distance du départ : union des fenêtres de toutes les vagues
+    pub fn passage_window(&self, chainage_m: f64) -> Option<PassageWindow> {
+        self.waves
+            .iter()
+            .filter_map(|w| passage_window(w.start, chainage_m, w.speed_low, w.speed_high))
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /// Fenêtre d'occupation (voir `occupancy_window`) : union sur toutes les vagues
+    pub fn occupancy_window(
+        &self,
+        points: &[(f64, f64)],
+        tolerance_m: f64,
+    ) -> Option<(f64, PassageWindow)> {
+        self.waves
+            .iter()
+            .filter_map(|w| {
+                occupancy_window(
+                    &self.line,
+                    w.start,
+                    w.speed_low,
+                    w.speed_high,
+                    points,
+                    tolerance_m,
+                )
+            })
+            .reduce(|(c1, w1), (c2, w2)| (c1.min(c2), w1.union(&w2)))
+    }
+}
+
+/// Lieu dont on veut connaître l'heure de passage de la course
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PassageTarget {
+    Coordinate { x: f64, y: f64 },
+    Point { id: String },
+    Equipement { id: String },
+    Interest { id: String },
+}
+
+/// Passage de la course au plus près d'un lieu
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PassageTime {
+    /// "coordinate", "point", "equipement" ou "interest"
+    pub target_kind: String,
+    pub target_id: Option<String>,
+    pub parcours_id: String,
+    pub parcours_name: Option<String>,
+    pub chainage_m: f64,
+    pub offset_m: f64,
+    /// Faux si le lieu est à plus de PARCOURS_TOLERANCE_METERS du tracé le plus proche
+    pub on_parcours: bool,
+    pub first_passage: String,
+    pub last_passage: String,
+}
+
+/// Résultat de `estimate_passage`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PassageEstimate {
+    /// Index du parcours le plus proche dans la liste fournie
+    pub parcours_index: usize,
+    pub position: RoutePosition,
+    pub window: PassageWindow,
+}
+
+// ==================== Fonctions helper publiques et testables ====================
+
+/// Longueurs cumulées (m) à chaque sommet du tracé
+pub fn cumulative_lengths(line: &[(f64, f64)]) -> Vec<f64> {
+    let mut lengths = Vec::with_capacity(line.len());
+    let mut total = 0.0;
+    for (i, point) in line.iter().enumerate() {
+        if i > 0 {
+            let prev = line[i - 1];
+            total += calculate_distance(prev.1, prev.0, point.1, point.0);
+        }
+        lengths.push(total);
+    }
+    lengths
+}
+
+/// Projette un point (lon, lat) sur le tracé et retourne sa position le long du parcours.
+/// La projection se fait dans un plan local (équirectangulaire) centré sur le point.
+pub fn locate_on_line(line: &[(f64, f64)], point: (f64, f64)) -> Option<RoutePosition> {
+    match line.len() {
+        0 => return None,
+        1 => {
+            return Some(RoutePosition {
+                chainage_m: 0.0,
+                offset_m: calculate_distance(point.1, point.0, line[0].1, line[0].0),
+            })
+        }
+        _ => {}
+    }
+
+    let lengths = cumulative_lengths(line);
+    let scale_y = EARTH_RADIUS_METERS * std::f64::consts::PI / 180.0;
+    let scale_x = scale_y * point.1.to_radians().cos();
+    let to_local = |p: (f64, f64)| ((p.0 - point.0) * scale_x, (p.1 - point.1) * scale_y);
+
+    let mut best: Option<RoutePosition> = None;
+
+    for i in 0..line.len() - 1 {
+        let (ax, ay) = to_local(line[i]);
+        let (bx, by) = to_local(line[i + 1]);
+        let (dx, dy) = (bx - ax, by - ay);
+        let length_sq = dx * dx + dy * dy;
+
+        // Le point est à l'origine du repère local
+        let t = if length_sq > 0.0 {
+            (-(ax * dx + ay * dy) / length_sq).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let (px, py) = (ax + t * dx, ay + t * dy);
+        let offset = (px * px + py * py).sqrt();
+
+        if best.is_none_or(|b| offset < b.offset_m) {
+            best = Some(RoutePosition {
+                chainage_m: lengths[i] + t * (lengths[i + 1] - lengths[i]),
+                offset_m: offset,
+            });
+        }
+    }
+
+    best
+}
+
+/// Position (lon, lat) sur le tracé à une distance donnée du départ (bornée au tracé)
+pub fn point_at_chainage(line: &[(f64, f64)], chainage_m: f64) -> Option<(f64, f64)> {
+    if line.len() < 2 {
+        return line.first().copied();
+    }
+
+    interpolate_at_chainage(line, &cumulative_lengths(line), chainage_m).map(|(_, point)| point)
+}
+
+/// Segment du tracé contenant une distance donnée et position interpolée (bornée au tracé).
+/// `lengths` est le résultat de `cumulative_lengths(line)`, calculé une fois par l'appelant
+/// lorsqu'il interpole plusieurs distances sur le même tracé.
+pub fn interpolate_at_chainage(
+    line: &[(f64, f64)],
+    lengths: &[f64],
+    chainage_m: f64,
+) -> Option<(usize, (f64, f64))> {
+    if line.len() < 2 || lengths.len() != line.len() {
+        return None;
+    }
+
+    let segment = lengths[1..]
+        .partition_point(|&l| l < chainage_m)
+        .min(line.len() - 2);
+    let (a, b) = (line[segment], line[segment + 1]);
+    let length = lengths[segment + 1] - lengths[segment];
+    let t = if length > 0.0 {
+        ((chainage_m - lengths[segment]) / length).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    Some((segment, (a.0 + t * (b.0 - a.0), a.1 + t * (b.1 - a.1))))
+}
+
+/// Fenêtre de passage à une distance donnée du départ.
+/// Si une seule vitesse est renseignée, elle sert pour le premier et le dernier coureur.
+pub fn passage_window(
+    start: NaiveDateTime,
+    chainage_m: f64,
+    speed_low_kmh: Option<f64>,
+    speed_high_kmh: Option<f64>,
+) -> Option<PassageWindow> {
+    let valid = |s: Option<f64>| s.filter(|v| *v > 0.0);
+    let (low, high) = match (valid(speed_low_kmh), valid(speed_high_kmh)) {
+        (Some(low), Some(high)) => (low.min(high), low.max(high)),
+        (Some(speed), None) | (None, Some(speed)) => (speed, speed),
+        (None, None) => return None,
+    };
+
+    let at_speed = |speed_kmh: f64| {
+        let seconds = chainage_m / (speed_kmh * 1000.0 / 3600.0);
+        start + Duration::seconds(seconds.round() as i64)
+    };
+
+    Some(PassageWindow {
+        first_passage: at_speed(high),
+        last_passage: at_speed(low),
+    })
+}
+
+/// Vérifie une action par rapport à la fenêtre de passage.
+/// La pose doit être terminée avant le premier coureur, la dépose commencer après le dernier.
+pub fn check_race_window(
+    action_type: &str,
+    scheduled: NaiveDateTime,
+    duration_minutes: i64,
+    window: &PassageWindow,
+) -> Option<String> {
+    if action_type == "pose" {
+        let end = scheduled + Duration::minutes(duration_minutes);
+        if end > window.first_passage {
+            return Some(format!(
+                "Pose terminée à {} après l'arrivée du premier coureur ({})",
+                format_datetime(&end),
+                format_datetime(&window.first_passage)
+            ));
+        }
+    } else if scheduled < window.last_passage {
+        return Some(format!(
+            "Dépose à {} avant le passage du dernier coureur ({})",
+            format_datetime(&scheduled),
+            format_datetime(&window.last_passage)
+        ));
+    }
+    None
+}
+
+/// Fenêtre d'occupation d'un équipement (tous ses points à moins de `tolerance_m` du tracé).
+/// Retourne la position la plus proche du départ et l'union des fenêtres de passage.
+pub fn occupancy_window(
+    line: &[(f64, f64)],
+    start: NaiveDateTime,
+    speed_low_kmh: Option<f64>,
+    speed_high_kmh: Option<f64>,
+    points: &[(f64, f64)],
+    tolerance_m: f64,
+) -> Option<(f64, PassageWindow)> {
+    let mut result: Option<(f64, PassageWindow)> = None;
+
+    for point in points {
+        let Some(position) = locate_on_line(line, *point) else {
+            continue;
+        };
+        if position.offset_m > tolerance_m {
+            continue;
+        }
+        let Some(window) =
+            passage_window(start, position.chainage_m, speed_low_kmh, speed_high_kmh)
+        else {
+            continue;
+        };
+
+        result = Some(match result {
+            Some((chainage, current)) => {
+                (chainage.min(position.chainage_m), current.union(&window))
+            }
+            None => (position.chainage_m, window),
+        });
+    }
+
+    result
+}
+
+/// Passage de la course au plus près d'un lieu (un ou plusieurs points).
+/// Le parcours retenu est le plus proche ; si plusieurs points sont sur le tracé, la fenêtre
+/// couvre l'ensemble (voir `occupancy_window`). Les parcours sans vitesse sont ignorés.
+pub fn estimate_passage(
+    parcours: &[RaceParcours],
+    points: &[(f64, f64)],
+) -> Option<PassageEstimate> {
+    let mut nearest: Option<(usize, RoutePosition)> = None;
+
+    for (index, p) in parcours.iter().enumerate() {
+        if p.passage_window(0.0).is_none() {
+            continue;
+        }
+        for point in points {
+            let Some(position) = locate_on_line(&p.line, *point) else {
+                continue;
+            };
+            if nearest.is_none_or(|(_, best)| position.offset_m < best.offset_m) {
+                nearest = Some((index, position));
+            }
+        }
+    }
+
+    let (index, position) = nearest?;
+    let p = &parcours[index];
+    let (chainage_m, window) = match p.occupancy_window(points, PARCOURS_TOLERANCE_METERS) {
+        Some(occupancy) => occupancy,
+        None => (position.chainage_m, p.passage_window(position.chainage_m)?),
+    };
+
+    Some(PassageEstimate {
+        parcours_index: index,
+        position: RoutePosition {
+            chainage_m,
+            offset_m: position.offset_m,
+        },
+        window,
+    })
+}
+
+/// Heure de passage d'un lieu, prête à être envoyée au frontend ou exportée
+pub fn passage_time(
+    parcours: &[RaceParcours],
+    target_kind: &str,
+    target_id: Option<String>,
+    points: &[(f64, f64)],
+) -> Option<PassageTime> {
+    let estimate = estimate_passage(parcours, points)?;
+    let p = &parcours[estimate.parcours_index];
+
+    Some(PassageTime {
+        target_kind: target_kind.to_string(),
+        target_id,
+        parcours_id: p.id.clone(),
+        parcours_name: p.name.clone(),
+        chainage_m: estimate.position.chainage_m,
+        offset_m: estimate.position.offset_m,
+        on_parcours: estimate.position.offset_m <= PARCOURS_TOLERANCE_METERS,
+        first_passage: format_datetime(&estimate.window.first_passage),
+        last_passage: format_datetime(&estimate.window.last_passage),
+    })
+}
+
+/// Heure de passage d'un lieu situé sur un parcours (à moins de PARCOURS_TOLERANCE_METERS).
+/// Un lieu éloigné de tout tracé n'a pas de passage : pas d'heure à annoncer aux équipes.
+pub fn passage_time_on_parcours(
+    parcours: &[RaceParcours],
+    target_kind: &str,
+    target_id: Option<String>,
+    points: &[(f64, f64)],
+) -> Option<PassageTime> {
+    passage_time(parcours, target_kind, target_id, points).filter(|p| p.on_parcours)
+}
+
+/// Vagues d'un parcours : celles définies explicitement, sinon une vague unique reprenant
+/// l'heure de départ et les vitesses du parcours
+pub fn race_waves(
+    waves: Vec<RaceWave>,
+    start: Option<NaiveDateTime>,
+    speed_low: Option<f64>,
+    speed_high: Option<f64>,
+) -> Vec<RaceWave> {
+    if !waves.is_empty() {
+        return waves;
+    }
+    start
+        .map(|start| RaceWave {
+            name: None,
+            start,
+            speed_low,
+            speed_high,
+        })
+        .into_iter()
+        .collect()
+}
+
+/// Heures de passage indexées par (type de lieu, id), pour les exports.
+/// Les lieux hors parcours sont écartés.
+pub fn passages_by_target(passages: Vec<PassageTime>) -> HashMap<(String, String), PassageTime> {
+    passages
+        .into_iter()
+        .filter(|p| p.on_parcours)
+        .filter_map(|p| Some(((p.target_kind.clone(), p.target_id.clone()?), p)))
+        .collect()
+}
+
+// ==================== Chargement des données ====================
+
+/// Parcours d'un événement ayant un tracé et au moins une vague (ou une heure de départ)
+pub async fn load_race_parcours(
+    pool: &SqlitePool,
+    event_id: &str,
+) -> Result<Vec<RaceParcours>, String> {
+    let rows = sqlx::query(
+        "SELECT id, name, start_time, speed_low, speed_high, geometry_json FROM parcours WHERE event_id = ?",
+    )
+    .bind(event_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let wave_rows = sqlx::query(
+        r#"
+        SELECT w.parcours_id, w.name, w.start_time, w.speed_low, w.speed_high
+        FROM wave w
+        JOIN parcours p ON w.parcours_id = p.id
+        WHERE p.event_id = ?
+        ORDER BY w.start_time
+        "#,
+    )
+    .bind(event_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut waves: HashMap<String, Vec<RaceWave>> = HashMap::new();
+    for row in wave_rows {
+        let start_time: Option<i64> = row.get("start_time");
+        let Some(start) = start_time.and_then(utils::timestamp_ms_to_local) else {
+            continue;
+        };
+        waves
+            .entry(row.get("parcours_id"))
+            .or_default()
+            .push(RaceWave {
+                name: row.get("name"),
+                start,
+                speed_low: row.get("speed_low"),
+                speed_high: row.get("speed_high"),
+            });
+    }
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let id: String = row.get("id");
+            let start_time: Option<i64> = row.get("start_time");
+            let geometry: Option<String> = row.get("geometry_json");
+            let line = parse_linestring_wkt(geometry.as_deref().unwrap_or(""));
+            let waves = race_waves(
+                waves.remove(&id).unwrap_or_default(),
+                start_time.and_then(utils::timestamp_ms_to_local),
+                row.get("speed_low"),
+                row.get("speed_high"),
+            );
+            if line.len() < 2 || waves.is_empty() {
+                return None;
+            }
+            Some(RaceParcours {
+                id,
+                name: row.get("name"),
+                waves,
+                line,
+            })
+        })
+        .collect())
+}
+
+/// Coordonnées (dans l'ordre du tracé) des équipements d'un événement
+pub async fn load_equipement_points(
+    pool: &SqlitePool,
+    event_id: &str,
+) -> Result<HashMap<String, Vec<(f64, f64)>>, String> {
+    let rows = sqlx::query(
+        r#"
+        SELECT c.equipement_id, c.x, c.y
+        FROM equipement_coordinate c
+        JOIN equipement e ON c.equipement_id = e.id
+        WHERE e.event_id = ?
+        ORDER BY c.order_index ASC
+    "#,
+    )
+    .bind(event_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut coordinates: HashMap<String, Vec<(f64, f64)>> = HashMap::new();
+    for row in rows {
+        coordinates
+            .entry(row.get("equipement_id"))
+            .or_default()
+            .push((row.get("x"), row.get("y")));
+    }
+    Ok(coordinates)
+}
+
+/// Heures de passage de tous les points, équipements et points d'intérêt d'un événement
+pub async fn load_event_passage_times(
+    pool: &SqlitePool,
+    event_id: &str,
+) -> Result<Vec<PassageTime>, String> {
+    let parcours = load_race_parcours(pool, event_id).await?;
+    if parcours.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut passages = Vec::new();
+
+    let point_rows = sqlx::query("SELECT id, x, y FROM point WHERE event_id = ?")
+        .bind(event_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    for row in point_rows {
+        let point = (row.get("x"), row.get("y"));
+        passages.extend(passage_time(
+            &parcours,
+            "point",
+            Some(row.get("id")),
+            &[point],
+        ));
+    }
+
+    for (equipement_id, points) in load_equipement_points(pool, event_id).await? {
+        passages.extend(passage_time(
+            &parcours,
+            "equipement",
+            Some(equipement_id),
+            &points,
+        ));
+    }
+
+    let interest_rows = sqlx::query("SELECT id, x, y FROM interest WHERE event_id = ?")
+        .bind(event_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    for row in interest_rows {
+        let point = (row.get("x"), row.get("y"));
+        passages.extend(passage_time(
+            &parcours,
+            "interest",
+            Some(row.get("id")),
+            &[point],
+        ));
+    }
+
+    Ok(passages)
+}
+
+// ==================== Commandes Tauri ====================
+
+/// Heure de passage de la course en un lieu : coordonnée libre, point, équipement ou
+/// point d'intérêt. Retourne None si aucun parcours de l'événement n'a de vitesse renseignée.
+#[tauri::command]
+pub async fn compute_passage_time(
+    app: AppHandle,
+    event_id: String,
+    target: PassageTarget,
+) -> Result<Option<PassageTime>, String> {
+    let pool = db::get_db_pool(&app).await?;
+    let parcours = load_race_parcours(&pool, &event_id).await?;
+
+    let (kind, target_id, points): (&str, Option<String>, Vec<(f64, f64)>) = match target {
+        PassageTarget::Coordinate { x, y } => ("coordinate", None, vec![(x, y)]),
+        PassageTarget::Point { id } => {
+            let point: Option<(f64, f64)> = sqlx::query_as("SELECT x, y FROM point WHERE id = ?")
+                .bind(&id)
+                .fetch_optional(&pool)
+                .await
+                .map_err(|e| e.to_string())?;
+            let point = point.ok_or_else(|| format!("Point {} introuvable", id))?;
+            ("point", Some(id), vec![point])
+        }
+        PassageTarget::Equipement { id } => {
+            let points: Vec<(f64, f64)> = sqlx::query_as(
+                "SELECT x, y FROM equipement_coordinate WHERE equipement_id = ? ORDER BY order_index ASC",
+            )
+            .bind(&id)
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| e.to_string())?;
+            ("equipement", Some(id), points)
+        }
+        PassageTarget::Interest { id } => {
+            let point: Option<(f64, f64)> =
+                sqlx::query_as("SELECT x, y FROM interest WHERE id = ?")
+                    .bind(&id)
+                    .fetch_optional(&pool)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            let point = point.ok_or_else(|| format!("Point d'intérêt {} introuvable", id))?;
+            ("interest", Some(id), vec![point])
+        }
+    };
+
+    let passage = passage_time(&parcours, kind, target_id, &points);
+    if let Some(p) = &passage {
+        println!(
+            "[RACE] 🏃 Passage {} à {:.0} m ({}) : {} -> {}",
+            kind, p.chainage_m, p.parcours_id, p.first_passage, p.last_passage
+        );
+    }
+    Ok(passage)
+}
+
+/// Heures de passage de tous les lieux d'un événement
+#[tauri::command]
+pub async fn fetch_event_passage_times(
+    app: AppHandle,
+    event_id: String,
+) -> Result<Vec<PassageTime>, String> {
+    let pool = db::get_db_pool(&app).await?;
+    let passages = load_event_passage_times(&pool, &event_id).await?;
+    println!(
+        "[RACE] 🏃 {} heure(s) de passage pour l'événement {}",
+        passages.len(),
+        event_id
+    );
+    Ok(passages)
+}
@@ -0,0 +1,254 @@
+// Génération automatique du planning de pose / dépose
+// Heuristique gloutonne : les tâches sont traitées par ordre de fenêtre et affectées
+// à l'équipe qui peut les réaliser au plus tôt, en pénalisant les trajets et la charge.
+
+use crate::durations::team_size_factor;
+use crate::map::calculate_distance;
+use crate::utils::format_datetime;
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+/// Durée estimée d'une action quand aucune estimation n'est disponible
+pub const DEFAULT_ACTION_DURATION_MINUTES: i64 = 15;
+
+/// Vitesse moyenne de déplacement d'une équipe en ville (camion)
+pub const DEFAULT_TRAVEL_SPEED_KMH: f64 = 20.0;
+
+/// Largeur de la fenêtre de pose (avant date_pose) et de dépose (après date_depose)
+pub const DEFAULT_WINDOW_MINUTES: i64 = 240;
+
+// ==================== Fonctions helper publiques et testables ====================
+
+/// Temps de trajet en minutes (arrondi au supérieur) pour une distance en mètres
+pub fn travel_minutes(distance_m: f64, speed_kmh: f64) -> i64 {
+    if speed_kmh <= 0.0 || distance_m <= 0.0 {
+        return 0;
+    }
+    (distance_m / 1000.0 / speed_kmh * 60.0).ceil() as i64
+}
+
+/// Distance en mètres entre deux positions (lon, lat)
+pub fn distance_between(from: (f64, f64), to: (f64, f64)) -> f64 {
+    calculate_distance(from.1, from.0, to.1, to.0)
+}
+
+/// Paramètres du générateur de planning
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SolverOptions {
+    /// Durée des actions dont le type d'équipement n'a pas de cadence
+    pub action_duration_minutes: i64,
+    pub travel_speed_kmh: f64,
+    pub window_minutes: i64,
+    /// Poids de l'équilibrage : minutes de coût ajoutées par minute de charge déjà affectée
+    pub balance_weight: f64,
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        Self {
+            action_duration_minutes: DEFAULT_ACTION_DURATION_MINUTES,
+            travel_speed_kmh: DEFAULT_TRAVEL_SPEED_KMH,
+            window_minutes: DEFAULT_WINDOW_MINUTES,
+            balance_weight: 0.5,
+        }
+    }
+}
+
+/// Action à planifier (une pose ou une dépose d'équipement)
+#[derive(Debug, Clone)]
+pub struct SolverTask {
+    pub action_id: Option<String>,
+    pub equipement_id: String,
+    pub action_type: String,
+    /// date_pose pour une pose, date_depose pour une dépose
+    pub reference_time: NaiveDateTime,
+    pub position: Option<(f64, f64)>,
+    /// Dépôt d'origine : point de départ d'une équipe qui n'a encore rien fait
+    pub origin: Option<(f64, f64)>,
+    /// Durée estimée pour une équipe de `reference_team_size` personnes
+    pub duration_minutes: i64,
+    /// 0 : durée indépendante de la taille de l'équipe
+    pub reference_team_size: i64,
+}
+
+impl SolverTask {
+    /// Fenêtre de début autorisée : la pose doit être finie à date_pose,
+    /// la dépose ne peut commencer qu'à date_depose
+    pub fn start_window(&self, window_minutes: i64) -> (NaiveDateTime, NaiveDateTime) {
+        self.start_window_for(window_minutes, self.duration_minutes)
+    }
+
+    /// Comme `start_window`, pour une durée donnée (dépend de l'équipe affectée)
+    pub fn start_window_for(
+        &self,
+        window_minutes: i64,
+        duration_minutes: i64,
+    ) -> (NaiveDateTime, NaiveDateTime) {
+        if self.action_type == "pose" {
+            let latest = self.reference_time - Duration::minutes(duration_minutes);
+            (latest - Duration::minutes(window_minutes), latest)
+        } else {
+            (
+                self.reference_time,
+                self.reference_time + Duration::minutes(window_minutes),
+            )
+        }
+    }
+
+    /// Durée pour une équipe de `team_size` personnes (0 : taille inconnue)
+    pub fn duration_for(&self, team_size: i64) -> i64 {
+        if self.reference_team_size <= 0 || team_size <= 0 {
+            return self.duration_minutes;
+        }
+        let factor = team_size_factor(self.reference_team_size, team_size);
+        ((self.duration_minutes as f64 * factor).ceil() as i64).max(1)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SolverTeam {
+    pub id: String,
+    pub name: Option<String>,
+    /// Nombre de membres (0 : inconnu)
+    pub size: i64,
+}
+
+/// Action proposée par le générateur, à valider avant écriture dans la table action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposedAction {
+    pub action_id: Option<String>,
+    pub team_id: String,
+    pub team_name: Option<String>,
+    pub equipement_id: String,
+    pub action_type: String,
+    pub scheduled_time: String,
+    pub duration_minutes: i64,
+    pub travel_distance_m: f64,
+    pub is_late: bool,
+}
+
+/// Charge de travail d'une équipe dans la proposition
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamLoad {
+    pub team_id: String,
+    pub team_name: Option<String>,
+    pub action_count: usize,
+    pub work_minutes: i64,
+    pub travel_distance_m: f64,
+}
+
+/// Proposition de planning complète
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanningProposal {
+    pub event_id: String,
+    pub actions: Vec<ProposedAction>,
+    pub team_loads: Vec<TeamLoad>,
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+struct TeamState {
+    position: Option<(f64, f64)>,
+    available_at: Option<NaiveDateTime>,
+    load: TeamLoad,
+}
+
+/// Affecte chaque tâche à une équipe et calcule son horaire.
+/// Retourne les actions proposées (triées par horaire) et la charge de chaque équipe.
+pub fn solve_planning(
+    tasks: &[SolverTask],
+    teams: &[SolverTeam],
+    options: &SolverOptions,
+) -> (Vec<ProposedAction>, Vec<TeamLoad>) {
+    let mut states: Vec<TeamState> = teams
+        .iter()
+        .map(|t| TeamState {
+            position: None,
+            available_at: None,
+            load: TeamLoad {
+                team_id: t.id.clone(),
+                team_name: t.name.clone(),
+                action_count: 0,
+                work_minutes: 0,
+                travel_distance_m: 0.0,
+            },
+        })
+        .collect();
+
+    if states.is_empty() {
+        return (vec![], vec![]);
+    }
+
+    // Traitement par fenêtre la plus précoce, puis échéance la plus proche
+    let mut ordered: Vec<&SolverTask> = tasks.iter().collect();
+    ordered.sort_by_key(|t| {
+        let (earliest, latest) = t.start_window(options.window_minutes);
+        (earliest, latest)
+    });
+
+    let mut proposed = Vec::new();
+
+    for task in ordered {
+        // (coût, index équipe, début, durée, distance, en retard)
+        let mut best: Option<(f64, usize, NaiveDateTime, i64, f64, bool)> = None;
+
+        for (index, state) in states.iter().enumerate() {
+            let duration = task.duration_for(teams[index].size);
+            let (earliest, latest) = task.start_window_for(options.window_minutes, duration);
+            let from = state.position.or(task.origin);
+            let distance = match (from, task.position) {
+                (Some(a), Some(b)) => distance_between(a, b),
+                _ => 0.0,
+            };
+            let travel = travel_minutes(distance, options.travel_speed_kmh);
+
+            let start = match state.available_at {
+                Some(available) => (available + Duration::minutes(travel)).max(earliest),
+                None => earliest,
+            };
+            let is_late = start > latest;
+
+            let cost = (start - earliest).num_minutes() as f64
+                + travel as f64
+                + options.balance_weight * state.load.work_minutes as f64
+                // Une affectation en retard n'est retenue que si aucune équipe n'est à l'heure
+                + if is_late { 1_000_000.0 } else { 0.0 };
+
+            if best
+                .as_ref()
+                .is_none_or(|(best_cost, ..)| cost < *best_cost)
+            {
+                best = Some((cost, index, start, duration, distance, is_late));
+            }
+        }
+
+        let Some((_, index, start, duration, distance, is_late)) = best else {
+            continue;
+        };
+
+        let state = &mut states[index];
+        state.available_at = Some(start + Duration::minutes(duration));
+        if task.position.is_some() {
+            state.position = task.position;
+        }
+        state.load.action_count += 1;
+        state.load.work_minutes += duration;
+        state.load.travel_distance_m += distance;
+
+        proposed.push(ProposedAction {
+            action_id: task.action_id.clone(),
+            team_id: state.load.team_id.clone(),
+            team_name: state.load.team_name.clone(),
+            equipement_id: task.equipement_id.clone(),
+            action_type: task.action_type.clone(),
+            scheduled_time: format_datetime(&start),
+            duration_minutes: duration,
+            travel_distance_m: distance,
+            is_late,
+        });
+    }
+
+    let loads = states.into_iter().map(|s| s.load).collect();
+    (proposed, loads)
+}
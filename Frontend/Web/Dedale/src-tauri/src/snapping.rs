@@ -0,0 +1,442 @@
+// Accrochage des points et équipements au parcours
+// Les points de sécurité et barrières posés à la main dérivent de quelques mètres du tracé :
+// on les projette sur le parcours le plus proche (dans une tolérance) et on enregistre leur
+// position kilométrique pour pouvoir trier les listes dans l'ordre de la course.
+
+use crate::db;
+use crate::map::parse_linestring_wkt;
+use crate::race::{locate_on_line, point_at_chainage, PARCOURS_TOLERANCE_METERS};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+use tauri::AppHandle;
+
+/// Tracé d'un parcours (sans contrainte d'horaire)
+#[derive(Debug, Clone)]
+pub struct SnapLine {
+    pub id: String,
+    pub name: Option<String>,
+    pub line: Vec<(f64, f64)>,
+}
+
+/// Projection d'un ensemble de points sur un parcours
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapped {
+    pub parcours_index: usize,
+    /// Position kilométrique la plus proche du départ (m)
+    pub chainage_m: f64,
+    /// Écart maximal entre un point d'origine et le tracé (m)
+    pub offset_m: f64,
+    /// Points projetés (lon, lat), dans l'ordre d'origine
+    pub points: Vec<(f64, f64)>,
+}
+
+/// Résultat de l'accrochage d'un point ou d'un équipement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapResult {
+    /// "point" ou "equipement"
+    pub target_kind: String,
+    pub target_id: String,
+    /// Faux si aucun parcours n'est à portée : rien n'a été modifié
+    pub snapped: bool,
+    pub parcours_id: Option<String>,
+    pub parcours_name: Option<String>,
+    pub chainage_m: Option<f64>,
+    pub offset_m: Option<f64>,
+    /// Coordonnées après accrochage (lon, lat)
+    pub coordinates: Vec<(f64, f64)>,
+}
+
+// ==================== Fonctions helper publiques et testables ====================
+
+/// Projette des points sur le parcours le plus proche.
+/// Tous les points doivent être à moins de `tolerance_m` du même tracé ; parmi les parcours
+/// possibles, on retient celui dont l'écart maximal est le plus faible.
+pub fn snap_to_parcours(
+    parcours: &[SnapLine],
+    points: &[(f64, f64)],
+    tolerance_m: f64,
+) -> Option<Snapped> {
+    if points.is_empty() {
+        return None;
+    }
+
+    let mut best: Option<Snapped> = None;
+    for (index, p) in parcours.iter().enumerate() {
+        let positions: Option<Vec<_>> = points
+            .iter()
+            .map(|point| locate_on_line(&p.line, *point))
+            .collect();
+        let Some(positions) = positions else {
+            continue;
+        };
+
+        let offset = positions.iter().map(|pos| pos.offset_m).fold(0.0, f64::max);
+        if offset > tolerance_m || best.as_ref().is_some_and(|b| b.offset_m <= offset) {
+            continue;
+        }
+
+        let projected: Option<Vec<_>> = positions
+            .iter()
+            .map(|pos| point_at_chainage(&p.line, pos.chainage_m))
+            .collect();
+        let Some(projected) = projected else {
+            continue;
+        };
+
+        best = Some(Snapped {
+            parcours_index: index,
+            chainage_m: positions
+                .iter()
+                .map(|pos| pos.chainage_m)
+                .fold(f64::MAX, f64::min),
+            offset_m: offset,
+            points: projected,
+        });
+    }
+    best
+}
+
+/// Résultat d'accrochage à partir d'une projection (ou de son absence)
+pub fn snap_result(
+    target_kind: &str,
+    target_id: &str,
+    parcours: &[SnapLine],
+    original: &[(f64, f64)],
+    snapped: Option<&Snapped>,
+) -> SnapResult {
+    match snapped {
+        Some(s) => SnapResult {
+            target_kind: target_kind.to_string(),
+            target_id: target_id.to_string(),
+            snapped: true,
+            parcours_id: Some(parcours[s.parcours_index].id.clone()),
+            parcours_name: parcours[s.parcours_index].name.clone(),
+            chainage_m: Some(s.chainage_m),
+            offset_m: Some(s.offset_m),
+            coordinates: s.points.clone(),
+        },
+        None => SnapResult {
+            target_kind: target_kind.to_string(),
+            target_id: target_id.to_string(),
+            snapped: false,
+            parcours_id: None,
+            parcours_name: None,
+            chainage_m: None,
+            offset_m: None,
+            coordinates: original.to_vec(),
+        },
+    }
+}
+
+/// Parcours et position kilométrique d'un élément déjà accroché, recalculés après un
+/// déplacement ou une modification de tracé, sans déplacer ses points.
+/// (None, None) s'il n'est plus à portée d'aucun parcours.
+pub fn refreshed_chainage(
+    parcours: &[SnapLine],
+    points: &[(f64, f64)],
+) -> (Option<String>, Option<f64>) {
+    match snap_to_parcours(parcours, points, PARCOURS_TOLERANCE_METERS) {
+        Some(s) => (
+            Some(parcours[s.parcours_index].id.clone()),
+            Some(s.chainage_m),
+        ),
+        None => (None, None),
+    }
+}
+
+// ==================== Chargement des données ====================
+
+/// Tracés des parcours d'un événement
+pub async fn load_snap_lines(pool: &SqlitePool, event_id: &str) -> Result<Vec<SnapLine>, String> {
+    let rows = sqlx::query("SELECT id, name, geometry_json FROM parcours WHERE event_id = ?")
+        .bind(event_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let geometry: Option<String> = row.get("geometry_json");
+            let line = parse_linestring_wkt(geometry.as_deref().unwrap_or(""));
+            (line.len() >= 2).then(|| SnapLine {
+                id: row.get("id"),
+                name: row.get("name"),
+                line,
+            })
+        })
+        .collect())
+}
+
+/// Accroche un point et enregistre sa nouvelle position
+async fn snap_point(
+    pool: &SqlitePool,
+    lines: &[SnapLine],
+    point_id: &str,
+    position: (f64, f64),
+    tolerance_m: f64,
+) -> Result<SnapResult, String> {
+    let snapped = snap_to_parcours(lines, &[position], tolerance_m);
+    let result = snap_result("point", point_id, lines, &[position], snapped.as_ref());
+
+    if let Some(s) = &snapped {
+        sqlx::query("UPDATE point SET x = ?, y = ?, parcours_id = ?, chainage_m = ? WHERE id = ?")
+            .bind(s.points[0].0)
+            .bind(s.points[0].1)
+            .bind(&result.parcours_id)
+            .bind(s.chainage_m)
+            .bind(point_id)
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Erreur lors de l'accrochage du point : {}", e))?;
+    }
+    Ok(result)
+}
+
+/// Accroche la polyligne d'un équipement et enregistre ses nouvelles coordonnées
+async fn snap_equipement(
+    pool: &SqlitePool,
+    lines: &[SnapLine],
+    equipement_id: &str,
+    tolerance_m: f64,
+) -> Result<SnapResult, String> {
+    let coordinates = db::fetch_equipement_coordinates(pool, equipement_id).await?;
+    let original: Vec<(f64, f64)> = coordinates.iter().map(|c| (c.x, c.y)).collect();
+
+    let snapped = snap_to_parcours(lines, &original, tolerance_m);
+    let result = snap_result(
+        "equipement",
+        equipement_id,
+        lines,
+        &original,
+        snapped.as_ref(),
+    );
+
+    if let Some(s) = &snapped {
+        let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+        for (coordinate, (x, y)) in coordinates.iter().zip(&s.points) {
+            sqlx::query("UPDATE equipement_coordinate SET x = ?, y = ? WHERE id = ?")
+                .bind(x)
+                .bind(y)
+                .bind(&coordinate.id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Erreur lors de l'accrochage de l'équipement : {}", e))?;
+        }
+
+        sqlx::query("UPDATE equipement SET parcours_id = ?, chainage_m = ? WHERE id = ?")
+            .bind(&result.parcours_id)
+            .bind(s.chainage_m)
+            .bind(equipement_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Erreur lors de l'accrochage de l'équipement : {}", e))?;
+
+        tx.commit().await.map_err(|e| e.to_string())?;
+    }
+    Ok(result)
+}
+
+/// Recalcule la position kilométrique d'un point accroché (sans effet sinon)
+pub async fn refresh_point_chainage(pool: &SqlitePool, point_id: &str) -> Result<(), String> {
+    let row =
+        sqlx::query("SELECT event_id, x, y FROM point WHERE id = ? AND parcours_id IS NOT NULL")
+            .bind(point_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+    let Some(row) = row else {
+        return Ok(());
+    };
+
+    let lines = load_snap_lines(pool, &row.get::<String, _>("event_id")).await?;
+    let position: (f64, f64) = (row.get("x"), row.get("y"));
+    let (parcours_id, chainage_m) = refreshed_chainage(&lines, &[position]);
+    sqlx::query("UPDATE point SET parcours_id = ?, chainage_m = ? WHERE id = ?")
+        .bind(&parcours_id)
+        .bind(chainage_m)
+        .bind(point_id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Recalcule la position kilométrique des points et équipements accrochés à un parcours
+/// dont le tracé vient de changer
+pub async fn refresh_parcours_chainages(
+    pool: &SqlitePool,
+    event_id: &str,
+    parcours_id: &str,
+) -> Result<(), String> {
+    let lines = load_snap_lines(pool, event_id).await?;
+
+    let points = sqlx::query("SELECT id, x, y FROM point WHERE parcours_id = ?")
+        .bind(parcours_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    for row in &points {
+        let position: (f64, f64) = (row.get("x"), row.get("y"));
+        let (new_parcours_id, chainage_m) = refreshed_chainage(&lines, &[position]);
+        sqlx::query("UPDATE point SET parcours_id = ?, chainage_m = ? WHERE id = ?")
+            .bind(&new_parcours_id)
+            .bind(chainage_m)
+            .bind(row.get::<String, _>("id"))
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    let equipement_ids: Vec<String> =
+        sqlx::query_scalar("SELECT id FROM equipement WHERE parcours_id = ?")
+            .bind(parcours_id)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+    for equipement_id in &equipement_ids {
+        let coordinates: Vec<(f64, f64)> = db::fetch_equipement_coordinates(pool, equipement_id)
+            .await?
+            .iter()
+            .map(|c| (c.x, c.y))
+            .collect();
+        let (new_parcours_id, chainage_m) = refreshed_chainage(&lines, &coordinates);
+        sqlx::query("UPDATE equipement SET parcours_id = ?, chainage_m = ? WHERE id = ?")
+            .bind(&new_parcours_id)
+            .bind(chainage_m)
+            .bind(equipement_id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    println!(
+        "[SNAP] 🔄 Parcours {} modifié : {} point(s) et {} équipement(s) recalculé(s)",
+        parcours_id,
+        points.len(),
+        equipement_ids.len()
+    );
+    Ok(())
+}
+
+// ==================== Commandes Tauri ====================
+
+/// Accroche un point au parcours le plus proche (tolérance en mètres, optionnelle)
+#[tauri::command]
+pub async fn snap_point_to_parcours(
+    app: AppHandle,
+    point_id: String,
+    tolerance_m: Option<f64>,
+) -> Result<SnapResult, String> {
+    let pool = db::get_db_pool(&app).await?;
+
+    let row = sqlx::query("SELECT event_id, x, y FROM point WHERE id = ?")
+        .bind(&point_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Point {} introuvable", point_id))?;
+
+    let event_id: String = row.get("event_id");
+    let lines = load_snap_lines(&pool, &event_id).await?;
+    let result = snap_point(
+        &pool,
+        &lines,
+        &point_id,
+        (row.get("x"), row.get("y")),
+        tolerance_m.unwrap_or(PARCOURS_TOLERANCE_METERS),
+    )
+    .await?;
+
+    println!(
+        "[SNAP] 📍 Point {} : {}",
+        point_id,
+        if result.snapped {
+            "accroché"
+        } else {
+            "hors tolérance"
+        }
+    );
+    Ok(result)
+}
+
+/// Accroche un équipement (tous ses points) au parcours le plus proche
+#[tauri::command]
+pub async fn snap_equipement_to_parcours(
+    app: AppHandle,
+    equipement_id: String,
+    tolerance_m: Option<f64>,
+) -> Result<SnapResult, String> {
+    let pool = db::get_db_pool(&app).await?;
+
+    let event_id: String = sqlx::query_scalar("SELECT event_id FROM equipement WHERE id = ?")
+        .bind(&equipement_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Équipement {} introuvable", equipement_id))?;
+
+    let lines = load_snap_lines(&pool, &event_id).await?;
+    let result = snap_equipement(
+        &pool,
+        &lines,
+        &equipement_id,
+        tolerance_m.unwrap_or(PARCOURS_TOLERANCE_METERS),
+    )
+    .await?;
+
+    println!(
+        "[SNAP] 🚧 Équipement {} : {}",
+        equipement_id,
+        if result.snapped {
+            "accroché"
+        } else {
+            "hors tolérance"
+        }
+    );
+    Ok(result)
+}
+
+/// Accroche tous les points et équipements d'un événement situés près d'un parcours
+#[tauri::command]
+pub async fn snap_event_to_parcours(
+    app: AppHandle,
+    event_id: String,
+    tolerance_m: Option<f64>,
+) -> Result<Vec<SnapResult>, String> {
+    let pool = db::get_db_pool(&app).await?;
+    let tolerance = tolerance_m.unwrap_or(PARCOURS_TOLERANCE_METERS);
+    let lines = load_snap_lines(&pool, &event_id).await?;
+
+    let mut results = Vec::new();
+
+    let points = sqlx::query("SELECT id, x, y FROM point WHERE event_id = ?")
+        .bind(&event_id)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    for row in points {
+        let point_id: String = row.get("id");
+        let position = (row.get("x"), row.get("y"));
+        results.push(snap_point(&pool, &lines, &point_id, position, tolerance).await?);
+    }
+
+    let equipement_ids: Vec<String> =
+        sqlx::query_scalar("SELECT id FROM equipement WHERE event_id = ?")
+            .bind(&event_id)
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| e.to_string())?;
+    for equipement_id in equipement_ids {
+        results.push(snap_equipement(&pool, &lines, &equipement_id, tolerance).await?);
+    }
+
+    println!(
+        "[SNAP] ✅ {}/{} élément(s) accroché(s) aux parcours de l'événement {}",
+        results.iter().filter(|r| r.snapped).count(),
+        results.len(),
+        event_id
+    );
+    Ok(results)
+}
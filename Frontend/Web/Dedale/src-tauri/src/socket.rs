@@ -1,7 +1,9 @@
 #![allow(dead_code)]
 
 use crate::db::equipements::send_planning;
-use crate::db::{get_db_pool, insert_point, PointWithDetails};
+use crate::db::{apply_mobile_statuses, get_db_pool, insert_point, PointWithDetails};
+use crate::durations::recalibrate_rates;
+use crate::progress::emit_event_progress;
 use crate::types::*;
 use base64::{engine::general_purpose, Engine as _};
 use image::codecs::png::PngEncoder;
@@ -154,6 +156,39 @@ static EVENT_SENDER: Lazy<Mutex<Option<Sender<TransferEvent>>>> = Lazy::new(|| M
 /// Canal global pour envoyer des messages de contrôle (comme "terminate")
 static CONTROL_SENDER: Lazy<Mutex<Option<Sender<String>>>> = Lazy::new(|| Mutex::new(None));
 
+/// Applique les statuts d'actions remontés par le mobile et notifie le frontend
+async fn sync_mobile_statuses(app: &AppHandle, event_id: &str, statuses: Vec<MobileActionStatus>) {
+    if statuses.is_empty() {
+        return;
+    }
+
+    let count = statuses.len();
+    let pool = match get_db_pool(app).await {
+        Ok(pool) => pool,
+        Err(e) => {
+            eprintln!("❌ Erreur mise à jour des statuts: {}", e);
+            return;
+        }
+    };
+
+    match apply_mobile_statuses(&pool, statuses).await {
+        Ok(applied) => {
+            println!("✅ {}/{} statut(s) d'action appliqué(s)", applied, count);
+            if let Err(e) = app.emit("actions-updated", event_id) {
+                eprintln!("⚠️ Erreur émission événement actions-updated: {}", e);
+            }
+            if applied > 0 {
+                emit_event_progress(app, &pool, event_id).await;
+                // Les durées réelles remontées affinent les cadences
+                if let Err(e) = recalibrate_rates(&pool).await {
+                    eprintln!("⚠️ Erreur recalibration des cadences: {}", e);
+                }
+            }
+        }
+        Err(e) => eprintln!("❌ Erreur mise à jour des statuts: {}", e),
+    }
+}
+
 /// Insère les points au format mobile dans la base de données
 async fn insert_mobile_points(
     app: &AppHandle,
@@ -167,10 +202,23 @@ async fn insert_mobile_points(
         .map_err(|e| format!("Erreur démarrage transaction: {}", e))?;
 
     for point in &points {
-        // Insérer ou mettre à jour le point avec tous les champs (sans created_at/modified_at)
+        // Insérer ou mettre à jour le point avec tous les champs (sans created_at/modified_at).
+        // L'accrochage au parcours n'est conservé que si le mobile n'a pas déplacé le point.
         sqlx::query(
-            r#"INSERT OR REPLACE INTO point (id, event_id, x, y, name, type, status, comment) 
-               VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+            r#"INSERT INTO point (id, event_id, x, y, name, type, status, comment)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+               ON CONFLICT(id) DO UPDATE SET
+                   event_id = excluded.event_id,
+                   name = excluded.name,
+                   type = excluded.type,
+                   status = excluded.status,
+                   comment = excluded.comment,
+                   parcours_id = CASE WHEN point.x = excluded.x AND point.y = excluded.y
+                                      THEN point.parcours_id END,
+                   chainage_m = CASE WHEN point.x = excluded.x AND point.y = excluded.y
+                                     THEN point.chainage_m END,
+                   x = excluded.x,
+                   y = excluded.y"#,
         )
         .bind(&point.id)
         .bind(&event_id)
@@ -491,6 +539,8 @@ async fn handle_websocket(
                             event_name, event_id, points_count
                         );
 
+                        sync_mobile_statuses(app, &event_id, mobile_export.action_statuses).await;
+
                         // Insérer les points dans la base de données
                         if points_count > 0 {
                             println!(
@@ -988,6 +1038,8 @@ async fn handle_receive_websocket(
                             event_name, points_count
                         );
 
+                        sync_mobile_statuses(app, &event_id, mobile_export.action_statuses).await;
+
                         if points_count > 0 {
                             println!("🚀 Insertion de {} point(s)...", points_count);
                             match insert_mobile_points(app, event_id.clone(), mobile_export.points)
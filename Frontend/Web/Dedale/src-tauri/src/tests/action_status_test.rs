@@ -0,0 +1,104 @@
+use crate::db::action_status::{can_transition, normalize_changed_at, toggled_status};
+use crate::types::ActionStatus;
+use crate::utils::parse_datetime;
+
+const ALL: [ActionStatus; 6] = [
+    ActionStatus::Planned,
+    ActionStatus::EnRoute,
+    ActionStatus::InProgress,
+    ActionStatus::Done,
+    ActionStatus::Blocked,
+    ActionStatus::Cancelled,
+];
+
+#[test]
+fn test_status_roundtrip() {
+    for status in ALL {
+        assert_eq!(ActionStatus::parse(status.as_str()), Some(status));
+    }
+    assert_eq!(ActionStatus::parse("unknown"), None);
+}
+
+#[test]
+fn test_closed_statuses() {
+    let closed: Vec<ActionStatus> = ALL.into_iter().filter(|s| s.is_closed()).collect();
+    assert_eq!(closed, vec![ActionStatus::Done, ActionStatus::Cancelled]);
+}
+
+#[test]
+fn test_same_status_is_not_a_transition() {
+    for status in ALL {
+        assert!(!can_transition(status, status));
+    }
+}
+
+#[test]
+fn test_field_progression() {
+    assert!(can_transition(ActionStatus::Planned, ActionStatus::EnRoute));
+    assert!(can_transition(
+        ActionStatus::EnRoute,
+        ActionStatus::InProgress
+    ));
+    assert!(can_transition(ActionStatus::InProgress, ActionStatus::Done));
+    assert!(can_transition(
+        ActionStatus::InProgress,
+        ActionStatus::Blocked
+    ));
+    assert!(can_transition(
+        ActionStatus::Blocked,
+        ActionStatus::InProgress
+    ));
+}
+
+#[test]
+fn test_cancelled_only_replanned() {
+    assert!(can_transition(
+        ActionStatus::Cancelled,
+        ActionStatus::Planned
+    ));
+    assert!(!can_transition(ActionStatus::Cancelled, ActionStatus::Done));
+    assert!(!can_transition(
+        ActionStatus::Cancelled,
+        ActionStatus::EnRoute
+    ));
+}
+
+#[test]
+fn test_done_can_be_reopened() {
+    assert!(can_transition(ActionStatus::Done, ActionStatus::Planned));
+    assert!(can_transition(ActionStatus::Done, ActionStatus::InProgress));
+    assert!(!can_transition(ActionStatus::Done, ActionStatus::Cancelled));
+}
+
+#[test]
+fn test_normalize_changed_at() {
+    let now = parse_datetime("2025-06-01T10:00").unwrap();
+    assert_eq!(
+        normalize_changed_at(Some("2025-06-01T07:42:10"), now),
+        "2025-06-01T07:42:10"
+    );
+    assert_eq!(
+        normalize_changed_at(Some("pas une date"), now),
+        "2025-06-01T10:00:00"
+    );
+    assert_eq!(normalize_changed_at(None, now), "2025-06-01T10:00:00");
+}
+
+#[test]
+fn test_toggled_status() {
+    assert_eq!(
+        toggled_status(ActionStatus::Planned),
+        Ok(ActionStatus::Done)
+    );
+    assert_eq!(
+        toggled_status(ActionStatus::InProgress),
+        Ok(ActionStatus::Done)
+    );
+    assert_eq!(
+        toggled_status(ActionStatus::Done),
+        Ok(ActionStatus::Planned)
+    );
+    assert!(toggled_status(ActionStatus::Cancelled)
+        .unwrap_err()
+        .contains("annulée"));
+}
@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use crate::closures::{closure_schedule, sample_line, street_runs, StreetRun};
+    use crate::geocoding::Street;
+    use crate::race::{RaceParcours, RaceWave};
+    use crate::utils::parse_datetime;
+
+    // ~111 m par millième de degré de latitude
+    const MILLI_DEG_M: f64 = 111.2;
+
+    fn street(name: &str, line: Vec<(f64, f64)>) -> Street {
+        Street {
+            name: name.to_string(),
+            highway: Some("residential".to_string()),
+            line,
+        }
+    }
+
+    fn run(name: &str, from_m: f64, to_m: f64) -> StreetRun {
+        StreetRun {
+            street: name.to_string(),
+            highway: Some("residential".to_string()),
+            from_m,
+            to_m,
+        }
+    }
+
+    fn parcours() -> RaceParcours {
+        RaceParcours {
+            id: "p1".to_string(),
+            name: Some("10 km".to_string()),
+            // 12 à 6 km/h
+            waves: vec![RaceWave {
+                name: None,
+                start: parse_datetime("2025-06-01T09:00").unwrap(),
+                speed_low: Some(6.0),
+                speed_high: Some(12.0),
+            }],
+            line: vec![(0.0, 0.0), (0.0, 0.01)],
+        }
+    }
+
+    #[test]
+    fn test_sample_line_regular_steps_and_end() {
+        let line = vec![(0.0, 0.0), (0.0, 0.001)];
+        let samples = sample_line(&line, 25.0);
+
+        assert_eq!(samples.len(), 6);
+        assert_eq!(samples[0].0, 0.0);
+        assert_eq!(samples[1].0, 25.0);
+        let (last_chainage, last_point) = samples[5];
+        assert!((last_chainage - MILLI_DEG_M).abs() < 1.0);
+        assert_eq!(last_point, (0.0, 0.001));
+    }
+
+    #[test]
+    fn test_sample_line_degenerate() {
+        assert!(sample_line(&[], 10.0).is_empty());
+        assert_eq!(sample_line(&[(1.0, 2.0)], 10.0), vec![(0.0, (1.0, 2.0))]);
+    }
+
+    #[test]
+    fn test_street_runs_follow_streets_in_order() {
+        // Parcours : 300 m vers le nord puis 300 m vers l'est
+        let line = vec![(0.0, 0.0), (0.0, 0.0027), (0.0037, 0.0027)];
+        let streets = vec![
+            street("Rue du Nord", vec![(0.0, -0.001), (0.0, 0.0027)]),
+            street("Avenue de l'Est", vec![(0.0, 0.0027), (0.005, 0.0027)]),
+            // Rue perpendiculaire simplement traversée
+            street("Rue Traversière", vec![(-0.002, 0.0015), (0.002, 0.0015)]),
+            // Rue parallèle trop éloignée (~110 m)
+            street("Rue Lointaine", vec![(0.001, 0.0), (0.001, 0.0027)]),
+        ];
+
+        let runs = street_runs(&line, &streets);
+        let names: Vec<&str> = runs.iter().map(|r| r.street.as_str()).collect();
+
+        assert_eq!(names, vec!["Rue du Nord", "Avenue de l'Est"]);
+        assert_eq!(runs[0].from_m, 0.0);
+        assert!(runs[0].to_m > 280.0 && runs[0].to_m <= runs[1].from_m);
+        assert!(runs[1].to_m > 550.0);
+    }
+
+    #[test]
+    fn test_street_runs_without_streets() {
+        let line = vec![(0.0, 0.0), (0.0, 0.001)];
+        assert!(street_runs(&line, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_closure_schedule_applies_buffers() {
+        // 1 km à 12 km/h = 5 min, 2 km à 6 km/h = 20 min
+        let runs = vec![run("Rue du Nord", 1000.0, 2000.0)];
+        let closures = closure_schedule(&parcours(), &runs, 30, 15);
+
+        assert_eq!(closures.len(), 1);
+        let closure = &closures[0];
+        assert_eq!(closure.parcours_id, "p1");
+        assert_eq!(closure.street, "Rue du Nord");
+        assert_eq!(closure.closes_at, "2025-06-01T08:35");
+        assert_eq!(closure.first_passage, "2025-06-01T09:05");
+        assert_eq!(closure.last_passage, "2025-06-01T09:20");
+        assert_eq!(closure.reopens_at, "2025-06-01T09:35");
+    }
+}
@@ -0,0 +1,92 @@
+use crate::conflicts::{detect_conflicts, ConflictKind, ScheduledAction};
+use crate::utils::parse_datetime;
+
+fn action(id: &str, team: &str, time: &str, position: (f64, f64)) -> ScheduledAction {
+    ScheduledAction {
+        action_id: id.to_string(),
+        equipement_id: format!("eq-{}", id),
+        team_id: team.to_string(),
+        team_name: None,
+        start: parse_datetime(time).unwrap(),
+        duration_minutes: 15,
+        position: Some(position),
+    }
+}
+
+#[test]
+fn test_no_conflict_when_spaced() {
+    let actions = vec![
+        action("a", "t1", "2025-06-01T08:00", (7.75, 48.58)),
+        action("b", "t1", "2025-06-01T09:00", (7.76, 48.58)),
+    ];
+    assert!(detect_conflicts(&actions, 20.0).is_empty());
+}
+
+#[test]
+fn test_overlap_same_team() {
+    let actions = vec![
+        action("a", "t1", "2025-06-01T08:00", (7.75, 48.58)),
+        action("b", "t1", "2025-06-01T08:10", (7.75, 48.58)),
+    ];
+    let conflicts = detect_conflicts(&actions, 20.0);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].kind, ConflictKind::Overlap);
+    assert_eq!(conflicts[0].first_action_id, "a");
+    assert_eq!(conflicts[0].second_action_id, "b");
+    assert_eq!(conflicts[0].available_minutes, -5);
+}
+
+#[test]
+fn test_same_time_different_teams_is_fine() {
+    let actions = vec![
+        action("a", "t1", "2025-06-01T08:00", (7.75, 48.58)),
+        action("b", "t2", "2025-06-01T08:00", (7.85, 48.58)),
+    ];
+    assert!(detect_conflicts(&actions, 20.0).is_empty());
+}
+
+#[test]
+fn test_travel_time_infeasible() {
+    // ~7.4 km entre les deux équipements : 23 minutes à 20 km/h, 5 minutes disponibles
+    let actions = vec![
+        action("a", "t1", "2025-06-01T08:00", (7.75, 48.58)),
+        action("b", "t1", "2025-06-01T08:20", (7.85, 48.58)),
+    ];
+    let conflicts = detect_conflicts(&actions, 20.0);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].kind, ConflictKind::Travel);
+    assert_eq!(conflicts[0].available_minutes, 5);
+    assert!(conflicts[0].required_minutes > 20);
+}
+
+#[test]
+fn test_overlap_reported_for_every_pair() {
+    let actions = vec![
+        action("a", "t1", "2025-06-01T08:00", (7.75, 48.58)),
+        action("b", "t1", "2025-06-01T08:00", (7.75, 48.58)),
+        action("c", "t1", "2025-06-01T08:00", (7.75, 48.58)),
+    ];
+    let conflicts = detect_conflicts(&actions, 20.0);
+    assert_eq!(conflicts.len(), 3);
+    assert!(conflicts.iter().all(|c| c.kind == ConflictKind::Overlap));
+}
+
+#[test]
+fn test_travel_checked_from_latest_ending_action() {
+    // Une longue action (2 h) chevauche une action courte ; la suivante est juste après
+    // l'action courte, mais l'équipe finit la longue action à ~7,4 km de là
+    let mut long = action("a", "t1", "2025-06-01T08:00", (7.85, 48.58));
+    long.duration_minutes = 120;
+    let actions = vec![
+        long,
+        action("b", "t1", "2025-06-01T08:10", (7.75, 48.58)),
+        action("c", "t1", "2025-06-01T10:05", (7.75, 48.58)),
+    ];
+    let conflicts = detect_conflicts(&actions, 20.0);
+    assert_eq!(conflicts.len(), 2);
+    assert_eq!(conflicts[0].kind, ConflictKind::Overlap);
+    assert_eq!(conflicts[1].kind, ConflictKind::Travel);
+    assert_eq!(conflicts[1].first_action_id, "a");
+    assert_eq!(conflicts[1].second_action_id, "c");
+    assert_eq!(conflicts[1].available_minutes, 5);
+}
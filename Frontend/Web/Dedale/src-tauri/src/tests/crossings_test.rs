@@ -0,0 +1,159 @@
+use crate::crossings::{
+    crossing_point_comment, crossing_point_name, line_crossings, parcours_crossings,
+    road_crossings, segment_intersection,
+};
+use crate::geocoding::Street;
+use crate::snapping::SnapLine;
+
+fn parcours(id: &str, line: Vec<(f64, f64)>) -> SnapLine {
+    SnapLine {
+        id: id.to_string(),
+        name: Some(format!("Parcours {}", id)),
+        line,
+    }
+}
+
+fn street(name: &str, highway: &str, line: Vec<(f64, f64)>) -> Street {
+    Street {
+        name: name.to_string(),
+        highway: Some(highway.to_string()),
+        line,
+    }
+}
+
+/// Boucle en « 8 » : le tracé se recoupe en (0.005, 0.005)
+fn figure_eight() -> Vec<(f64, f64)> {
+    vec![
+        (0.0, 0.0),
+        (0.01, 0.01),
+        (0.01, 0.0),
+        (0.0, 0.01),
+        (0.0, 0.0),
+    ]
+}
+
+#[test]
+fn test_segment_intersection() {
+    let (t, u) = segment_intersection((0.0, 0.0), (2.0, 2.0), (0.0, 2.0), (2.0, 0.0)).unwrap();
+    assert!((t - 0.5).abs() < 1e-12);
+    assert!((u - 0.5).abs() < 1e-12);
+
+    // Segments disjoints ou parallèles
+    assert!(segment_intersection((0.0, 0.0), (1.0, 1.0), (2.0, 0.0), (3.0, -1.0)).is_none());
+    assert!(segment_intersection((0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)).is_none());
+}
+
+#[test]
+fn test_self_crossing_of_closed_loop() {
+    let crossings = line_crossings(&figure_eight(), None);
+
+    // Le départ/arrivée commun de la boucle n'est pas un croisement
+    assert_eq!(crossings.len(), 1);
+    let c = crossings[0];
+    assert!((c.point.0 - 0.005).abs() < 1e-9);
+    assert!((c.point.1 - 0.005).abs() < 1e-9);
+    assert!(c.chainage_a < c.chainage_b);
+}
+
+#[test]
+fn test_crossing_at_shared_vertex_is_counted_once() {
+    let a = vec![(0.0, 0.0), (0.01, 0.0)];
+    // Le second tracé passe par (0.005, 0) qui est un sommet
+    let b = vec![(0.005, -0.005), (0.005, 0.0), (0.005, 0.005)];
+    assert_eq!(line_crossings(&a, Some(&b)).len(), 1);
+}
+
+#[test]
+fn test_parcours_crossings_self_and_pairwise() {
+    let all = vec![
+        parcours("p1", figure_eight()),
+        parcours("p2", vec![(-0.005, 0.002), (0.015, 0.002)]),
+    ];
+    let crossings = parcours_crossings(&all);
+
+    let self_count = crossings.iter().filter(|c| c.kind == "self").count();
+    let pair_count = crossings.iter().filter(|c| c.kind == "parcours").count();
+    assert_eq!(self_count, 1);
+    // La ligne horizontale coupe les 4 branches du « 8 »
+    assert_eq!(pair_count, 4);
+    assert!(crossings
+        .iter()
+        .filter(|c| c.kind == "parcours")
+        .all(|c| c.other_parcours_id.as_deref() == Some("p2")));
+}
+
+#[test]
+fn test_road_crossings_keep_major_roads_only() {
+    let route = parcours("p1", vec![(0.0, 0.0), (0.0, 0.01)]);
+    let streets = vec![
+        street(
+            "Avenue Principale",
+            "primary",
+            vec![(-0.005, 0.004), (0.005, 0.004)],
+        ),
+        // Même route découpée en deux ways au niveau du tracé
+        street(
+            "Boulevard",
+            "secondary",
+            vec![(-0.005, 0.006), (0.0, 0.006)],
+        ),
+        street("Boulevard", "secondary", vec![(0.0, 0.006), (0.005, 0.006)]),
+        street(
+            "Rue Tranquille",
+            "residential",
+            vec![(-0.005, 0.008), (0.005, 0.008)],
+        ),
+    ];
+
+    let crossings = road_crossings(&route, &streets);
+    let names: Vec<&str> = crossings
+        .iter()
+        .filter_map(|c| c.street.as_deref())
+        .collect();
+    assert_eq!(names, vec!["Avenue Principale", "Boulevard"]);
+}
+
+#[test]
+fn test_road_followed_by_parcours_is_not_a_crossing() {
+    let route = parcours("p1", vec![(0.0, 0.0), (0.0, 0.01)]);
+    let streets = vec![
+        street(
+            "Route Nationale",
+            "trunk",
+            vec![(0.0, -0.001), (0.0, 0.011)],
+        ),
+        street(
+            "Route Nationale",
+            "trunk",
+            vec![(-0.005, 0.011), (0.0, 0.011)],
+        ),
+    ];
+    assert!(road_crossings(&route, &streets).is_empty());
+}
+
+#[test]
+fn test_crossing_point_labels() {
+    let route = parcours("p1", vec![(0.0, 0.0), (0.0, 0.01)]);
+    let streets = vec![street(
+        "Avenue Principale",
+        "primary",
+        vec![(-0.005, 0.004), (0.005, 0.004)],
+    )];
+    let crossing = &road_crossings(&route, &streets)[0];
+
+    assert_eq!(
+        crossing_point_name(crossing),
+        "Traversée Avenue Principale – Parcours p1"
+    );
+    assert_eq!(
+        crossing_point_comment(crossing),
+        "Traversée au km 0,44 (primary)"
+    );
+
+    let self_crossing = &parcours_crossings(&[parcours("p1", figure_eight())])[0];
+    assert_eq!(
+        crossing_point_name(self_crossing),
+        "Croisement Parcours p1 (double passage)"
+    );
+    assert!(crossing_point_comment(self_crossing).starts_with("Passages au km 0,79"));
+}
@@ -0,0 +1,91 @@
+use crate::db::dependencies::{
+    blocked_action_ids, check_precedence, creates_cycle, dependency_exists,
+};
+use crate::types::ActionDependency;
+use crate::utils::parse_datetime;
+use chrono::Duration;
+use std::collections::{HashMap, HashSet};
+
+fn dep(action_id: &str, depends_on_id: &str) -> ActionDependency {
+    ActionDependency {
+        id: format!("{}-{}", action_id, depends_on_id),
+        action_id: action_id.to_string(),
+        depends_on_id: depends_on_id.to_string(),
+    }
+}
+
+#[test]
+fn test_dependency_exists() {
+    let dependencies = vec![dep("b", "a")];
+    assert!(dependency_exists(&dependencies, "b", "a"));
+    // Le sens compte
+    assert!(!dependency_exists(&dependencies, "a", "b"));
+    assert!(!dependency_exists(&dependencies, "c", "a"));
+}
+
+#[test]
+fn test_self_dependency_is_cycle() {
+    assert!(creates_cycle(&[], "a", "a"));
+}
+
+#[test]
+fn test_direct_cycle() {
+    let deps = vec![dep("b", "a")];
+    assert!(creates_cycle(&deps, "a", "b"));
+}
+
+#[test]
+fn test_transitive_cycle() {
+    let deps = vec![dep("b", "a"), dep("c", "b")];
+    assert!(creates_cycle(&deps, "a", "c"));
+}
+
+#[test]
+fn test_no_cycle() {
+    let deps = vec![dep("b", "a"), dep("c", "b")];
+    assert!(!creates_cycle(&deps, "c", "a"));
+    assert!(!creates_cycle(&deps, "d", "c"));
+}
+
+#[test]
+fn test_blocked_until_predecessor_done() {
+    let deps = vec![dep("b", "a")];
+
+    let blocked = blocked_action_ids(&deps, &HashSet::new());
+    assert!(blocked.contains("b"));
+    assert!(!blocked.contains("a"));
+
+    let done: HashSet<String> = ["a".to_string()].into_iter().collect();
+    assert!(blocked_action_ids(&deps, &done).is_empty());
+}
+
+#[test]
+fn test_precedence_violation() {
+    let start = parse_datetime("2025-06-01T08:00").unwrap();
+    let mut schedule = HashMap::new();
+    schedule.insert("a".to_string(), (start, start + Duration::minutes(15)));
+    schedule.insert(
+        "b".to_string(),
+        (start + Duration::minutes(10), start + Duration::minutes(25)),
+    );
+
+    let violations = check_precedence(&[dep("b", "a")], &schedule);
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].action_id, "b");
+    assert_eq!(violations[0].depends_on_end, "2025-06-01T08:15");
+}
+
+#[test]
+fn test_precedence_respected() {
+    let start = parse_datetime("2025-06-01T08:00").unwrap();
+    let mut schedule = HashMap::new();
+    schedule.insert("a".to_string(), (start, start + Duration::minutes(15)));
+    schedule.insert(
+        "b".to_string(),
+        (start + Duration::minutes(15), start + Duration::minutes(30)),
+    );
+
+    assert!(check_precedence(&[dep("b", "a")], &schedule).is_empty());
+    // Action non planifiée : ignorée
+    assert!(check_precedence(&[dep("c", "a")], &schedule).is_empty());
+}
@@ -0,0 +1,163 @@
+use crate::db::depots::{build_depot_report, compute_peak_usage};
+use crate::types::{Depot, DepotMovement, DepotStock};
+
+fn depot(id: &str, stock: &[(&str, i32)]) -> Depot {
+    Depot {
+        id: id.to_string(),
+        name: Some(format!("Dépôt {}", id)),
+        address: None,
+        x: 7.75,
+        y: 48.58,
+        stock: stock
+            .iter()
+            .map(|(type_id, quantity)| DepotStock {
+                depot_id: id.to_string(),
+                type_id: type_id.to_string(),
+                type_name: None,
+                quantity: *quantity,
+            })
+            .collect(),
+    }
+}
+
+fn movement(
+    id: &str,
+    depot_id: Option<&str>,
+    type_id: &str,
+    quantity: i32,
+    departure: &str,
+    back: Option<&str>,
+) -> DepotMovement {
+    DepotMovement {
+        equipement_id: id.to_string(),
+        depot_id: depot_id.map(|d| d.to_string()),
+        type_id: Some(type_id.to_string()),
+        type_name: Some(type_id.to_uppercase()),
+        description: None,
+        quantity,
+        length_per_unit: 2,
+        departure_time: Some(departure.to_string()),
+        return_time: back.map(|b| b.to_string()),
+        team_name: None,
+    }
+}
+
+// ============== Tests pour compute_peak_usage ==============
+
+#[test]
+fn test_peak_usage_overlapping() {
+    let intervals = vec![
+        (
+            "2025-06-01T08:00".to_string(),
+            Some("2025-06-01T18:00".to_string()),
+            10,
+        ),
+        (
+            "2025-06-01T09:00".to_string(),
+            Some("2025-06-01T12:00".to_string()),
+            5,
+        ),
+    ];
+    assert_eq!(compute_peak_usage(&intervals), 15);
+}
+
+#[test]
+fn test_peak_usage_return_before_departure_at_same_time() {
+    let intervals = vec![
+        (
+            "2025-06-01T08:00".to_string(),
+            Some("2025-06-01T12:00".to_string()),
+            10,
+        ),
+        (
+            "2025-06-01T12:00".to_string(),
+            Some("2025-06-01T18:00".to_string()),
+            10,
+        ),
+    ];
+    assert_eq!(compute_peak_usage(&intervals), 10);
+}
+
+#[test]
+fn test_peak_usage_without_return() {
+    let intervals = vec![
+        ("2025-06-01T08:00".to_string(), None, 4),
+        ("2025-06-02T08:00".to_string(), None, 3),
+    ];
+    assert_eq!(compute_peak_usage(&intervals), 7);
+}
+
+#[test]
+fn test_peak_usage_empty() {
+    assert_eq!(compute_peak_usage(&[]), 0);
+}
+
+// ============== Tests pour build_depot_report ==============
+
+#[test]
+fn test_depot_report_groups_by_depot_and_sorts_departures() {
+    let depots = vec![depot("a", &[("barriere", 20)]), depot("b", &[])];
+    let movements = vec![
+        movement("e2", Some("a"), "barriere", 5, "2025-06-01T10:00", None),
+        movement("e1", Some("a"), "barriere", 5, "2025-06-01T08:00", None),
+        movement("e3", None, "bloc", 2, "2025-06-01T09:00", None),
+    ];
+
+    let reports = build_depot_report(&depots, movements);
+
+    // Le dépôt b n'a aucune sortie : il n'apparaît pas
+    assert_eq!(reports.len(), 2);
+    assert_eq!(reports[0].depot.as_ref().unwrap().id, "a");
+    assert_eq!(reports[0].departures[0].equipement_id, "e1");
+    assert_eq!(reports[0].departures[1].equipement_id, "e2");
+    assert!(reports[1].depot.is_none());
+}
+
+#[test]
+fn test_depot_report_flags_missing_stock() {
+    let depots = vec![depot("a", &[("barriere", 8)])];
+    let movements = vec![
+        movement(
+            "e1",
+            Some("a"),
+            "barriere",
+            5,
+            "2025-06-01T08:00",
+            Some("2025-06-02T08:00"),
+        ),
+        movement(
+            "e2",
+            Some("a"),
+            "barriere",
+            5,
+            "2025-06-01T09:00",
+            Some("2025-06-02T08:00"),
+        ),
+    ];
+
+    let reports = build_depot_report(&depots, movements);
+    let balance = &reports[0].balances[0];
+
+    assert_eq!(balance.stock, 8);
+    assert_eq!(balance.total_out, 10);
+    assert_eq!(balance.peak_out, 10);
+    assert_eq!(balance.missing, 2);
+}
+
+#[test]
+fn test_depot_report_unknown_depot_is_treated_as_none() {
+    let movements = vec![movement(
+        "e1",
+        Some("supprime"),
+        "barriere",
+        1,
+        "2025-06-01T08:00",
+        None,
+    )];
+
+    let reports = build_depot_report(&[], movements);
+
+    assert_eq!(reports.len(), 1);
+    assert!(reports[0].depot.is_none());
+    assert_eq!(reports[0].balances[0].missing, 1);
+}
@@ -0,0 +1,105 @@
+use crate::durations::{
+    actual_duration_minutes, calibration_factor, estimate_minutes, rate_action_type, rate_key,
+    team_size_factor, CompletionSample, ProductivityRate,
+};
+use crate::scheduling::DEFAULT_ACTION_DURATION_MINUTES;
+use crate::types::ActionStatus;
+use crate::utils::parse_datetime;
+
+fn rate() -> ProductivityRate {
+    ProductivityRate {
+        type_id: "barriere".to_string(),
+        action_type: "pose".to_string(),
+        base_minutes: 5.0,
+        minutes_per_unit: 1.0,
+        minutes_per_metre: 0.0,
+        reference_team_size: 2,
+        calibration_factor: 1.0,
+        sample_count: 0,
+    }
+}
+
+fn sample(actual_minutes: f64) -> CompletionSample {
+    CompletionSample {
+        quantity: 10.0,
+        length_m: 20.0,
+        team_size: 2,
+        actual_minutes,
+    }
+}
+
+#[test]
+fn test_team_size_factor() {
+    assert_eq!(team_size_factor(2, 2), 1.0);
+    assert!(team_size_factor(2, 4) < 1.0);
+    assert!(team_size_factor(2, 1) > 1.0);
+    assert_eq!(team_size_factor(2, 0), 1.0);
+}
+
+#[test]
+fn test_estimate_minutes() {
+    // 5 min fixes + 10 unités à 1 min
+    assert_eq!(estimate_minutes(Some(&rate()), 10.0, 20.0, 2), 15);
+    assert!(estimate_minutes(Some(&rate()), 10.0, 20.0, 4) < 15);
+    assert_eq!(
+        estimate_minutes(None, 10.0, 20.0, 2),
+        DEFAULT_ACTION_DURATION_MINUTES
+    );
+
+    let mut calibrated = rate();
+    calibrated.calibration_factor = 2.0;
+    assert_eq!(estimate_minutes(Some(&calibrated), 10.0, 20.0, 2), 30);
+}
+
+#[test]
+fn test_actual_duration_from_history() {
+    let at = |t: &str| parse_datetime(t).unwrap();
+    let history = vec![
+        (ActionStatus::EnRoute, at("2025-06-01T08:00")),
+        (ActionStatus::InProgress, at("2025-06-01T08:10")),
+        (ActionStatus::Blocked, at("2025-06-01T08:20")),
+        (ActionStatus::InProgress, at("2025-06-01T08:30")),
+        (ActionStatus::Done, at("2025-06-01T08:55")),
+    ];
+    assert_eq!(actual_duration_minutes(&history), Some(25.0));
+
+    // Terminée sans passage « en cours » : pas de durée réelle
+    let history = vec![(ActionStatus::Done, at("2025-06-01T08:55"))];
+    assert_eq!(actual_duration_minutes(&history), None);
+}
+
+#[test]
+fn test_calibration_factor() {
+    assert_eq!(calibration_factor(&rate(), &[]), 1.0);
+
+    // Durées réelles deux fois plus longues : facteur > 1, atténué par le peu d'échantillons
+    let samples = vec![sample(30.0), sample(30.0), sample(30.0)];
+    let factor = calibration_factor(&rate(), &samples);
+    assert!(factor > 1.0 && factor < 2.0);
+
+    // Avec beaucoup d'échantillons, on se rapproche du rapport observé
+    let many: Vec<CompletionSample> = (0..100).map(|_| sample(30.0)).collect();
+    assert!((calibration_factor(&rate(), &many) - 2.0).abs() < 0.1);
+}
+
+// ============== Tests pour les types d'action des cadences ==============
+
+#[test]
+fn test_rate_action_type_accepts_retrait() {
+    assert_eq!(rate_action_type("pose"), Some("pose"));
+    assert_eq!(rate_action_type("depose"), Some("depose"));
+    assert_eq!(rate_action_type("retrait"), Some("depose"));
+    assert_eq!(rate_action_type("inspection"), None);
+}
+
+#[test]
+fn test_rate_key_maps_retrait_to_depose() {
+    assert_eq!(
+        rate_key("t1".to_string(), "retrait"),
+        ("t1".to_string(), "depose".to_string())
+    );
+    assert_eq!(
+        rate_key("t1".to_string(), "pose"),
+        ("t1".to_string(), "pose".to_string())
+    );
+}
@@ -0,0 +1,134 @@
+use crate::gantt::{
+    bar_color, chart_bounds, race_window, render_gantt_svg, tick_step_minutes, GanttBar,
+    GanttChart, GanttLane, GanttRaceWindow,
+};
+use crate::types::ActionStatus;
+use crate::utils::parse_datetime;
+
+fn bar(id: &str, action_type: &str, status: ActionStatus, start: &str, end: &str) -> GanttBar {
+    GanttBar {
+        action_id: id.to_string(),
+        label: format!("Barrière {}", id),
+        action_type: action_type.to_string(),
+        status,
+        start: parse_datetime(start).unwrap(),
+        end: parse_datetime(end).unwrap(),
+    }
+}
+
+fn chart() -> GanttChart {
+    GanttChart {
+        event_name: "Course <test>".to_string(),
+        lanes: vec![
+            GanttLane {
+                team_id: "t1".to_string(),
+                team_name: "Équipe A".to_string(),
+                bars: vec![
+                    bar(
+                        "1",
+                        "pose",
+                        ActionStatus::Done,
+                        "2025-06-01T07:00",
+                        "2025-06-01T07:15",
+                    ),
+                    bar(
+                        "2",
+                        "retrait",
+                        ActionStatus::InProgress,
+                        "2025-06-01T12:00",
+                        "2025-06-01T12:15",
+                    ),
+                ],
+            },
+            GanttLane {
+                team_id: "t2".to_string(),
+                team_name: "Équipe B".to_string(),
+                bars: vec![],
+            },
+        ],
+        race_windows: vec![GanttRaceWindow {
+            label: "10 km".to_string(),
+            start: parse_datetime("2025-06-01T09:00").unwrap(),
+            end: parse_datetime("2025-06-01T10:30").unwrap(),
+        }],
+    }
+}
+
+#[test]
+fn test_chart_bounds_include_margin() {
+    let (start, end) = chart_bounds(&chart()).unwrap();
+    assert_eq!(start, parse_datetime("2025-06-01T06:30").unwrap());
+    assert_eq!(end, parse_datetime("2025-06-01T12:45").unwrap());
+}
+
+#[test]
+fn test_chart_bounds_empty() {
+    let empty = GanttChart {
+        event_name: "Vide".to_string(),
+        lanes: vec![],
+        race_windows: vec![],
+    };
+    assert!(chart_bounds(&empty).is_none());
+}
+
+#[test]
+fn test_tick_step_minutes() {
+    assert_eq!(tick_step_minutes(120), 15);
+    assert_eq!(tick_step_minutes(6 * 60), 30);
+    assert_eq!(tick_step_minutes(10 * 60), 60);
+    assert_eq!(tick_step_minutes(10 * 24 * 60), 1440);
+}
+
+#[test]
+fn test_race_window_uses_low_speed() {
+    let start = parse_datetime("2025-06-01T09:00").unwrap();
+    let (from, to) = race_window(start, 10_000.0, Some(10.0), Some(20.0)).unwrap();
+    assert_eq!(from, start);
+    assert_eq!(to, parse_datetime("2025-06-01T10:00").unwrap());
+    assert!(race_window(start, 10_000.0, None, None).is_none());
+}
+
+#[test]
+fn test_render_gantt_svg() {
+    let svg = render_gantt_svg(&chart());
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert!(svg.contains("Équipe A"));
+    assert!(svg.contains("Équipe B"));
+    // Titre échappé
+    assert!(svg.contains("Course &lt;test&gt;"));
+    // Barres colorées selon le statut, libellé du statut dans l'infobulle
+    let bar_fill = |color: &str| {
+        format!(
+            r##"fill="{}" stroke="#555555" stroke-width="0.5"><title>"##,
+            color
+        )
+    };
+    assert!(svg.contains(&bar_fill("#2e7d32")));
+    assert!(svg.contains(&bar_fill("#e57373")));
+    assert!(!svg.contains(&bar_fill("#ef9a9a")));
+    assert!(svg.contains("Barrière 2 (En cours)"));
+    // Fenêtre de course
+    assert!(svg.contains("10 km"));
+}
+
+#[test]
+fn test_bar_color_by_status() {
+    assert_eq!(bar_color("pose", ActionStatus::Planned), "#a5d6a7");
+    assert_eq!(bar_color("pose", ActionStatus::InProgress), "#66bb6a");
+    assert_eq!(bar_color("pose", ActionStatus::EnRoute), "#66bb6a");
+    assert_eq!(bar_color("retrait", ActionStatus::Done), "#c62828");
+    assert_eq!(bar_color("depose", ActionStatus::Planned), "#ef9a9a");
+    assert_eq!(bar_color("pose", ActionStatus::Cancelled), "#e0e0e0");
+    assert_eq!(bar_color("depose", ActionStatus::Blocked), "#ffb74d");
+}
+
+#[test]
+fn test_render_gantt_svg_without_actions() {
+    let empty = GanttChart {
+        event_name: "Vide".to_string(),
+        lanes: vec![],
+        race_windows: vec![],
+    };
+    assert!(render_gantt_svg(&empty).contains("Aucune action planifiée"));
+}
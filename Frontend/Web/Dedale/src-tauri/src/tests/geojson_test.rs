@@ -0,0 +1,186 @@
+use crate::geojson::{
+    declared_crs, feature_collection, geometry_from_json, geometry_to_json, layer_accepts,
+    parse_feature_collection, prop_bool, prop_i64, resolve_type_id, GeoFeature, GeoGeometry,
+};
+use crate::projection::Crs;
+use serde_json::{json, Map, Value};
+
+fn props(value: Value) -> Map<String, Value> {
+    value.as_object().cloned().unwrap()
+}
+
+// ============== Tests pour geometry_to_json / geometry_from_json ==============
+
+#[test]
+fn test_polygon_is_closed_on_export() {
+    let geometry = GeoGeometry::Polygon(vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)]);
+    let value = geometry_to_json(&geometry);
+    assert_eq!(value["type"], "Polygon");
+    assert_eq!(value["coordinates"][0].as_array().unwrap().len(), 4);
+    assert_eq!(value["coordinates"][0][3], json!([0.0, 0.0]));
+}
+
+#[test]
+fn test_geometry_roundtrip() {
+    let line = GeoGeometry::LineString(vec![(2.35, 48.85), (2.36, 48.86)]);
+    assert_eq!(
+        geometry_from_json(&geometry_to_json(&line), Crs::Wgs84).unwrap(),
+        line
+    );
+
+    let point = GeoGeometry::Point((2.35, 48.85));
+    assert_eq!(
+        geometry_from_json(&geometry_to_json(&point), Crs::Wgs84).unwrap(),
+        point
+    );
+}
+
+#[test]
+fn test_geometry_validation_errors() {
+    let short_line = json!({ "type": "LineString", "coordinates": [[2.35, 48.85]] });
+    assert!(geometry_from_json(&short_line, Crs::Wgs84).is_err());
+
+    let out_of_range = json!({ "type": "Point", "coordinates": [200.0, 48.85] });
+    assert!(geometry_from_json(&out_of_range, Crs::Wgs84)
+        .unwrap_err()
+        .contains("hors limites"));
+
+    let multi = json!({ "type": "MultiPoint", "coordinates": [[2.35, 48.85]] });
+    assert!(geometry_from_json(&multi, Crs::Wgs84)
+        .unwrap_err()
+        .contains("non pris en charge"));
+}
+
+#[test]
+fn test_layer_accepts() {
+    let point = GeoGeometry::Point((0.0, 0.0));
+    let line = GeoGeometry::LineString(vec![(0.0, 0.0), (1.0, 1.0)]);
+    assert!(layer_accepts("interest", &point));
+    assert!(layer_accepts("equipement", &point));
+    assert!(layer_accepts("equipement", &line));
+    assert!(!layer_accepts("parcours", &point));
+    assert!(!layer_accepts("zone", &line));
+}
+
+// ============== Tests pour feature_collection / parse_feature_collection ==============
+
+#[test]
+fn test_feature_collection_roundtrip() {
+    let features = vec![GeoFeature {
+        layer: "zone".to_string(),
+        id: "z1".to_string(),
+        name: Some("Village".to_string()),
+        geometry: GeoGeometry::Polygon(vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)]),
+        properties: props(json!({ "color": "#ff0000", "restricted": 1 })),
+    }];
+
+    let text = feature_collection("Marathon", &features, Crs::Wgs84).to_string();
+    let (parsed, errors) = parse_feature_collection(&text, None).unwrap();
+
+    assert!(errors.is_empty());
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(parsed[0].layer, "zone");
+    assert_eq!(parsed[0].id, "z1");
+    assert_eq!(parsed[0].name.as_deref(), Some("Village"));
+    assert_eq!(parsed[0].geometry, features[0].geometry);
+    assert_eq!(prop_bool(&parsed[0].properties, "restricted"), Some(true));
+}
+
+#[test]
+fn test_parse_feature_collection_defaults_and_errors() {
+    let text = json!({
+        "type": "FeatureCollection",
+        "features": [
+            { "type": "Feature", "geometry": { "type": "LineString", "coordinates": [[0, 0], [1, 1]] }, "properties": {} },
+            { "type": "Feature", "geometry": { "type": "Point", "coordinates": [0, 0] }, "properties": { "layer": "zone" } },
+            { "type": "Feature", "geometry": null, "properties": {} }
+        ]
+    })
+    .to_string();
+
+    let (parsed, errors) = parse_feature_collection(&text, None).unwrap();
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(parsed[0].layer, "parcours");
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].starts_with("Entité 2"));
+    assert!(errors[1].starts_with("Entité 3"));
+}
+
+#[test]
+fn test_parse_rejects_non_collection() {
+    assert!(parse_feature_collection(r#"{"type": "Feature"}"#, None).is_err());
+    assert!(parse_feature_collection("pas du json", None).is_err());
+}
+
+#[test]
+fn test_feature_collection_projected_roundtrip() {
+    let features = vec![GeoFeature {
+        layer: "parcours".to_string(),
+        id: "p1".to_string(),
+        name: None,
+        geometry: GeoGeometry::LineString(vec![(7.7521, 48.5734), (7.7612, 48.5802)]),
+        properties: Map::new(),
+    }];
+
+    let collection = feature_collection("Marathon", &features, Crs::Cc48);
+    assert_eq!(
+        collection["crs"]["properties"]["name"],
+        "urn:ogc:def:crs:EPSG::3948"
+    );
+    let x = collection["features"][0]["geometry"]["coordinates"][0][0]
+        .as_f64()
+        .unwrap();
+    assert!(x > 2_000_000.0 && x < 2_100_000.0);
+
+    // Le système déclaré par le fichier est relu sans être imposé
+    let (parsed, errors) = parse_feature_collection(&collection.to_string(), None).unwrap();
+    assert!(errors.is_empty());
+    let GeoGeometry::LineString(line) = &parsed[0].geometry else {
+        panic!("ligne attendue");
+    };
+    assert!((line[0].0 - 7.7521).abs() < 1e-8);
+    assert!((line[1].1 - 48.5802).abs() < 1e-8);
+}
+
+#[test]
+fn test_declared_crs() {
+    assert_eq!(declared_crs(&json!({})).unwrap(), Crs::Wgs84);
+    let lambert = json!({ "crs": { "type": "name", "properties": { "name": "EPSG:2154" } } });
+    assert_eq!(declared_crs(&lambert).unwrap(), Crs::Lambert93);
+    let unknown = json!({ "crs": { "type": "name", "properties": { "name": "EPSG:27572" } } });
+    assert!(declared_crs(&unknown).is_err());
+}
+
+// ============== Tests pour les propriétés ==============
+
+#[test]
+fn test_prop_conversions() {
+    let p = props(json!({ "start_time": 1717225200000.0, "status": true, "quantity": "12" }));
+    assert_eq!(prop_i64(&p, "start_time"), Some(1717225200000));
+    assert_eq!(prop_bool(&p, "status"), Some(true));
+    assert_eq!(prop_i64(&p, "quantity"), Some(12));
+    assert_eq!(prop_i64(&p, "absent"), None);
+}
+
+#[test]
+fn test_resolve_type_id() {
+    let types = vec![
+        ("t1".to_string(), Some("Barrière Vauban".to_string())),
+        ("t2".to_string(), Some("Glissière".to_string())),
+    ];
+    assert_eq!(
+        resolve_type_id(&props(json!({ "type_id": "t2" })), &types),
+        Some("t2".to_string())
+    );
+    assert_eq!(
+        resolve_type_id(
+            &props(json!({ "type_id": "inconnu", "type_name": "barrière vauban" })),
+            &types
+        ),
+        Some("t1".to_string())
+    );
+    assert_eq!(
+        resolve_type_id(&props(json!({ "type_name": "Plot" })), &types),
+        None
+    );
+}
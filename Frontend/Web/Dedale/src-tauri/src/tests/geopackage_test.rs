@@ -0,0 +1,206 @@
+use crate::geojson::{GeoFeature, GeoGeometry};
+use crate::geopackage::{
+    column_type, geometry_wkb, gpkg_geometry, gpkg_layers, gpkg_srs, write_geopackage,
+};
+use crate::projection::Crs;
+use serde_json::{json, Value};
+
+fn feature(layer: &str, id: &str, geometry: GeoGeometry, properties: Value) -> GeoFeature {
+    GeoFeature {
+        layer: layer.to_string(),
+        id: id.to_string(),
+        name: None,
+        geometry,
+        properties: properties.as_object().cloned().unwrap_or_default(),
+    }
+}
+
+fn sample_features() -> Vec<GeoFeature> {
+    vec![
+        feature(
+            "parcours",
+            "p1",
+            GeoGeometry::LineString(vec![(7.74, 48.58), (7.75, 48.59)]),
+            json!({ "name": "10 km", "speed_low": 8.5, "start_time": 1717225200000i64 }),
+        ),
+        feature(
+            "zone",
+            "z1",
+            GeoGeometry::Polygon(vec![(7.74, 48.58), (7.75, 48.58), (7.75, 48.59)]),
+            json!({ "name": "Village", "restricted": 1 }),
+        ),
+        feature(
+            "equipement",
+            "e1",
+            GeoGeometry::LineString(vec![(7.74, 48.58), (7.741, 48.581), (7.742, 48.582)]),
+            json!({ "type_name": "Vauban", "quantity": 10 }),
+        ),
+        feature(
+            "equipement",
+            "e2",
+            GeoGeometry::Point((7.76, 48.6)),
+            json!({ "type_name": "Plot", "quantity": 1 }),
+        ),
+    ]
+}
+
+// ============== Tests pour l'encodage des géométries ==============
+
+#[test]
+fn test_geometry_wkb_point() {
+    let wkb = geometry_wkb(&GeoGeometry::Point((1.0, 2.0)));
+    assert_eq!(wkb.len(), 21);
+    assert_eq!(wkb[0], 1);
+    assert_eq!(&wkb[1..5], &1u32.to_le_bytes());
+    assert_eq!(&wkb[5..13], &1.0f64.to_le_bytes());
+    assert_eq!(&wkb[13..21], &2.0f64.to_le_bytes());
+}
+
+#[test]
+fn test_geometry_wkb_polygon_is_closed() {
+    let wkb = geometry_wkb(&GeoGeometry::Polygon(vec![
+        (0.0, 0.0),
+        (1.0, 0.0),
+        (1.0, 1.0),
+    ]));
+    // ordre + type + nb anneaux + nb points + 4 points
+    assert_eq!(wkb.len(), 1 + 4 + 4 + 4 + 4 * 16);
+    assert_eq!(&wkb[9..13], &4u32.to_le_bytes());
+}
+
+#[test]
+fn test_gpkg_geometry_header() {
+    let point = gpkg_geometry(&GeoGeometry::Point((1.0, 2.0)), 4326);
+    assert_eq!(&point[0..4], &[b'G', b'P', 0, 1]);
+    assert_eq!(&point[4..8], &4326i32.to_le_bytes());
+    assert_eq!(point.len(), 8 + 21);
+
+    let line = gpkg_geometry(&GeoGeometry::LineString(vec![(3.0, 4.0), (1.0, 2.0)]), 4326);
+    assert_eq!(line[3], 0b0000_0011);
+    // Emprise [min_x, max_x, min_y, max_y]
+    assert_eq!(&line[8..16], &1.0f64.to_le_bytes());
+    assert_eq!(&line[16..24], &3.0f64.to_le_bytes());
+    assert_eq!(&line[24..32], &2.0f64.to_le_bytes());
+    assert_eq!(&line[32..40], &4.0f64.to_le_bytes());
+}
+
+// ============== Tests pour les couches ==============
+
+#[test]
+fn test_column_type() {
+    assert_eq!(column_type(&[json!(1), Value::Null]), "INTEGER");
+    assert_eq!(column_type(&[json!(1), json!(2.5)]), "REAL");
+    assert_eq!(column_type(&[json!(1), json!("a")]), "TEXT");
+    assert_eq!(column_type(&[Value::Null]), "TEXT");
+}
+
+#[test]
+fn test_gpkg_layers_split_equipements() {
+    let layers = gpkg_layers(&sample_features(), Crs::Wgs84);
+    let tables: Vec<&str> = layers.iter().map(|l| l.table.as_str()).collect();
+    assert_eq!(
+        tables,
+        vec![
+            "parcours",
+            "zones",
+            "points",
+            "interest_points",
+            "equipement_lines",
+            "equipement_nodes"
+        ]
+    );
+
+    let parcours = &layers[0];
+    assert!(parcours
+        .columns
+        .contains(&("speed_low".to_string(), "REAL")));
+    assert!(parcours
+        .columns
+        .contains(&("start_time".to_string(), "INTEGER")));
+    assert!(parcours.columns.contains(&("id".to_string(), "TEXT")));
+
+    assert_eq!(layers[4].rows.len(), 1);
+    assert_eq!(layers[5].rows.len(), 4);
+}
+
+// ============== Tests pour write_geopackage ==============
+
+#[test]
+fn test_write_geopackage() {
+    let path = std::env::temp_dir().join(format!("dedale_test_{}.gpkg", std::process::id()));
+    write_geopackage(
+        &path,
+        &gpkg_layers(&sample_features(), Crs::Wgs84),
+        &gpkg_srs(Crs::Wgs84),
+    )
+    .unwrap();
+
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    let application_id: i64 = conn
+        .query_row("PRAGMA application_id", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(application_id, 0x47504B47);
+
+    let contents: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM gpkg_contents WHERE srs_id = 4326",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(contents, 6);
+
+    let (name, geom): (String, Vec<u8>) = conn
+        .query_row("SELECT name, geom FROM parcours", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(name, "10 km");
+    assert_eq!(&geom[0..2], b"GP");
+
+    let nodes: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM equipement_nodes WHERE equipement_id = 'e1'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(nodes, 3);
+
+    drop(conn);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_write_geopackage_lambert93() {
+    let path = std::env::temp_dir().join(format!("dedale_l93_{}.gpkg", std::process::id()));
+    let layers = gpkg_layers(&sample_features(), Crs::Lambert93);
+    let GeoGeometry::Point((x, y)) = &layers[5].rows[3].0 else {
+        panic!("point attendu");
+    };
+    assert!(*x > 1_000_000.0 && *y > 6_800_000.0);
+
+    write_geopackage(&path, &layers, &gpkg_srs(Crs::Lambert93)).unwrap();
+
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    let srs_ids: Vec<i64> = conn
+        .prepare("SELECT srs_id FROM gpkg_spatial_ref_sys WHERE srs_id > 0 ORDER BY srs_id")
+        .unwrap()
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(srs_ids, vec![2154, 4326]);
+
+    let columns: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM gpkg_geometry_columns WHERE srs_id = 2154",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(columns, 6);
+
+    drop(conn);
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,99 @@
+use crate::gpx::{build_gpx, parse_gpx, thin_points, GpxTrack, GpxWaypoint};
+
+const TRACK_GPX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx version="1.1" creator="Watch" xmlns="http://www.topografix.com/GPX/1/1">
+  <metadata><name>Sortie du matin</name></metadata>
+  <trk>
+    <name>Marathon</name>
+    <trkseg>
+      <trkpt lat="48.85" lon="2.35"><ele>35</ele></trkpt>
+      <trkpt lat="48.86" lon="2.36"/>
+    </trkseg>
+    <trkseg>
+      <trkpt lat="48.87" lon="2.37"/>
+    </trkseg>
+  </trk>
+</gpx>"#;
+
+// ============== Tests pour parse_gpx ==============
+
+#[test]
+fn test_parse_gpx_track_joins_segments() {
+    let track = parse_gpx(TRACK_GPX).unwrap();
+    assert_eq!(track.name.as_deref(), Some("Marathon"));
+    assert_eq!(
+        track.points,
+        vec![(2.35, 48.85), (2.36, 48.86), (2.37, 48.87)]
+    );
+}
+
+#[test]
+fn test_parse_gpx_route_with_metadata_name() {
+    let xml = r#"<gpx xmlns="http://www.topografix.com/GPX/1/0">
+      <metadata><name>10 km</name></metadata>
+      <rte><rtept lat="45.0" lon="5.0"/><rtept lat="45.1" lon="5.1"/></rte>
+    </gpx>"#;
+    let track = parse_gpx(xml).unwrap();
+    assert_eq!(track.name.as_deref(), Some("10 km"));
+    assert_eq!(track.points, vec![(5.0, 45.0), (5.1, 45.1)]);
+}
+
+#[test]
+fn test_parse_gpx_errors() {
+    assert!(parse_gpx("<kml/>").is_err());
+    assert!(parse_gpx("<gpx><wpt lat=\"1\" lon=\"1\"/></gpx>").is_err());
+    assert!(parse_gpx(
+        "<gpx><trk><trkseg><trkpt lat=\"95\" lon=\"1\"/><trkpt lat=\"1\" lon=\"1\"/></trkseg></trk></gpx>"
+    )
+    .is_err());
+}
+
+// ============== Tests pour thin_points ==============
+
+#[test]
+fn test_thin_points_removes_aligned_points() {
+    // Points alignés sur un méridien + un détour de ~110 m
+    let line = vec![
+        (2.0, 48.0),
+        (2.0, 48.001),
+        (2.0, 48.002),
+        (2.0015, 48.003),
+        (2.0, 48.004),
+    ];
+    let thinned = thin_points(&line, 10.0);
+    assert_eq!(
+        thinned,
+        vec![(2.0, 48.0), (2.0, 48.002), (2.0015, 48.003), (2.0, 48.004)]
+    );
+
+    assert_eq!(thin_points(&line, 500.0), vec![(2.0, 48.0), (2.0, 48.004)]);
+    assert_eq!(thin_points(&line, 0.0), line);
+}
+
+// ============== Tests pour build_gpx ==============
+
+#[test]
+fn test_build_gpx_roundtrip_and_waypoints() {
+    let waypoints = vec![GpxWaypoint {
+        name: "Carrefour <Nord> & Sud".to_string(),
+        description: Some("Signaleur".to_string()),
+        x: 2.35,
+        y: 48.85,
+    }];
+    let line = vec![(2.35, 48.85), (2.36, 48.86)];
+    let gpx = build_gpx(
+        "Marathon",
+        &[GpxTrack {
+            name: Some("Boucle".to_string()),
+            points: line.clone(),
+        }],
+        &waypoints,
+    );
+
+    assert!(gpx.contains("<name>Carrefour &lt;Nord&gt; &amp; Sud</name>"));
+    assert!(gpx.contains("<wpt lat=\"48.85\" lon=\"2.35\">"));
+
+    let track = parse_gpx(&gpx).unwrap();
+    assert_eq!(track.name.as_deref(), Some("Boucle"));
+    assert_eq!(track.points, line);
+}
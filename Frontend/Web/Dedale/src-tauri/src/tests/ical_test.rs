@@ -0,0 +1,133 @@
+use crate::ical::{
+    action_uid, build_calendar, escape_ical_text, event_content_lines, fold_line,
+    format_ical_datetime, next_content_revision, CalendarEntry,
+};
+use crate::utils::parse_datetime;
+use chrono::{TimeZone, Utc};
+
+fn entry(id: &str) -> CalendarEntry {
+    CalendarEntry {
+        action_id: id.to_string(),
+        action_type: "pose".to_string(),
+        equipement_description: Some("Barrières, rue du Dôme".to_string()),
+        type_name: Some("Barrière".to_string()),
+        team_name: Some("Équipe A".to_string()),
+        position: Some((7.75, 48.58)),
+        start: parse_datetime("2025-06-01T08:00").unwrap(),
+        end: parse_datetime("2025-06-01T08:15").unwrap(),
+        sequence: 0,
+        last_modified: None,
+    }
+}
+
+#[test]
+fn test_action_uid_is_stable() {
+    assert_eq!(action_uid("abc"), action_uid("abc"));
+    assert_eq!(action_uid("abc"), "abc@dedale");
+}
+
+#[test]
+fn test_escape_ical_text() {
+    assert_eq!(escape_ical_text("a,b;c\\d\ne"), r"a\,b\;c\\d\ne");
+}
+
+#[test]
+fn test_format_ical_datetime() {
+    let dt = parse_datetime("2025-06-01T08:05").unwrap();
+    assert_eq!(format_ical_datetime(&dt), "20250601T080500");
+}
+
+#[test]
+fn test_fold_line_short_unchanged() {
+    assert_eq!(fold_line("SUMMARY:Pose"), "SUMMARY:Pose");
+}
+
+#[test]
+fn test_fold_line_long() {
+    let line = "X".repeat(100);
+    let folded = fold_line(&line);
+    let parts: Vec<&str> = folded.split("\r\n").collect();
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].len(), 75);
+    assert!(parts[1].starts_with(' '));
+    assert_eq!(parts[1].len(), 26);
+}
+
+#[test]
+fn test_fold_line_keeps_utf8_characters() {
+    let line = "é".repeat(60);
+    let folded = fold_line(&line);
+    for part in folded.split("\r\n") {
+        assert!(part.len() <= 75);
+    }
+    assert_eq!(folded.replace("\r\n ", ""), line);
+}
+
+#[test]
+fn test_build_calendar() {
+    let stamp = Utc.with_ymd_and_hms(2025, 5, 1, 12, 0, 0).unwrap();
+    let calendar = build_calendar("Équipe A", &[entry("a1"), entry("a2")], stamp);
+
+    assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+    assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 2);
+    assert!(calendar.contains("UID:a1@dedale\r\n"));
+    assert!(calendar.contains("DTSTAMP:20250501T120000Z\r\n"));
+    assert!(calendar.contains("SEQUENCE:0\r\n"));
+    assert!(!calendar.contains("LAST-MODIFIED"));
+    assert!(calendar.contains("DTSTART:20250601T080000\r\n"));
+    assert!(calendar.contains("DTEND:20250601T081500\r\n"));
+    assert!(calendar.contains("SUMMARY:Pose - Barrières\\, rue du Dôme\r\n"));
+    assert!(calendar.contains("CATEGORIES:POSE\r\n"));
+    // GEO est au format latitude;longitude
+    assert!(calendar.contains("GEO:48.580000;7.750000\r\n"));
+}
+
+#[test]
+fn test_build_calendar_uses_action_revision() {
+    let modified = Utc.with_ymd_and_hms(2025, 5, 20, 9, 30, 0).unwrap();
+    let mut revised = entry("a1");
+    revised.sequence = 3;
+    revised.last_modified = Some(modified);
+
+    let first = build_calendar("Équipe A", &[revised.clone()], Utc::now());
+    assert!(first.contains("SEQUENCE:3\r\n"));
+    assert!(first.contains("LAST-MODIFIED:20250520T093000Z\r\n"));
+    assert!(first.contains("DTSTAMP:20250520T093000Z\r\n"));
+
+    // Un réexport sans modification est identique
+    let stamp = Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap();
+    assert_eq!(build_calendar("Équipe A", &[revised], stamp), first);
+}
+
+#[test]
+fn test_event_content_follows_estimated_end() {
+    let original = entry("a1");
+    let content = event_content_lines(&original).join("\n");
+
+    // Même action, durée estimée recalculée (cadence modifiée ou recalibrée)
+    let mut longer = original.clone();
+    longer.end = parse_datetime("2025-06-01T08:40").unwrap();
+    let longer_content = event_content_lines(&longer).join("\n");
+    assert_ne!(content, longer_content);
+    assert!(longer_content.contains("DTEND:20250601T084000"));
+
+    // La révision ne change que si le contenu change
+    assert_eq!(next_content_revision(&content, 2, &content), 2);
+    assert_eq!(next_content_revision(&content, 2, &longer_content), 3);
+}
+
+#[test]
+fn test_event_content_excludes_revision_fields() {
+    let mut revised = entry("a1");
+    revised.sequence = 4;
+    revised.last_modified = Some(Utc.with_ymd_and_hms(2025, 5, 20, 9, 30, 0).unwrap());
+
+    assert_eq!(
+        event_content_lines(&revised),
+        event_content_lines(&entry("a1"))
+    );
+    assert!(event_content_lines(&revised)
+        .iter()
+        .all(|l| !l.starts_with("SEQUENCE") && !l.starts_with("DTSTAMP")));
+}
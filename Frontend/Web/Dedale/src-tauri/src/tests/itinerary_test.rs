@@ -0,0 +1,244 @@
+use crate::itinerary::{
+    build_itinerary, enforce_precedences, order_tour, precedence_pairs, time_windows,
+    ItineraryInput,
+};
+use crate::scheduling::distance_between;
+use crate::types::ActionDependency;
+use crate::utils::parse_datetime;
+
+/// Longueur en mètres d'un trajet ouvert passant par les points dans l'ordre donné
+fn path_length(start: Option<(f64, f64)>, points: &[(f64, f64)], order: &[usize]) -> f64 {
+    let mut previous = start;
+    let mut total = 0.0;
+    for &index in order {
+        if let Some(from) = previous {
+            total += distance_between(from, points[index]);
+        }
+        previous = Some(points[index]);
+    }
+    total
+}
+
+fn input(id: &str, position: Option<(f64, f64)>) -> ItineraryInput {
+    ItineraryInput {
+        action_id: id.to_string(),
+        equipement_id: format!("eq-{}", id),
+        action_type: Some("pose".to_string()),
+        position,
+        scheduled_time: None,
+        duration_minutes: 10,
+    }
+}
+
+fn planned(
+    id: &str,
+    equipement: &str,
+    action_type: &str,
+    position: (f64, f64),
+    time: &str,
+) -> ItineraryInput {
+    ItineraryInput {
+        action_id: id.to_string(),
+        equipement_id: equipement.to_string(),
+        action_type: Some(action_type.to_string()),
+        position: Some(position),
+        scheduled_time: Some(time.to_string()),
+        duration_minutes: 10,
+    }
+}
+
+fn dependency(action_id: &str, depends_on_id: &str) -> ActionDependency {
+    ActionDependency {
+        id: String::new(),
+        action_id: action_id.to_string(),
+        depends_on_id: depends_on_id.to_string(),
+    }
+}
+
+#[test]
+fn test_order_tour_empty() {
+    assert!(order_tour(Some((7.75, 48.58)), &[], &[]).is_empty());
+}
+
+#[test]
+fn test_order_tour_follows_line_from_start() {
+    // Points alignés sur un axe est-ouest, donnés dans le désordre
+    let points = vec![(7.76, 48.58), (7.78, 48.58), (7.75, 48.58), (7.77, 48.58)];
+    let order = order_tour(Some((7.74, 48.58)), &points, &[]);
+    assert_eq!(order, vec![2, 0, 3, 1]);
+}
+
+#[test]
+fn test_order_tour_without_start_keeps_first_point() {
+    let points = vec![(7.75, 48.58), (7.77, 48.58), (7.76, 48.58)];
+    let order = order_tour(None, &points, &[]);
+    assert_eq!(order[0], 0);
+    assert_eq!(order, vec![0, 2, 1]);
+}
+
+#[test]
+fn test_order_tour_not_longer_than_input_order() {
+    let points = vec![
+        (7.75, 48.58),
+        (7.80, 48.60),
+        (7.76, 48.58),
+        (7.79, 48.61),
+        (7.77, 48.57),
+        (7.81, 48.59),
+    ];
+    let start = Some((7.74, 48.58));
+    let identity: Vec<usize> = (0..points.len()).collect();
+    let order = order_tour(start, &points, &[]);
+
+    let mut sorted = order.clone();
+    sorted.sort();
+    assert_eq!(sorted, identity);
+    assert!(path_length(start, &points, &order) <= path_length(start, &points, &identity));
+}
+
+#[test]
+fn test_build_itinerary_cumulative_distance_and_eta() {
+    let inputs = vec![
+        input("b", Some((7.77, 48.58))),
+        input("a", Some((7.76, 48.58))),
+    ];
+    let start_time = parse_datetime("2025-06-01T08:00");
+    let stops = build_itinerary(Some((7.75, 48.58)), start_time, inputs, &[], 20.0);
+
+    assert_eq!(stops.len(), 2);
+    assert_eq!(stops[0].action_id, "a");
+    assert_eq!(stops[0].order, 1);
+    assert_eq!(stops[1].action_id, "b");
+
+    // ~737 m par étape : 3 minutes à 20 km/h
+    assert!((stops[0].leg_distance_m - 737.0).abs() < 10.0);
+    assert!(
+        (stops[1].cumulative_distance_m - stops[0].leg_distance_m - stops[1].leg_distance_m).abs()
+            < 1e-6
+    );
+    assert_eq!(stops[0].eta.as_deref(), Some("2025-06-01T08:03"));
+    // 3 min de trajet + 10 min d'action + 3 min de trajet
+    assert_eq!(stops[1].eta.as_deref(), Some("2025-06-01T08:16"));
+}
+
+#[test]
+fn test_build_itinerary_unlocated_actions_last() {
+    let inputs = vec![input("x", None), input("a", Some((7.76, 48.58)))];
+    let stops = build_itinerary(None, None, inputs, &[], 20.0);
+
+    assert_eq!(stops[0].action_id, "a");
+    assert_eq!(stops[1].action_id, "x");
+    assert_eq!(stops[1].leg_distance_m, 0.0);
+    assert!(stops[1].eta.is_none());
+}
+
+// ============== Tests pour les créneaux et contraintes ==============
+
+#[test]
+fn test_order_tour_respects_precedence() {
+    // Le point le plus proche (0) ne peut être visité qu'après le point 1
+    let points = vec![(7.75, 48.58), (7.78, 48.58)];
+    let order = order_tour(Some((7.74, 48.58)), &points, &[(1, 0)]);
+    assert_eq!(order, vec![1, 0]);
+}
+
+#[test]
+fn test_time_windows_groups_by_hour() {
+    let times: Vec<_> = [
+        Some("2025-06-01T08:00"),
+        Some("2025-06-01T18:00"),
+        None,
+        Some("2025-06-01T08:45"),
+        Some("2025-06-01T09:10"),
+    ]
+    .iter()
+    .map(|t| t.and_then(parse_datetime))
+    .collect();
+
+    assert_eq!(
+        time_windows(&times),
+        vec![vec![0, 3], vec![4], vec![1], vec![2]]
+    );
+}
+
+#[test]
+fn test_precedence_pairs_pose_before_retrait_and_dependencies() {
+    let inputs = vec![
+        planned("d", "eq1", "retrait", (7.75, 48.58), "2025-06-01T18:00"),
+        planned("p", "eq1", "pose", (7.75, 48.58), "2025-06-01T08:00"),
+        planned("x", "eq2", "pose", (7.76, 48.58), "2025-06-01T08:00"),
+    ];
+    let pairs = precedence_pairs(
+        &inputs,
+        &[dependency("x", "p"), dependency("x", "ailleurs")],
+    );
+    assert_eq!(pairs, vec![(1, 0), (1, 2)]);
+}
+
+#[test]
+fn test_enforce_precedences_moves_minimum() {
+    assert_eq!(
+        enforce_precedences(&[0, 1, 2, 3], &[(2, 1)]),
+        vec![0, 2, 1, 3]
+    );
+    // Cycle : l'ordre reçu est conservé
+    assert_eq!(enforce_precedences(&[0, 1], &[(0, 1), (1, 0)]), vec![0, 1]);
+}
+
+#[test]
+fn test_build_itinerary_keeps_windows_apart() {
+    // Dépose du soir tout près du départ, poses du matin plus loin
+    let inputs = vec![
+        planned(
+            "depose",
+            "eq1",
+            "depose",
+            (7.751, 48.58),
+            "2025-06-01T18:00",
+        ),
+        planned("pose1", "eq1", "pose", (7.751, 48.58), "2025-06-01T08:00"),
+        planned("pose2", "eq2", "pose", (7.80, 48.58), "2025-06-01T08:00"),
+    ];
+    let stops = build_itinerary(Some((7.75, 48.58)), None, inputs, &[], 20.0);
+
+    let ids: Vec<&str> = stops.iter().map(|s| s.action_id.as_str()).collect();
+    assert_eq!(ids, vec!["pose1", "pose2", "depose"]);
+    // Le chronomètre repart de l'horaire planifié du créneau
+    assert_eq!(stops[0].eta.as_deref(), Some("2025-06-01T08:01"));
+    assert!(stops[2].eta.as_deref().unwrap() >= "2025-06-01T18:00");
+}
+
+#[test]
+fn test_build_itinerary_depose_never_before_pose() {
+    // Dépose planifiée par erreur avant la pose du même équipement
+    let inputs = vec![
+        planned(
+            "depose",
+            "eq1",
+            "retrait",
+            (7.76, 48.58),
+            "2025-06-01T07:00",
+        ),
+        planned("pose", "eq1", "pose", (7.76, 48.58), "2025-06-01T09:00"),
+    ];
+    let stops = build_itinerary(None, None, inputs, &[], 20.0);
+    assert_eq!(stops[0].action_id, "pose");
+    assert_eq!(stops[1].action_id, "depose");
+}
+
+#[test]
+fn test_build_itinerary_follows_dependencies() {
+    let inputs = vec![
+        planned("near", "eq1", "pose", (7.751, 48.58), "2025-06-01T08:00"),
+        planned("far", "eq2", "pose", (7.80, 48.58), "2025-06-01T08:00"),
+    ];
+    let stops = build_itinerary(
+        Some((7.75, 48.58)),
+        None,
+        inputs,
+        &[dependency("near", "far")],
+        20.0,
+    );
+    assert_eq!(stops[0].action_id, "far");
+    assert_eq!(stops[1].action_id, "near");
+}
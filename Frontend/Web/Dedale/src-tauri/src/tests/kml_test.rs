@@ -0,0 +1,130 @@
+use crate::geojson::{GeoFeature, GeoGeometry};
+use crate::kml::{build_kml, build_kmz, decode_picture, kml_color, KmzPicture};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+
+fn feature(
+    layer: &str,
+    id: &str,
+    name: &str,
+    geometry: GeoGeometry,
+    properties: Value,
+) -> GeoFeature {
+    GeoFeature {
+        layer: layer.to_string(),
+        id: id.to_string(),
+        name: Some(name.to_string()),
+        geometry,
+        properties: properties.as_object().cloned().unwrap_or_else(Map::new),
+    }
+}
+
+// ============== Tests pour kml_color ==============
+
+#[test]
+fn test_kml_color_reverses_channels() {
+    assert_eq!(kml_color("#ff8000", 0xff), "ff0080ff");
+    assert_eq!(kml_color("#3b82f6", 0x66), "66f6823b");
+    assert_eq!(kml_color("#f00", 0xff), "ff0000ff");
+}
+
+#[test]
+fn test_kml_color_invalid_falls_back() {
+    assert_eq!(kml_color("rouge", 0xff), kml_color("#ef4444", 0xff));
+}
+
+// ============== Tests pour decode_picture ==============
+
+#[test]
+fn test_decode_picture_detects_format() {
+    // En-têtes PNG et JPEG minimaux encodés en base64
+    let (bytes, extension) = decode_picture("data:image/png;base64,iVBORw0KGgo=").unwrap();
+    assert_eq!(extension, "png");
+    assert_eq!(bytes.len(), 8);
+
+    let (_, extension) = decode_picture("/9j/4AAQ").unwrap();
+    assert_eq!(extension, "jpg");
+
+    assert!(decode_picture("pas une image").is_none());
+    assert!(decode_picture("aGVsbG8=").is_none());
+}
+
+// ============== Tests pour build_kml ==============
+
+#[test]
+fn test_build_kml_layers_and_styles() {
+    let features = vec![
+        feature(
+            "parcours",
+            "p1",
+            "10 km",
+            GeoGeometry::LineString(vec![(2.35, 48.85), (2.36, 48.86)]),
+            json!({ "color": "#00ff00" }),
+        ),
+        feature(
+            "zone",
+            "z1",
+            "Village",
+            GeoGeometry::Polygon(vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)]),
+            json!({ "color": "#0000ff" }),
+        ),
+        feature(
+            "point",
+            "pt1",
+            "Carrefour",
+            GeoGeometry::Point((2.35, 48.85)),
+            json!({ "comment": "Signaleur & barrière", "type": "danger" }),
+        ),
+        feature(
+            "equipement",
+            "e1",
+            "Vauban",
+            GeoGeometry::LineString(vec![(0.0, 0.0), (0.1, 0.1)]),
+            json!({}),
+        ),
+        feature(
+            "equipement",
+            "e2",
+            "Vauban",
+            GeoGeometry::Point((0.2, 0.2)),
+            json!({}),
+        ),
+    ];
+    let pictures = HashMap::from([("pt1".to_string(), vec!["files/pt1_1.jpg".to_string()])]);
+
+    let kml = build_kml("Marathon", &features, &pictures);
+
+    assert!(kml.contains("<name>Marathon</name>"));
+    assert!(kml.contains("<color>ff00ff00</color>"));
+    assert!(kml.contains("<PolyStyle><color>66ff0000</color></PolyStyle>"));
+    assert!(kml.contains("0,0,0 1,0,0 1,1,0 0,0,0"));
+    assert!(kml.contains("Signaleur &amp; barrière"));
+    assert!(kml.contains("<img src=\"files/pt1_1.jpg\""));
+    assert_eq!(kml.matches("<Folder><name>Vauban</name>").count(), 1);
+    assert!(!kml.contains("Points d'intérêt"));
+    assert!(roxmltree::Document::parse(&kml).is_ok());
+}
+
+// ============== Tests pour build_kmz ==============
+
+#[test]
+fn test_build_kmz_archive() {
+    let pictures = vec![KmzPicture {
+        path: "files/pt1_1.jpg".to_string(),
+        bytes: vec![0xFF, 0xD8, 0xFF],
+    }];
+    let bytes = build_kmz("<kml/>", &pictures).unwrap();
+
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+    assert_eq!(archive.len(), 2);
+
+    let mut kml = String::new();
+    archive
+        .by_index(0)
+        .unwrap()
+        .read_to_string(&mut kml)
+        .unwrap();
+    assert_eq!(kml, "<kml/>");
+    assert_eq!(archive.by_index(1).unwrap().name(), "files/pt1_1.jpg");
+}
@@ -1,8 +1,9 @@
 use crate::map::{
     bbox_intersects, bearing_to_cardinal, calculate_bbox, calculate_bbox_area, calculate_bearing,
-    calculate_center, calculate_distance, decimal_to_dms, degrees_to_meters, format_coordinates,
-    is_valid_coordinate, meters_to_degrees, midpoint, normalize_longitude, point_in_bbox,
-    round_coordinate,
+    calculate_center, calculate_distance, close_ring, decimal_to_dms, degrees_to_meters,
+    destination_point, format_coordinates, format_linestring_wkt, format_point_wkt,
+    format_polygon_wkt, is_valid_coordinate, meters_to_degrees, midpoint, normalize_longitude,
+    parse_linestring_wkt, parse_polygon_wkt, point_in_bbox, point_in_ring, round_coordinate,
 };
 
 // ============== Tests pour decimal_to_dms ==============
@@ -276,6 +277,22 @@ fn test_calculate_bearing_south() {
     assert!((bearing - 180.0).abs() < 1.0);
 }
 
+// ============== Tests pour destination_point ==============
+
+#[test]
+fn test_destination_point_roundtrip() {
+    let (lat, lon) = destination_point(48.85, 2.35, 90.0, 1000.0);
+    assert!((calculate_distance(48.85, 2.35, lat, lon) - 1000.0).abs() < 0.5);
+    assert!((calculate_bearing(48.85, 2.35, lat, lon) - 90.0).abs() < 0.1);
+}
+
+#[test]
+fn test_destination_point_zero_distance() {
+    let (lat, lon) = destination_point(48.85, 2.35, 45.0, 0.0);
+    assert!((lat - 48.85).abs() < 1e-9);
+    assert!((lon - 2.35).abs() < 1e-9);
+}
+
 // ============== Tests pour bearing_to_cardinal ==============
 
 #[test]
@@ -371,3 +388,54 @@ fn test_round_coordinate_zero_decimals() {
     let rounded = round_coordinate(1.6, 0);
     assert!((rounded - 2.0).abs() < 0.001);
 }
+
+// ============== Tests pour parse_linestring_wkt / parse_polygon_wkt ==============
+
+#[test]
+fn test_parse_linestring_wkt() {
+    let coords = parse_linestring_wkt("LINESTRING(7.75 48.58, 7.76 48.59)");
+    assert_eq!(coords, vec![(7.75, 48.58), (7.76, 48.59)]);
+}
+
+#[test]
+fn test_parse_linestring_wkt_wrong_type() {
+    assert!(parse_linestring_wkt("POINT(7.75 48.58)").is_empty());
+}
+
+#[test]
+fn test_parse_polygon_wkt_keeps_first_point() {
+    let coords = parse_polygon_wkt("POLYGON((0 0, 1 0, 1 1, 0 0))");
+    assert_eq!(coords, vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)]);
+}
+
+// ============== Tests pour format_*_wkt et point_in_ring ==============
+
+#[test]
+fn test_format_linestring_wkt_roundtrip() {
+    let coords = vec![(2.35, 48.85), (2.36, 48.86)];
+    let wkt = format_linestring_wkt(&coords);
+    assert_eq!(wkt, "LINESTRING(2.35 48.85, 2.36 48.86)");
+    assert_eq!(parse_linestring_wkt(&wkt), coords);
+}
+
+#[test]
+fn test_format_point_wkt() {
+    assert_eq!(format_point_wkt(2.35, 48.85), "POINT(2.35 48.85)");
+}
+
+#[test]
+fn test_point_in_ring() {
+    let square = vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+    assert!(point_in_ring((0.5, 0.5), &square));
+    assert!(!point_in_ring((1.5, 0.5), &square));
+    assert!(!point_in_ring((0.5, -0.1), &square));
+}
+
+#[test]
+fn test_format_polygon_wkt_closes_ring() {
+    let ring = vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)];
+    assert_eq!(close_ring(&ring).len(), 4);
+    let wkt = format_polygon_wkt(&ring);
+    assert_eq!(wkt, "POLYGON((0 0, 1 0, 1 1, 0 0))");
+    assert_eq!(parse_polygon_wkt(&wkt), close_ring(&ring));
+}
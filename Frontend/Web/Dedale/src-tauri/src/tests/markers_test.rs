@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use crate::map::calculate_distance;
+    use crate::markers::{km_markers, marker_description, marker_label, parcours_length};
+
+    // ~2,22 km vers le nord le long du méridien de Greenwich
+    fn north_line() -> Vec<(f64, f64)> {
+        vec![(0.0, 0.0), (0.0, 0.01), (0.0, 0.02)]
+    }
+
+    #[test]
+    fn test_parcours_length_is_geodesic() {
+        let length = parcours_length(&north_line());
+        assert!((length - 2223.9).abs() < 1.0);
+        assert_eq!(parcours_length(&[(0.0, 0.0)]), 0.0);
+        assert_eq!(parcours_length(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_marker_label() {
+        assert_eq!(marker_label(1000.0), "Km 1");
+        assert_eq!(marker_label(5500.0), "Km 5,5");
+        assert_eq!(marker_label(21000.0), "Km 21");
+    }
+
+    #[test]
+    fn test_km_markers_every_kilometre() {
+        let markers = km_markers(&north_line(), false, 0.0);
+
+        let labels: Vec<&str> = markers.iter().map(|m| m.label.as_str()).collect();
+        assert_eq!(labels, vec!["Km 1", "Km 2"]);
+        for marker in &markers {
+            let from_start = calculate_distance(0.0, 0.0, marker.y, marker.x);
+            assert!((from_start - marker.distance_m).abs() < 0.5);
+            assert!(marker.bearing.abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_km_markers_half_km() {
+        let markers = km_markers(&north_line(), true, 0.0);
+        let distances: Vec<f64> = markers.iter().map(|m| m.distance_m).collect();
+        assert_eq!(distances, vec![500.0, 1000.0, 1500.0, 2000.0]);
+    }
+
+    #[test]
+    fn test_km_markers_offset_to_the_right() {
+        // En allant vers le nord, la droite est à l'est
+        let markers = km_markers(&north_line(), false, 3.0);
+        let marker = &markers[0];
+        assert!(marker.x > 0.0);
+        let offset = calculate_distance(marker.y, 0.0, marker.y, marker.x);
+        assert!((offset - 3.0).abs() < 0.01);
+
+        // Vers l'ouest, la droite est au nord
+        let west = vec![(0.0, 0.0), (-0.02, 0.0)];
+        assert!(km_markers(&west, false, 3.0)[0].y > 0.0);
+    }
+
+    #[test]
+    fn test_km_markers_short_line() {
+        assert!(km_markers(&[(0.0, 0.0), (0.0, 0.001)], false, 3.0).is_empty());
+        assert!(km_markers(&[], true, 3.0).is_empty());
+    }
+
+    #[test]
+    fn test_marker_description_includes_direction() {
+        let markers = km_markers(&north_line(), false, 0.0);
+        assert_eq!(
+            marker_description(&markers[0], Some("10 km")),
+            "Km 1 – 10 km (sens N)"
+        );
+    }
+}
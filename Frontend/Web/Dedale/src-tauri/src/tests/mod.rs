@@ -1,7 +1,31 @@
 // Tests désactivés temporairement - fonctions helper non implémentées
+mod action_status_test;
+mod closures_test;
+mod conflicts_test;
+mod crossings_test;
 // mod db_test;
+mod dependencies_test;
+mod depots_test;
+mod durations_test;
 // mod excel_test;
+mod gantt_test;
+mod geojson_test;
+mod geopackage_test;
+mod gpx_test;
+mod ical_test;
+mod itinerary_test;
+mod kml_test;
 mod map_test;
+mod markers_test;
 // mod pdf_test;
+mod planning_excel_test;
+mod planning_pdf_test;
+mod progress_test;
+mod projection_test;
+mod race_test;
+mod scheduling_test;
+mod snapping_test;
 mod socket_test;
 mod utils_test;
+mod waves_test;
+mod zone_conflicts_test;
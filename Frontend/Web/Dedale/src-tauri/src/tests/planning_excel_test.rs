@@ -0,0 +1,246 @@
+#[cfg(test)]
+mod tests {
+    use crate::planning_excel::{
+        build_planning_workbook, column_index, diff_planning_rows, excel_serial_to_datetime,
+        import_change_is_stale, parse_import_time, read_xlsx_rows, sheet_to_imported_rows,
+        CurrentAction, ImportedRow, PlanningImportChange, PlanningSheetRow, XlsxCell, XlsxRow,
+    };
+    use crate::utils::format_datetime;
+    use std::collections::HashMap;
+
+    fn sheet_row(id: &str, team: &str, time: Option<&str>) -> PlanningSheetRow {
+        PlanningSheetRow {
+            action_id: id.to_string(),
+            team_name: team.to_string(),
+            action_type: "pose".to_string(),
+            type_name: "Barrière".to_string(),
+            description: "Carrefour [nord]".to_string(),
+            scheduled_time: time.map(|t| t.to_string()),
+            status: "Planifiée".to_string(),
+            first_passage: Some("2025-06-01T09:00".to_string()),
+            last_passage: None,
+        }
+    }
+
+    fn current(id: &str, team_id: &str, team: &str, time: Option<&str>) -> CurrentAction {
+        CurrentAction {
+            action_id: id.to_string(),
+            label: "pose Barrière".to_string(),
+            team_id: team_id.to_string(),
+            team_name: Some(team.to_string()),
+            scheduled_time: time.map(|t| t.to_string()),
+        }
+    }
+
+    fn imported(row: u32, id: &str, team: &str, time: Option<&str>) -> ImportedRow {
+        ImportedRow {
+            row,
+            action_id: id.to_string(),
+            team_name: team.to_string(),
+            scheduled_time: time.map(|t| t.to_string()),
+        }
+    }
+
+    fn teams() -> Vec<(String, Option<String>)> {
+        vec![
+            ("t1".to_string(), Some("Équipe A".to_string())),
+            ("t2".to_string(), Some("Équipe B".to_string())),
+        ]
+    }
+
+    fn actions() -> HashMap<String, CurrentAction> {
+        [
+            current("a1", "t1", "Équipe A", Some("2025-06-01T08:00")),
+            current("a2", "t1", "Équipe A", Some("2025-06-01T09:00:00")),
+            current("a3", "t2", "Équipe B", None),
+        ]
+        .into_iter()
+        .map(|a| (a.action_id.clone(), a))
+        .collect()
+    }
+
+    #[test]
+    fn test_column_index() {
+        assert_eq!(column_index("A1"), Some(0));
+        assert_eq!(column_index("F12"), Some(5));
+        assert_eq!(column_index("AA3"), Some(26));
+        assert_eq!(column_index("12"), None);
+    }
+
+    #[test]
+    fn test_excel_serial_to_datetime() {
+        // 45809 = 01/06/2025, 0.34375 = 08:15
+        let dt = excel_serial_to_datetime(45809.34375).unwrap();
+        assert_eq!(format_datetime(&dt), "2025-06-01T08:15");
+        assert!(excel_serial_to_datetime(-1.0).is_none());
+    }
+
+    #[test]
+    fn test_parse_import_time_accepts_french_format() {
+        let dt = parse_import_time("01/06/2025 08:30").unwrap();
+        assert_eq!(format_datetime(&dt), "2025-06-01T08:30");
+        assert!(parse_import_time("2025-06-01T08:30").is_some());
+        assert!(parse_import_time("demain").is_none());
+    }
+
+    #[test]
+    fn test_export_then_read_roundtrip() {
+        let rows = vec![
+            sheet_row("a1", "Équipe A", Some("2025-06-01T08:00")),
+            sheet_row("a3", "Équipe B", None),
+        ];
+        let mut workbook = build_planning_workbook(&rows).unwrap();
+        let bytes = workbook.save_to_buffer().unwrap();
+
+        let read = sheet_to_imported_rows(&read_xlsx_rows(&bytes).unwrap()).unwrap();
+        assert_eq!(
+            read,
+            vec![
+                imported(2, "a1", "Équipe A", Some("2025-06-01T08:00")),
+                imported(3, "a3", "Équipe B", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sheet_rows_convert_excel_dates_and_skip_empty_ids() {
+        let text = |s: &str| XlsxCell::Text(s.to_string());
+        let rows = vec![
+            XlsxRow {
+                number: 1,
+                cells: vec![text("ID action"), text("Équipe"), text("Heure prévue")],
+            },
+            XlsxRow {
+                number: 2,
+                cells: vec![text("a1"), text("Équipe B"), XlsxCell::Number(45809.5)],
+            },
+            XlsxRow {
+                number: 3,
+                cells: vec![XlsxCell::Empty, text("Équipe B")],
+            },
+        ];
+
+        let read = sheet_to_imported_rows(&rows).unwrap();
+        assert_eq!(
+            read,
+            vec![imported(2, "a1", "Équipe B", Some("2025-06-01T12:00"))]
+        );
+    }
+
+    #[test]
+    fn test_sheet_rows_require_id_column() {
+        let rows = vec![XlsxRow {
+            number: 1,
+            cells: vec![XlsxCell::Text("Équipe".to_string())],
+        }];
+        assert!(sheet_to_imported_rows(&rows).is_err());
+    }
+
+    #[test]
+    fn test_diff_detects_team_and_time_changes() {
+        let rows = vec![
+            // Identique (l'équipe est reconnue sans tenir compte de la casse)
+            imported(2, "a1", "équipe a", Some("2025-06-01T08:00")),
+            // Identique, secondes en base
+            imported(3, "a2", "Équipe A", Some("01/06/2025 09:00")),
+            // Change d'équipe et reçoit un horaire
+            imported(4, "a3", "Équipe A", Some("2025-06-02T07:30")),
+        ];
+
+        let preview = diff_planning_rows("e1", &rows, &actions(), &teams());
+
+        assert!(preview.errors.is_empty());
+        assert_eq!(preview.unchanged, 2);
+        assert_eq!(preview.changes.len(), 1);
+        let change = &preview.changes[0];
+        assert_eq!(change.action_id, "a3");
+        assert_eq!(change.previous_team_id, "t2");
+        assert_eq!(change.team_id, "t1");
+        assert_eq!(change.previous_scheduled_time, None);
+        assert_eq!(change.scheduled_time.as_deref(), Some("2025-06-02T07:30"));
+    }
+
+    #[test]
+    fn test_diff_reports_invalid_rows() {
+        let rows = vec![
+            imported(2, "inconnue", "Équipe A", None),
+            imported(3, "a1", "Équipe Z", None),
+            imported(4, "a2", "Équipe A", Some("vers 9h")),
+            imported(5, "a3", "", None),
+            imported(6, "a1", "Équipe A", None),
+        ];
+
+        let preview = diff_planning_rows("e1", &rows, &actions(), &teams());
+
+        assert!(preview.changes.is_empty());
+        let rows_in_error: Vec<u32> = preview.errors.iter().map(|e| e.row).collect();
+        assert_eq!(rows_in_error, vec![2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_diff_rejects_ambiguous_team_name() {
+        let mut teams = teams();
+        teams.push(("t3".to_string(), Some("Équipe A".to_string())));
+
+        let rows = vec![imported(2, "a3", "Équipe A", None)];
+        let preview = diff_planning_rows("e1", &rows, &actions(), &teams);
+
+        assert_eq!(preview.errors.len(), 1);
+    }
+
+    // ============== Tests pour import_change_is_stale ==============
+
+    fn import_change(previous_team: &str, previous_time: Option<&str>) -> PlanningImportChange {
+        PlanningImportChange {
+            row: 2,
+            action_id: "a1".to_string(),
+            label: "pose Barrière".to_string(),
+            previous_team_id: previous_team.to_string(),
+            previous_team_name: None,
+            team_id: "t2".to_string(),
+            team_name: None,
+            previous_scheduled_time: previous_time.map(str::to_string),
+            scheduled_time: Some("2025-06-01T10:00".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_change_not_stale_when_row_unchanged() {
+        let change = import_change("t1", Some("2025-06-01T08:00"));
+        assert!(!import_change_is_stale(
+            &change,
+            Some("t1"),
+            Some("2025-06-01T08:00:00")
+        ));
+
+        let unscheduled = import_change("t1", None);
+        assert!(!import_change_is_stale(&unscheduled, Some("t1"), None));
+        assert!(!import_change_is_stale(&unscheduled, Some("t1"), Some("")));
+    }
+
+    #[test]
+    fn test_change_stale_when_row_modified_since_preview() {
+        let change = import_change("t1", Some("2025-06-01T08:00"));
+        // Réaffectée à une autre équipe
+        assert!(import_change_is_stale(
+            &change,
+            Some("t3"),
+            Some("2025-06-01T08:00")
+        ));
+        // Replanifiée
+        assert!(import_change_is_stale(
+            &change,
+            Some("t1"),
+            Some("2025-06-01T09:00")
+        ));
+        // Déplanifiée
+        assert!(import_change_is_stale(&change, Some("t1"), None));
+        // Planifiée alors qu'elle ne l'était pas à l'aperçu
+        let unscheduled = import_change("t1", None);
+        assert!(import_change_is_stale(
+            &unscheduled,
+            Some("t1"),
+            Some("2025-06-01T08:00")
+        ));
+    }
+}
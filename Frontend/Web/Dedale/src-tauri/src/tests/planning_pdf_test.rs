@@ -0,0 +1,52 @@
+use crate::pdf::{action_type_cell, planning_summary_table, PlanningTeamSummary};
+
+#[test]
+fn test_action_type_cell_uses_export_labels() {
+    assert_eq!(
+        action_type_cell(Some("pose")),
+        r#"#text(fill: green, weight: "bold")[POSE]"#
+    );
+    // Les déposes enregistrées par l'interface web ("retrait") portent le même libellé
+    // que dans l'iCalendar, le Gantt et l'Excel
+    assert_eq!(
+        action_type_cell(Some("retrait")),
+        r#"#text(fill: red, weight: "bold")[DÉPOSE]"#
+    );
+    assert_eq!(action_type_cell(Some("depose")), action_type_cell(None));
+}
+
+#[test]
+fn test_planning_summary_table_source() {
+    let summary = vec![
+        PlanningTeamSummary {
+            team_name: "Équipe #1".to_string(),
+            member_count: 3,
+            action_count: 12,
+            distance_m: 8400.0,
+            first: Some("2025-06-01T07:30".to_string()),
+            last: Some("2025-06-01T18:45".to_string()),
+        },
+        PlanningTeamSummary {
+            team_name: "Réserve".to_string(),
+            member_count: 2,
+            action_count: 0,
+            distance_m: 0.0,
+            first: None,
+            last: None,
+        },
+    ];
+
+    let typst_src = planning_summary_table(&summary);
+
+    assert!(typst_src.starts_with("== Récapitulatif"));
+    assert!(
+        typst_src.contains("[*Équipe*], [*Membres*], [*Actions*], [*Trajet*], [*Début*], [*Fin*],")
+    );
+    assert!(typst_src.contains(
+        "[Équipe \\#1], [3], [12], [8.4 km], [01/06/2025 à 07:30], [01/06/2025 à 18:45],\n"
+    ));
+    assert!(typst_src.contains("[Réserve], [2], [0], [0.0 km], [-], [-],\n"));
+    // Le tableau est refermé
+    assert!(typst_src.ends_with(")\n"));
+    assert_eq!(typst_src.matches("#table(").count(), 1);
+}
@@ -0,0 +1,90 @@
+use crate::progress::{compute_progress, is_overdue, ProgressInput};
+use crate::types::ActionStatus;
+use crate::utils::parse_datetime;
+
+fn input(
+    id: &str,
+    team: &str,
+    action_type: &str,
+    status: ActionStatus,
+    time: &str,
+) -> ProgressInput {
+    ProgressInput {
+        action_id: id.to_string(),
+        team_id: team.to_string(),
+        team_name: Some(format!("Équipe {}", team)),
+        action_type: action_type.to_string(),
+        status,
+        scheduled_time: parse_datetime(time),
+        length_m: 20.0,
+    }
+}
+
+#[test]
+fn test_is_overdue() {
+    let now = parse_datetime("2025-06-01T10:00").unwrap();
+    let before = parse_datetime("2025-06-01T09:00");
+    let after = parse_datetime("2025-06-01T11:00");
+
+    assert!(is_overdue(ActionStatus::Planned, before, now));
+    assert!(is_overdue(ActionStatus::Blocked, before, now));
+    assert!(!is_overdue(ActionStatus::Planned, after, now));
+    assert!(!is_overdue(ActionStatus::InProgress, before, now));
+    assert!(!is_overdue(ActionStatus::Done, before, now));
+    assert!(!is_overdue(ActionStatus::Planned, None, now));
+}
+
+#[test]
+fn test_compute_progress_counts_and_lengths() {
+    let now = parse_datetime("2025-06-01T10:00").unwrap();
+    let inputs = vec![
+        input("a", "t1", "pose", ActionStatus::Done, "2025-06-01T08:00"),
+        input("b", "t1", "pose", ActionStatus::Planned, "2025-06-01T09:00"),
+        input("c", "t2", "pose", ActionStatus::Planned, "2025-06-01T12:00"),
+        input(
+            "d",
+            "t2",
+            "depose",
+            ActionStatus::Planned,
+            "2025-06-01T18:00",
+        ),
+        input(
+            "e",
+            "t2",
+            "pose",
+            ActionStatus::Cancelled,
+            "2025-06-01T07:00",
+        ),
+    ];
+
+    let progress = compute_progress("ev", &inputs, now);
+
+    assert_eq!(progress.pose.planned, 3);
+    assert_eq!(progress.pose.done, 1);
+    assert_eq!(progress.pose.late, 1);
+    assert!((progress.pose.planned_length_m - 60.0).abs() < 1e-9);
+    assert!((progress.pose.done_length_m - 20.0).abs() < 1e-9);
+    assert_eq!(progress.depose.planned, 1);
+    assert_eq!(progress.depose.done, 0);
+
+    assert_eq!(progress.teams.len(), 2);
+    assert_eq!(progress.teams[0].team_id, "t1");
+    assert_eq!(progress.teams[0].pose.planned, 2);
+    assert_eq!(progress.teams[1].pose.planned, 1);
+}
+
+#[test]
+fn test_overdue_sorted_by_delay() {
+    let now = parse_datetime("2025-06-01T10:00").unwrap();
+    let inputs = vec![
+        input("a", "t1", "pose", ActionStatus::Planned, "2025-06-01T09:30"),
+        input("b", "t1", "pose", ActionStatus::EnRoute, "2025-06-01T08:00"),
+    ];
+
+    let progress = compute_progress("ev", &inputs, now);
+
+    assert_eq!(progress.overdue.len(), 2);
+    assert_eq!(progress.overdue[0].action_id, "b");
+    assert_eq!(progress.overdue[0].late_minutes, 120);
+    assert_eq!(progress.overdue[1].late_minutes, 30);
+}
@@ -0,0 +1,117 @@
+use crate::projection::{coordinate_display, format_meters, format_projected, Crs};
+
+fn assert_close(actual: (f64, f64), expected: (f64, f64), tolerance: f64) {
+    assert!(
+        (actual.0 - expected.0).abs() < tolerance && (actual.1 - expected.1).abs() < tolerance,
+        "{:?} != {:?}",
+        actual,
+        expected
+    );
+}
+
+// ============== Tests pour project / unproject ==============
+
+#[test]
+fn test_lambert93_origin() {
+    assert_close(
+        Crs::Lambert93.project((3.0, 46.5)),
+        (700_000.0, 6_600_000.0),
+        1e-3,
+    );
+}
+
+#[test]
+fn test_cc48_origin() {
+    assert_close(
+        Crs::Cc48.project((3.0, 48.0)),
+        (1_700_000.0, 7_200_000.0),
+        1e-3,
+    );
+}
+
+#[test]
+fn test_wgs84_is_identity() {
+    assert_eq!(Crs::Wgs84.project((7.75, 48.58)), (7.75, 48.58));
+    assert_eq!(Crs::Wgs84.unproject((7.75, 48.58)), (7.75, 48.58));
+}
+
+#[test]
+fn test_roundtrip_strasbourg() {
+    for crs in [Crs::Lambert93, Crs::Cc48] {
+        let projected = crs.project((7.75, 48.58));
+        assert_close(crs.unproject(projected), (7.75, 48.58), 1e-8);
+    }
+}
+
+#[test]
+fn test_east_of_meridian_increases_x() {
+    let (x_west, _) = Crs::Cc48.project((7.70, 48.58));
+    let (x_east, _) = Crs::Cc48.project((7.80, 48.58));
+    // Environ 7,4 km pour 0,1° de longitude à cette latitude
+    assert!(x_east > x_west);
+    assert!((x_east - x_west - 7_380.0).abs() < 100.0);
+}
+
+// ============== Tests pour from_code / parse ==============
+
+#[test]
+fn test_from_code_variants() {
+    assert_eq!(Crs::from_code("EPSG:2154"), Some(Crs::Lambert93));
+    assert_eq!(Crs::from_code("2154"), Some(Crs::Lambert93));
+    assert_eq!(Crs::from_code("Lambert-93"), Some(Crs::Lambert93));
+    assert_eq!(
+        Crs::from_code("urn:ogc:def:crs:EPSG::3948"),
+        Some(Crs::Cc48)
+    );
+    assert_eq!(Crs::from_code("CC48"), Some(Crs::Cc48));
+    assert_eq!(
+        Crs::from_code("urn:ogc:def:crs:OGC:1.3:CRS84"),
+        Some(Crs::Wgs84)
+    );
+    assert_eq!(Crs::from_code("EPSG:27572"), None);
+}
+
+#[test]
+fn test_parse_defaults_and_errors() {
+    assert_eq!(Crs::parse(None).unwrap(), Crs::Wgs84);
+    assert_eq!(Crs::parse(Some("  ")).unwrap(), Crs::Wgs84);
+    assert_eq!(Crs::parse(Some("3948")).unwrap(), Crs::Cc48);
+    assert!(Crs::parse(Some("mercator"))
+        .unwrap_err()
+        .contains("inconnu"));
+}
+
+#[test]
+fn test_urn() {
+    assert_eq!(Crs::Lambert93.urn(), "urn:ogc:def:crs:EPSG::2154");
+}
+
+// ============== Tests pour l'affichage ==============
+
+#[test]
+fn test_format_meters() {
+    assert_eq!(format_meters(1_050_512.344), "1 050 512,34");
+    assert_eq!(format_meters(512.0), "512,00");
+    assert_eq!(format_meters(-1234.5), "-1 234,50");
+}
+
+#[test]
+fn test_format_projected() {
+    assert_eq!(
+        format_projected(Crs::Lambert93, 3.0, 46.5),
+        "X 700 000,00 m  Y 6 600 000,00 m"
+    );
+}
+
+#[test]
+fn test_coordinate_display() {
+    let wgs84 = coordinate_display(Crs::Wgs84, 7.75, 48.58);
+    assert_eq!(wgs84.epsg, 4326);
+    assert!(wgs84.projected.is_none());
+    assert!(!wgs84.dms.is_empty());
+
+    let cc48 = coordinate_display(Crs::Cc48, 7.75, 48.58);
+    assert_eq!(cc48.epsg, 3948);
+    assert_eq!(cc48.dms, wgs84.dms);
+    assert!(cc48.projected.unwrap().starts_with("X 2 0"));
+}
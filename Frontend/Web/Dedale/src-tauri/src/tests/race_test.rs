@@ -0,0 +1,268 @@
+use crate::race::{
+    check_race_window, cumulative_lengths, estimate_passage, interpolate_at_chainage,
+    locate_on_line, occupancy_window, passage_time, passage_time_on_parcours, passage_window,
+    passages_by_target, point_at_chainage, race_waves, PassageWindow, RaceParcours, RaceWave,
+};
+use crate::utils::parse_datetime;
+
+/// Parcours est-ouest d'environ 1474 m (deux segments de ~737 m)
+fn line() -> Vec<(f64, f64)> {
+    vec![(7.75, 48.58), (7.76, 48.58), (7.77, 48.58)]
+}
+
+fn window(first: &str, last: &str) -> PassageWindow {
+    PassageWindow {
+        first_passage: parse_datetime(first).unwrap(),
+        last_passage: parse_datetime(last).unwrap(),
+    }
+}
+
+#[test]
+fn test_cumulative_lengths() {
+    let lengths = cumulative_lengths(&line());
+    assert_eq!(lengths.len(), 3);
+    assert_eq!(lengths[0], 0.0);
+    assert!((lengths[1] - 737.0).abs() < 5.0);
+    assert!((lengths[2] - 2.0 * lengths[1]).abs() < 1.0);
+}
+
+#[test]
+fn test_locate_on_line_projects_point() {
+    // Point au milieu du second segment, décalé d'environ 11 m au nord
+    let position = locate_on_line(&line(), (7.765, 48.5801)).unwrap();
+    assert!((position.chainage_m - 1105.0).abs() < 5.0);
+    assert!((position.offset_m - 11.1).abs() < 1.0);
+}
+
+#[test]
+fn test_locate_on_line_clamps_to_ends() {
+    let position = locate_on_line(&line(), (7.74, 48.58)).unwrap();
+    assert_eq!(position.chainage_m, 0.0);
+    assert!(position.offset_m > 700.0);
+}
+
+#[test]
+fn test_locate_on_empty_line() {
+    assert!(locate_on_line(&[], (7.75, 48.58)).is_none());
+}
+
+#[test]
+fn test_point_at_chainage_inverts_locate() {
+    let line = line();
+    let point = point_at_chainage(&line, 1000.0).unwrap();
+    assert!((point.1 - 48.58).abs() < 1e-9);
+    assert!(point.0 > 7.76 && point.0 < 7.77);
+
+    let position = locate_on_line(&line, point).unwrap();
+    assert!((position.chainage_m - 1000.0).abs() < 0.5);
+    assert!(position.offset_m < 0.1);
+
+    // Bornée aux extrémités du tracé
+    assert_eq!(point_at_chainage(&line, 5000.0), Some((7.77, 48.58)));
+    assert_eq!(point_at_chainage(&line, -10.0), Some((7.75, 48.58)));
+    assert!(point_at_chainage(&[], 10.0).is_none());
+}
+
+#[test]
+fn test_interpolate_at_chainage_returns_segment() {
+    let line = line();
+    let lengths = cumulative_lengths(&line);
+
+    let (segment, point) = interpolate_at_chainage(&line, &lengths, 500.0).unwrap();
+    assert_eq!(segment, 0);
+    assert_eq!(Some(point), point_at_chainage(&line, 500.0));
+
+    // Un sommet appartient au segment qui y termine
+    assert_eq!(
+        interpolate_at_chainage(&line, &lengths, lengths[1])
+            .unwrap()
+            .0,
+        0
+    );
+    assert_eq!(
+        interpolate_at_chainage(&line, &lengths, 1000.0).unwrap().0,
+        1
+    );
+    assert_eq!(
+        interpolate_at_chainage(&line, &lengths, 5000.0),
+        Some((1, (7.77, 48.58)))
+    );
+
+    // Longueurs incohérentes avec le tracé
+    assert!(interpolate_at_chainage(&line, &lengths[..2], 500.0).is_none());
+}
+
+#[test]
+fn test_passage_window_uses_both_speeds() {
+    let start = parse_datetime("2025-06-01T09:00").unwrap();
+    // 10 km : 30 min à 20 km/h, 1 h à 10 km/h
+    let w = passage_window(start, 10_000.0, Some(10.0), Some(20.0)).unwrap();
+    assert_eq!(w, window("2025-06-01T09:30", "2025-06-01T10:00"));
+}
+
+#[test]
+fn test_passage_window_single_speed_or_none() {
+    let start = parse_datetime("2025-06-01T09:00").unwrap();
+    let w = passage_window(start, 10_000.0, None, Some(20.0)).unwrap();
+    assert_eq!(w.first_passage, w.last_passage);
+    assert!(passage_window(start, 10_000.0, None, Some(0.0)).is_none());
+}
+
+#[test]
+fn test_check_race_window_pose() {
+    let w = window("2025-06-01T09:30", "2025-06-01T10:00");
+    let ok = parse_datetime("2025-06-01T09:00").unwrap();
+    let late = parse_datetime("2025-06-01T09:20").unwrap();
+    assert!(check_race_window("pose", ok, 15, &w).is_none());
+    assert!(check_race_window("pose", late, 15, &w).is_some());
+}
+
+#[test]
+fn test_check_race_window_depose() {
+    let w = window("2025-06-01T09:30", "2025-06-01T10:00");
+    let early = parse_datetime("2025-06-01T09:45").unwrap();
+    let ok = parse_datetime("2025-06-01T10:00").unwrap();
+    assert!(check_race_window("depose", early, 15, &w).is_some());
+    assert!(check_race_window("depose", ok, 15, &w).is_none());
+}
+
+#[test]
+fn test_occupancy_window_ignores_far_points() {
+    let start = parse_datetime("2025-06-01T09:00").unwrap();
+    let far = [(7.76, 48.59)];
+    assert!(occupancy_window(&line(), start, Some(10.0), Some(20.0), &far, 25.0).is_none());
+
+    let near = [(7.77, 48.58), (7.76, 48.58)];
+    let (chainage, w) =
+        occupancy_window(&line(), start, Some(10.0), Some(20.0), &near, 25.0).unwrap();
+    assert!((chainage - 737.0).abs() < 5.0);
+    assert!(w.first_passage < w.last_passage);
+}
+
+fn wave(start: &str, speed_low: Option<f64>, speed_high: Option<f64>) -> RaceWave {
+    RaceWave {
+        name: None,
+        start: parse_datetime(start).unwrap(),
+        speed_low,
+        speed_high,
+    }
+}
+
+fn race_parcours(id: &str, line: Vec<(f64, f64)>, speed: Option<f64>) -> RaceParcours {
+    RaceParcours {
+        id: id.to_string(),
+        name: Some(format!("Parcours {}", id)),
+        waves: vec![wave("2025-06-01T09:00", speed, speed.map(|s| s * 2.0))],
+        line,
+    }
+}
+
+#[test]
+fn test_estimate_passage_picks_nearest_parcours() {
+    let parcours = vec![
+        race_parcours("p1", line(), Some(10.0)),
+        // Même tracé décalé d'environ 1,1 km au nord
+        race_parcours("p2", vec![(7.75, 48.59), (7.77, 48.59)], Some(10.0)),
+    ];
+
+    let estimate = estimate_passage(&parcours, &[(7.76, 48.5899)]).unwrap();
+    assert_eq!(estimate.parcours_index, 1);
+    assert!((estimate.position.chainage_m - 737.0).abs() < 5.0);
+    assert!(estimate.window.first_passage < estimate.window.last_passage);
+}
+
+#[test]
+fn test_estimate_passage_ignores_parcours_without_speed() {
+    let parcours = vec![
+        race_parcours("p1", line(), None),
+        race_parcours("p2", vec![(7.75, 48.59), (7.77, 48.59)], Some(10.0)),
+    ];
+
+    let estimate = estimate_passage(&parcours, &[(7.76, 48.58)]).unwrap();
+    assert_eq!(estimate.parcours_index, 1);
+    assert!(estimate.position.offset_m > 1000.0);
+    assert!(estimate_passage(&parcours[..1], &[(7.76, 48.58)]).is_none());
+}
+
+#[test]
+fn test_passage_time_flags_points_off_parcours() {
+    let parcours = vec![race_parcours("p1", line(), Some(10.0))];
+
+    // Sur le tracé, à ~737 m : 10 km/h -> 4 min 25 s, 20 km/h -> 2 min 13 s
+    let on = passage_time(&parcours, "point", Some("a".to_string()), &[(7.76, 48.58)]).unwrap();
+    assert!(on.on_parcours);
+    assert_eq!(on.parcours_id, "p1");
+    assert_eq!(on.first_passage, "2025-06-01T09:02");
+    assert_eq!(on.last_passage, "2025-06-01T09:04");
+
+    let off = passage_time(&parcours, "interest", None, &[(7.76, 48.585)]).unwrap();
+    assert!(!off.on_parcours);
+    assert!(off.offset_m > 500.0);
+}
+
+#[test]
+fn test_passage_time_on_parcours_skips_distant_points() {
+    let parcours = vec![race_parcours("p1", line(), Some(10.0))];
+
+    assert!(passage_time_on_parcours(&parcours, "point", None, &[(7.76, 48.58)]).is_some());
+    assert!(passage_time_on_parcours(&parcours, "point", None, &[(7.76, 48.585)]).is_none());
+}
+
+#[test]
+fn test_passages_by_target_skips_coordinates() {
+    let parcours = vec![race_parcours("p1", line(), Some(10.0))];
+    let passages = vec![
+        passage_time(&parcours, "point", Some("a".to_string()), &[(7.76, 48.58)]).unwrap(),
+        passage_time(&parcours, "coordinate", None, &[(7.76, 48.58)]).unwrap(),
+        // Hors parcours : pas d'heure dans les exports
+        passage_time(&parcours, "point", Some("b".to_string()), &[(7.76, 48.585)]).unwrap(),
+    ];
+
+    let by_target = passages_by_target(passages);
+    assert_eq!(by_target.len(), 1);
+    assert!(by_target.contains_key(&("point".to_string(), "a".to_string())));
+}
+
+#[test]
+fn test_race_waves_default_to_parcours_start() {
+    let start = parse_datetime("2025-06-01T09:00").unwrap();
+    let waves = race_waves(vec![], Some(start), Some(8.0), Some(16.0));
+    assert_eq!(waves, vec![wave("2025-06-01T09:00", Some(8.0), Some(16.0))]);
+
+    // Les vagues explicites remplacent le départ du parcours
+    let explicit = vec![wave("2025-06-01T09:30", Some(10.0), None)];
+    assert_eq!(
+        race_waves(explicit.clone(), Some(start), Some(8.0), None),
+        explicit
+    );
+
+    assert!(race_waves(vec![], None, Some(8.0), None).is_empty());
+}
+
+#[test]
+fn test_parcours_window_is_union_of_waves() {
+    // Élite à 9h00 (20 km/h), masse à 9h15 (8 à 12 km/h)
+    let mut parcours = race_parcours("p1", line(), None);
+    parcours.waves = vec![
+        wave("2025-06-01T09:00", Some(20.0), Some(20.0)),
+        wave("2025-06-01T09:15", Some(8.0), Some(12.0)),
+    ];
+
+    // 2 km : élite à 9h06, dernier coureur de la masse à 9h30
+    let w = parcours.passage_window(2000.0).unwrap();
+    assert_eq!(w.first_passage, parse_datetime("2025-06-01T09:06").unwrap());
+    assert_eq!(w.last_passage, parse_datetime("2025-06-01T09:30").unwrap());
+
+    let near = [(7.76, 48.58)];
+    let (chainage, w) = parcours.occupancy_window(&near, 25.0).unwrap();
+    assert!((chainage - 737.0).abs() < 5.0);
+    assert!(w.first_passage < parse_datetime("2025-06-01T09:03").unwrap());
+    assert!(w.last_passage > parse_datetime("2025-06-01T09:20").unwrap());
+}
+
+#[test]
+fn test_parcours_window_without_speed() {
+    let parcours = race_parcours("p1", line(), None);
+    assert!(parcours.passage_window(1000.0).is_none());
+    assert!(parcours.occupancy_window(&[(7.76, 48.58)], 25.0).is_none());
+}
@@ -0,0 +1,122 @@
+use crate::scheduling::{solve_planning, travel_minutes, SolverOptions, SolverTask, SolverTeam};
+use crate::utils::parse_datetime;
+
+fn task(id: &str, action_type: &str, time: &str, position: (f64, f64)) -> SolverTask {
+    SolverTask {
+        action_id: None,
+        equipement_id: id.to_string(),
+        action_type: action_type.to_string(),
+        reference_time: parse_datetime(time).unwrap(),
+        position: Some(position),
+        origin: None,
+        duration_minutes: 15,
+        reference_team_size: 0,
+    }
+}
+
+fn team(id: &str) -> SolverTeam {
+    SolverTeam {
+        id: id.to_string(),
+        name: Some(format!("Équipe {}", id)),
+        size: 0,
+    }
+}
+
+#[test]
+fn test_travel_minutes_rounds_up() {
+    // 10 km à 20 km/h = 30 minutes
+    assert_eq!(travel_minutes(10_000.0, 20.0), 30);
+    // 100 m à 20 km/h = 0.3 minute, arrondi à 1
+    assert_eq!(travel_minutes(100.0, 20.0), 1);
+    assert_eq!(travel_minutes(0.0, 20.0), 0);
+    assert_eq!(travel_minutes(1000.0, 0.0), 0);
+}
+
+#[test]
+fn test_start_window_pose_ends_before_reference() {
+    let t = task("e1", "pose", "2025-06-01T10:00", (7.75, 48.58));
+    let (earliest, latest) = t.start_window(60);
+    assert_eq!(earliest, parse_datetime("2025-06-01T08:45").unwrap());
+    assert_eq!(latest, parse_datetime("2025-06-01T09:45").unwrap());
+}
+
+#[test]
+fn test_start_window_depose_starts_at_reference() {
+    let t = task("e1", "depose", "2025-06-01T18:00", (7.75, 48.58));
+    let (earliest, latest) = t.start_window(60);
+    assert_eq!(earliest, parse_datetime("2025-06-01T18:00").unwrap());
+    assert_eq!(latest, parse_datetime("2025-06-01T19:00").unwrap());
+}
+
+#[test]
+fn test_solve_planning_without_teams() {
+    let tasks = vec![task("e1", "pose", "2025-06-01T10:00", (7.75, 48.58))];
+    let (actions, loads) = solve_planning(&tasks, &[], &SolverOptions::default());
+    assert!(actions.is_empty());
+    assert!(loads.is_empty());
+}
+
+#[test]
+fn test_solve_planning_balances_workload() {
+    let tasks = vec![
+        task("e1", "pose", "2025-06-01T10:00", (7.750, 48.580)),
+        task("e2", "pose", "2025-06-01T10:00", (7.751, 48.580)),
+        task("e3", "pose", "2025-06-01T10:00", (7.752, 48.580)),
+        task("e4", "pose", "2025-06-01T10:00", (7.753, 48.580)),
+    ];
+    let teams = vec![team("a"), team("b")];
+    let (actions, loads) = solve_planning(&tasks, &teams, &SolverOptions::default());
+
+    assert_eq!(actions.len(), 4);
+    assert!(actions.iter().all(|a| !a.is_late));
+    assert_eq!(loads[0].action_count, 2);
+    assert_eq!(loads[1].action_count, 2);
+}
+
+#[test]
+fn test_solve_planning_chains_actions_of_a_team() {
+    let tasks = vec![
+        task("e1", "depose", "2025-06-01T18:00", (7.750, 48.580)),
+        task("e2", "depose", "2025-06-01T18:00", (7.750, 48.580)),
+    ];
+    let (actions, _) = solve_planning(&tasks, &[team("a")], &SolverOptions::default());
+
+    assert_eq!(actions[0].scheduled_time, "2025-06-01T18:00");
+    // Même position : la seconde dépose commence à la fin de la première
+    assert_eq!(actions[1].scheduled_time, "2025-06-01T18:15");
+}
+
+#[test]
+fn test_solve_planning_flags_late_actions() {
+    let options = SolverOptions {
+        window_minutes: 0,
+        ..SolverOptions::default()
+    };
+    let tasks = vec![
+        task("e1", "pose", "2025-06-01T10:00", (7.75, 48.58)),
+        task("e2", "pose", "2025-06-01T10:00", (7.75, 48.58)),
+    ];
+    let (actions, _) = solve_planning(&tasks, &[team("a")], &options);
+
+    assert!(!actions[0].is_late);
+    assert!(actions[1].is_late);
+}
+
+#[test]
+fn test_duration_scales_with_team_size() {
+    let mut t = task("e1", "pose", "2025-06-01T10:00", (7.75, 48.58));
+    t.duration_minutes = 40;
+    t.reference_team_size = 2;
+
+    assert_eq!(t.duration_for(2), 40);
+    assert!(t.duration_for(4) < 40);
+    assert!(t.duration_for(1) > 40);
+    // Taille inconnue : durée de référence
+    assert_eq!(t.duration_for(0), 40);
+
+    // Le solveur reporte la durée propre à l'équipe retenue
+    let mut big = team("t1");
+    big.size = 4;
+    let (actions, _) = solve_planning(&[t.clone()], &[big], &SolverOptions::default());
+    assert_eq!(actions[0].duration_minutes, t.duration_for(4));
+}
@@ -0,0 +1,89 @@
+use crate::snapping::{refreshed_chainage, snap_result, snap_to_parcours, SnapLine};
+
+/// Deux parcours est-ouest parallèles, à ~111 m l'un de l'autre
+fn parcours() -> Vec<SnapLine> {
+    vec![
+        SnapLine {
+            id: "p1".to_string(),
+            name: Some("10 km".to_string()),
+            line: vec![(7.75, 48.58), (7.77, 48.58)],
+        },
+        SnapLine {
+            id: "p2".to_string(),
+            name: Some("Semi".to_string()),
+            line: vec![(7.75, 48.581), (7.77, 48.581)],
+        },
+    ]
+}
+
+#[test]
+fn test_snap_point_onto_nearest_parcours() {
+    // ~10 m au nord du parcours 1
+    let point = (7.76, 48.58009);
+    let snapped = snap_to_parcours(&parcours(), &[point], 25.0).unwrap();
+
+    assert_eq!(snapped.parcours_index, 0);
+    assert!((snapped.offset_m - 10.0).abs() < 0.5);
+    assert!((snapped.points[0].1 - 48.58).abs() < 1e-9);
+    assert!((snapped.points[0].0 - 7.76).abs() < 1e-6);
+    // Milieu du tracé (~1471 m au total)
+    assert!((snapped.chainage_m - 735.6).abs() < 1.0);
+}
+
+#[test]
+fn test_snap_point_out_of_tolerance() {
+    // À mi-distance des deux parcours (~55 m)
+    assert!(snap_to_parcours(&parcours(), &[(7.76, 48.5805)], 25.0).is_none());
+    assert!(snap_to_parcours(&parcours(), &[], 25.0).is_none());
+    assert!(snap_to_parcours(&[], &[(7.76, 48.58)], 25.0).is_none());
+}
+
+#[test]
+fn test_snap_polyline_keeps_order_and_uses_start_chainage() {
+    // Barrière posée dans le sens inverse de la course, près du parcours 2
+    let points = vec![(7.762, 48.58095), (7.761, 48.58105)];
+    let snapped = snap_to_parcours(&parcours(), &points, 25.0).unwrap();
+
+    assert_eq!(snapped.parcours_index, 1);
+    assert_eq!(snapped.points.len(), 2);
+    assert!(snapped.points[0].0 > snapped.points[1].0);
+    assert!(snapped.points.iter().all(|p| (p.1 - 48.581).abs() < 1e-9));
+    // Position de la barrière : son extrémité la plus proche du départ (7.761)
+    assert!((snapped.chainage_m - 811.0).abs() < 2.0);
+}
+
+#[test]
+fn test_snap_polyline_requires_every_point_in_tolerance() {
+    let points = vec![(7.76, 48.58005), (7.761, 48.5805)];
+    assert!(snap_to_parcours(&parcours(), &points, 25.0).is_none());
+}
+
+#[test]
+fn test_snap_result_without_projection_keeps_coordinates() {
+    let original = vec![(7.76, 48.59)];
+    let result = snap_result("point", "pt1", &parcours(), &original, None);
+
+    assert!(!result.snapped);
+    assert_eq!(result.parcours_id, None);
+    assert_eq!(result.coordinates, original);
+
+    let snapped = snap_to_parcours(&parcours(), &[(7.76, 48.5809)], 25.0).unwrap();
+    let result = snap_result("point", "pt1", &parcours(), &original, Some(&snapped));
+    assert!(result.snapped);
+    assert_eq!(result.parcours_id.as_deref(), Some("p2"));
+    assert_eq!(result.parcours_name.as_deref(), Some("Semi"));
+}
+
+#[test]
+fn test_refreshed_chainage_follows_moved_point() {
+    // Toujours près du parcours 1, plus loin sur le tracé
+    let (parcours_id, chainage_m) = refreshed_chainage(&parcours(), &[(7.765, 48.58005)]);
+    assert_eq!(parcours_id.as_deref(), Some("p1"));
+    assert!((chainage_m.unwrap() - 1103.4).abs() < 2.0);
+
+    // Déplacé loin des deux tracés : l'accrochage est effacé
+    assert_eq!(
+        refreshed_chainage(&parcours(), &[(7.76, 48.59)]),
+        (None, None)
+    );
+}
@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use crate::utils::create_file_name;
+    use crate::utils::{
+        action_kind, action_label, create_file_name, escape_typst, escape_xml, format_datetime,
+        parse_datetime, stored_action_type,
+    };
 
     /// Test que create_file_name retourne un nom de fichier avec l'extension correcte
     #[test]
@@ -103,4 +106,67 @@ mod tests {
             );
         }
     }
+
+    /// Test du parsing des dates saisies dans le formulaire
+    #[test]
+    fn test_parse_datetime_input_format() {
+        let dt = parse_datetime("2025-06-01T08:30").expect("date valide");
+        assert_eq!(format_datetime(&dt), "2025-06-01T08:30");
+    }
+
+    #[test]
+    fn test_parse_datetime_with_seconds_and_space() {
+        let with_seconds = parse_datetime("2025-06-01T08:30:15").unwrap();
+        let with_space = parse_datetime("2025-06-01 08:30").unwrap();
+        assert_eq!(format_datetime(&with_seconds), "2025-06-01T08:30");
+        assert_eq!(format_datetime(&with_space), "2025-06-01T08:30");
+    }
+
+    #[test]
+    fn test_parse_datetime_date_only_is_midnight() {
+        let dt = parse_datetime("2025-06-01").unwrap();
+        assert_eq!(format_datetime(&dt), "2025-06-01T00:00");
+    }
+
+    #[test]
+    fn test_parse_datetime_invalid() {
+        assert!(parse_datetime("").is_none());
+        assert!(parse_datetime("demain matin").is_none());
+    }
+
+    #[test]
+    fn test_action_kind() {
+        assert_eq!(action_kind("pose"), "pose");
+        assert_eq!(action_kind("depose"), "depose");
+        assert_eq!(action_kind("retrait"), "depose");
+    }
+
+    #[test]
+    fn test_action_label() {
+        assert_eq!(action_label("pose"), "Pose");
+        assert_eq!(action_label("depose"), "Dépose");
+        assert_eq!(action_label("retrait"), "Dépose");
+    }
+
+    #[test]
+    fn test_stored_action_type_matches_web_ui() {
+        assert_eq!(stored_action_type("pose"), "pose");
+        assert_eq!(stored_action_type("depose"), "retrait");
+        assert_eq!(stored_action_type("retrait"), "retrait");
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&apos;s&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn test_escape_typst() {
+        assert_eq!(escape_typst("Équipe #1 [Nord]"), r"Équipe \#1 \[Nord\]");
+        assert_eq!(escape_typst("a*b_c/d"), r"a\*b\_c\/d");
+        assert_eq!(escape_typst("Rue de la Paix"), "Rue de la Paix");
+    }
 }
@@ -0,0 +1,29 @@
+use crate::db::waves::validate_wave_speeds;
+
+#[test]
+fn test_valid_wave_speeds() {
+    assert!(validate_wave_speeds(Some(8.0), Some(16.0)).is_ok());
+    assert!(validate_wave_speeds(Some(12.0), Some(12.0)).is_ok());
+    // Vitesses facultatives
+    assert!(validate_wave_speeds(None, None).is_ok());
+    assert!(validate_wave_speeds(Some(8.0), None).is_ok());
+    assert!(validate_wave_speeds(None, Some(16.0)).is_ok());
+}
+
+#[test]
+fn test_low_speed_above_high_speed_is_rejected() {
+    let err = validate_wave_speeds(Some(18.0), Some(10.0)).unwrap_err();
+    assert!(err.contains("dépasse la vitesse haute"));
+}
+
+#[test]
+fn test_non_positive_speeds_are_rejected() {
+    assert!(validate_wave_speeds(Some(0.0), Some(10.0))
+        .unwrap_err()
+        .contains("vitesse basse"));
+    assert!(validate_wave_speeds(Some(8.0), Some(-5.0))
+        .unwrap_err()
+        .contains("vitesse haute"));
+    assert!(validate_wave_speeds(None, Some(f64::NAN)).is_err());
+    assert!(validate_wave_speeds(Some(f64::INFINITY), None).is_err());
+}
@@ -0,0 +1,133 @@
+use crate::snapping::SnapLine;
+use crate::zone_conflicts::{
+    analyze_spatial_conflicts, clip_line_to_polygon, SpatialEquipement, SpatialZone,
+};
+
+/// Carré de 0.01° centré sur (0.01, 0.01)
+fn square() -> Vec<(f64, f64)> {
+    vec![
+        (0.005, 0.005),
+        (0.015, 0.005),
+        (0.015, 0.015),
+        (0.005, 0.015),
+    ]
+}
+
+fn zone(id: &str, restricted: bool) -> SpatialZone {
+    SpatialZone {
+        id: id.to_string(),
+        name: Some(format!("Zone {}", id)),
+        polygon: square(),
+        restricted,
+    }
+}
+
+fn parcours(id: &str, line: Vec<(f64, f64)>) -> SnapLine {
+    SnapLine {
+        id: id.to_string(),
+        name: Some(format!("Parcours {}", id)),
+        line,
+    }
+}
+
+fn equipement(id: &str, line: Vec<(f64, f64)>) -> SpatialEquipement {
+    SpatialEquipement {
+        id: id.to_string(),
+        label: format!("Barrière {}", id),
+        line,
+    }
+}
+
+fn close(a: (f64, f64), b: (f64, f64)) -> bool {
+    (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9
+}
+
+// ============== Tests pour clip_line_to_polygon ==============
+
+#[test]
+fn test_clip_line_through_polygon() {
+    let fragments = clip_line_to_polygon(&[(0.0, 0.01), (0.02, 0.01)], &square());
+    assert_eq!(fragments.len(), 1);
+    assert_eq!(fragments[0].len(), 2);
+    assert!(close(fragments[0][0], (0.005, 0.01)));
+    assert!(close(fragments[0][1], (0.015, 0.01)));
+}
+
+#[test]
+fn test_clip_line_outside_polygon() {
+    let fragments = clip_line_to_polygon(&[(0.0, 0.0), (0.02, 0.0)], &square());
+    assert!(fragments.is_empty());
+}
+
+#[test]
+fn test_clip_line_starting_inside_keeps_vertices() {
+    let line = vec![(0.01, 0.01), (0.012, 0.01), (0.012, 0.02)];
+    let fragments = clip_line_to_polygon(&line, &square());
+    assert_eq!(fragments.len(), 1);
+    assert_eq!(fragments[0].len(), 3);
+    assert!(close(fragments[0][0], (0.01, 0.01)));
+    assert!(close(fragments[0][1], (0.012, 0.01)));
+    assert!(close(fragments[0][2], (0.012, 0.015)));
+}
+
+#[test]
+fn test_clip_line_entering_twice() {
+    // Aller-retour en « U » qui traverse deux fois le carré
+    let line = vec![(0.0, 0.008), (0.02, 0.008), (0.02, 0.012), (0.0, 0.012)];
+    let fragments = clip_line_to_polygon(&line, &square());
+    assert_eq!(fragments.len(), 2);
+    assert!(close(fragments[1][0], (0.015, 0.012)));
+    assert!(close(fragments[1][1], (0.005, 0.012)));
+}
+
+// ============== Tests pour analyze_spatial_conflicts ==============
+
+#[test]
+fn test_zone_on_parcours_reports_fragment() {
+    let warnings = analyze_spatial_conflicts(
+        &[zone("z1", false)],
+        &[parcours("p1", vec![(0.0, 0.01), (0.02, 0.01)])],
+        &[],
+    );
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].kind, "zone_parcours");
+    assert_eq!(warnings[0].zone_id.as_deref(), Some("z1"));
+    assert_eq!(warnings[0].parcours_id.as_deref(), Some("p1"));
+    assert!(warnings[0].fragment_wkt.starts_with("LINESTRING("));
+    // L'entrée dans la zone se fait à ~556 m du départ
+    let chainage = warnings[0].chainage_m.unwrap();
+    assert!((chainage - 556.0).abs() < 5.0);
+}
+
+#[test]
+fn test_equipement_crossing_parcours() {
+    let warnings = analyze_spatial_conflicts(
+        &[],
+        &[parcours("p1", vec![(0.0, 0.0), (0.02, 0.0)])],
+        &[equipement("e1", vec![(0.01, -0.001), (0.01, 0.001)])],
+    );
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].kind, "equipement_parcours");
+    assert_eq!(warnings[0].equipement_id.as_deref(), Some("e1"));
+    assert_eq!(warnings[0].fragment_wkt, "POINT(0.01 0)");
+}
+
+#[test]
+fn test_equipement_in_restricted_zone_only() {
+    let equipements = [
+        equipement("dedans", vec![(0.01, 0.01)]),
+        equipement("traverse", vec![(0.0, 0.01), (0.01, 0.01)]),
+        equipement("dehors", vec![(0.03, 0.03)]),
+    ];
+
+    let open = analyze_spatial_conflicts(&[zone("z1", false)], &[], &equipements);
+    assert!(open.is_empty());
+
+    let warnings = analyze_spatial_conflicts(&[zone("z1", true)], &[], &equipements);
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings.iter().all(|w| w.kind == "equipement_zone"));
+    assert_eq!(warnings[0].equipement_id.as_deref(), Some("dedans"));
+    assert_eq!(warnings[0].fragment_wkt, "POINT(0.01 0.01)");
+    assert_eq!(warnings[1].equipement_id.as_deref(), Some("traverse"));
+    assert!(warnings[1].fragment_wkt.starts_with("LINESTRING(0.005"));
+}
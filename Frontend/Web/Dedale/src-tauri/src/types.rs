@@ -1,3 +1,5 @@
+use crate::itinerary::ItineraryStop;
+use crate::race::PassageTime;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -20,6 +22,9 @@ pub struct Zone {
     pub color: Option<String>,
     pub description: Option<String>,
     pub geometry_json: Option<String>,
+    /// Zone interdite aux équipements (village, espace spectateurs...)
+    #[serde(default)]
+    pub restricted: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -35,6 +40,18 @@ pub struct Parcours {
     pub geometry_json: Option<String>,
 }
 
+/// Vague de départ d'un parcours (heure de départ en ms, comme pour le parcours)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Wave {
+    #[serde(default)]
+    pub id: String,
+    pub parcours_id: String,
+    pub name: Option<String>,
+    pub start_time: Option<i64>,
+    pub speed_low: Option<f64>,
+    pub speed_high: Option<f64>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Team {
     #[serde(default)]
@@ -125,6 +142,13 @@ pub struct Interest {
     pub y: f64,
     pub description: Option<String>,
     pub event_id: Option<String>,
+    /// Borne kilométrique générée : parcours, distance (km) et cap du tracé (degrés)
+    #[serde(default)]
+    pub parcours_id: Option<String>,
+    #[serde(default)]
+    pub marker_km: Option<f64>,
+    #[serde(default)]
+    pub bearing: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -139,6 +163,11 @@ pub struct PointWithDetails {
     pub r#type: Option<String>,
     #[serde(default)]
     pub pictures: Vec<Picture>,
+    /// Parcours auquel le point est accroché et position kilométrique (m)
+    #[serde(default)]
+    pub parcours_id: Option<String>,
+    #[serde(default)]
+    pub chainage_m: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -228,7 +257,69 @@ pub struct EquipementComplet {
     pub hour_pose: Option<String>,
     pub date_depose: Option<String>,
     pub hour_depose: Option<String>,
+    pub depot_id: Option<String>,
     pub coordinates: Vec<EquipementCoordinate>,
+    /// Parcours auquel l'équipement est accroché et position kilométrique (m)
+    #[serde(default)]
+    pub parcours_id: Option<String>,
+    #[serde(default)]
+    pub chainage_m: Option<f64>,
+}
+
+/// Dépôt (entrepôt) d'où part le matériel
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Depot {
+    #[serde(default)]
+    pub id: String,
+    pub name: Option<String>,
+    pub address: Option<String>,
+    pub x: f64,
+    pub y: f64,
+    #[serde(default)]
+    pub stock: Vec<DepotStock>,
+}
+
+/// Stock d'un type d'équipement dans un dépôt
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DepotStock {
+    pub depot_id: String,
+    pub type_id: String,
+    pub type_name: Option<String>,
+    pub quantity: i32,
+}
+
+/// Équipement qui sort d'un dépôt (pose) et y revient (dépose)
+#[derive(Debug, Serialize, Clone)]
+pub struct DepotMovement {
+    pub equipement_id: String,
+    pub depot_id: Option<String>,
+    pub type_id: Option<String>,
+    pub type_name: Option<String>,
+    pub description: Option<String>,
+    pub quantity: i32,
+    pub length_per_unit: i32,
+    pub departure_time: Option<String>,
+    pub return_time: Option<String>,
+    pub team_name: Option<String>,
+}
+
+/// Bilan stock / besoin d'un type d'équipement pour un dépôt
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct DepotTypeBalance {
+    pub type_id: Option<String>,
+    pub type_name: Option<String>,
+    pub stock: i32,
+    pub total_out: i32,
+    pub peak_out: i32,
+    pub missing: i32,
+}
+
+/// Rapport de chargement d'un dépôt pour un événement
+#[derive(Debug, Serialize, Clone)]
+pub struct DepotReport {
+    pub depot: Option<Depot>, // None = équipements sans dépôt
+    pub departures: Vec<DepotMovement>,
+    pub balances: Vec<DepotTypeBalance>,
 }
 
 #[derive(Debug, Serialize, Clone, Deserialize, sqlx::FromRow)]
@@ -240,6 +331,111 @@ pub struct Action {
     pub r#type: Option<String>,
     pub scheduled_time: Option<String>,
     pub is_done: Option<bool>,
+    /// Statut terrain (voir `ActionStatus`)
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Date du dernier changement de statut
+    #[serde(default)]
+    pub status_changed_at: Option<String>,
+    /// Actions qui doivent être faites avant celle-ci
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Vrai tant qu'une action de `depends_on` n'est pas faite
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub blocked: bool,
+}
+
+/// Dépendance entre actions : `action_id` ne peut commencer qu'après `depends_on_id`
+#[derive(Debug, Serialize, Clone, Deserialize, PartialEq)]
+pub struct ActionDependency {
+    #[serde(default)]
+    pub id: String,
+    pub action_id: String,
+    pub depends_on_id: String,
+}
+
+/// Dépendance non respectée par le planning
+#[derive(Debug, Serialize, Clone, Deserialize, PartialEq)]
+pub struct DependencyViolation {
+    pub action_id: String,
+    pub depends_on_id: String,
+    pub action_time: String,
+    pub depends_on_end: String,
+    pub message: String,
+}
+
+/// Statut d'une action sur le terrain
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionStatus {
+    Planned,
+    EnRoute,
+    InProgress,
+    Done,
+    Blocked,
+    Cancelled,
+}
+
+impl ActionStatus {
+    /// Valeur stockée en base (colonne action.status)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActionStatus::Planned => "planned",
+            ActionStatus::EnRoute => "en_route",
+            ActionStatus::InProgress => "in_progress",
+            ActionStatus::Done => "done",
+            ActionStatus::Blocked => "blocked",
+            ActionStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "planned" => Some(ActionStatus::Planned),
+            "en_route" => Some(ActionStatus::EnRoute),
+            "in_progress" => Some(ActionStatus::InProgress),
+            "done" => Some(ActionStatus::Done),
+            "blocked" => Some(ActionStatus::Blocked),
+            "cancelled" => Some(ActionStatus::Cancelled),
+            _ => None,
+        }
+    }
+
+    /// Libellé affiché dans les exports
+    pub fn label(&self) -> &'static str {
+        match self {
+            ActionStatus::Planned => "Planifiée",
+            ActionStatus::EnRoute => "En route",
+            ActionStatus::InProgress => "En cours",
+            ActionStatus::Done => "Terminée",
+            ActionStatus::Blocked => "Bloquée",
+            ActionStatus::Cancelled => "Annulée",
+        }
+    }
+
+    /// Action terminée ou annulée : plus rien à planifier
+    pub fn is_closed(&self) -> bool {
+        matches!(self, ActionStatus::Done | ActionStatus::Cancelled)
+    }
+}
+
+/// Changement de statut d'une action, avec les éléments remontés du terrain
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct ActionStatusChange {
+    pub id: String,
+    pub action_id: String,
+    pub previous_status: Option<ActionStatus>,
+    pub status: ActionStatus,
+    /// "YYYY-MM-DDTHH:MM:SS", heure locale
+    pub changed_at: String,
+    pub person_id: Option<String>,
+    pub person_name: Option<String>,
+    pub note: Option<String>,
+    /// Photos encodées en base64 (comme picture.image_data)
+    #[serde(default)]
+    pub photos: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -289,6 +485,12 @@ pub struct Planning {
     pub actions: Vec<Action>,
     pub equipements: Vec<TransferEquipement>,
     pub coordonees: Vec<TransferEquipementCoordinate>,
+    /// Étapes de la tournée (même ordre que `actions`), avec distances et heures estimées
+    pub itinerary: Vec<ItineraryStop>,
+    /// Passage de la course au niveau des équipements (premier et dernier coureur)
+    pub passages: Vec<PassageTime>,
+    /// Bornes kilométriques des parcours de l'événement, pour la pose des panneaux
+    pub km_markers: Vec<Interest>,
 }
 
 #[derive(sqlx::FromRow)]
@@ -404,6 +606,27 @@ pub struct ClientAction {
 pub struct MobileExport {
     pub event: MobileExportEvent,
     pub points: Vec<MobilePointDetail>,
+    /// Changements de statut des actions saisis sur le terrain
+    #[serde(default)]
+    pub action_statuses: Vec<MobileActionStatus>,
+}
+
+/// Changement de statut d'une action envoyé par le mobile
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MobileActionStatus {
+    pub action_id: String,
+    pub status: String,
+    #[serde(default)]
+    pub person_id: Option<String>,
+    #[serde(default)]
+    pub note: Option<String>,
+    /// Date du changement sur le téléphone (ISO 8601)
+    #[serde(default)]
+    pub changed_at: Option<String>,
+    /// Photos encodées en base64
+    #[serde(default)]
+    pub photos: Vec<String>,
 }
 
 /// Structure pour un point dans l'export mobile (format différent du desktop)
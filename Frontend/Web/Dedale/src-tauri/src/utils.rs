@@ -1,3 +1,4 @@
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime};
 use dirs::data_dir;
 use rfd::FileDialog;
 use std::path::Path;
@@ -31,13 +32,113 @@ pub fn show_save_dialog(
         .set_file_name(default_file_name)
         .set_directory(file_path);
 
-    if extension.to_lowercase().contains("pdf") {
+    let extension = extension.to_lowercase();
+    if extension.contains("pdf") {
         dialog = dialog.add_filter("PDF (.pdf)", &["pdf"]);
         dialog = dialog.add_filter("EXCEL (.xlsx)", &["xlsx"]);
-    } else {
+    } else if extension.contains("xlsx") {
         dialog = dialog.add_filter("EXCEL (.xlsx)", &["xlsx"]);
         dialog = dialog.add_filter("PDF (.pdf)", &["pdf"]);
+    } else {
+        let label = format!("{} (.{})", extension.to_uppercase(), extension);
+        dialog = dialog.add_filter(label, &[extension.as_str()]);
     }
 
     dialog.add_filter("Any", &["*"]).save_file()
 }
+
+/// Format des dates saisies dans l'interface (input datetime-local)
+pub const DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+/// Parse une date du planning ("YYYY-MM-DDTHH:MM", avec ou sans secondes, ou date seule).
+/// Les dates ISO avec fuseau (ex: "2025-06-01T08:00:00.000Z") sont ramenées à l'heure locale.
+pub fn parse_datetime(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+
+    for format in [
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%d %H:%M:%S",
+    ] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(dt);
+        }
+    }
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.with_timezone(&Local).naive_local());
+    }
+
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+}
+
+/// Formate une date au format du planning ("YYYY-MM-DDTHH:MM")
+pub fn format_datetime(dt: &NaiveDateTime) -> String {
+    dt.format(DATETIME_FORMAT).to_string()
+}
+
+/// Nature d'une action : "pose", ou "depose" pour tout autre type
+/// (l'interface web enregistre les déposes avec le type "retrait")
+pub fn action_kind(action_type: &str) -> &'static str {
+    if action_type == "pose" {
+        "pose"
+    } else {
+        "depose"
+    }
+}
+
+/// Libellé d'une action dans les exports (iCalendar, Gantt, Excel, PDF) : "Pose" ou "Dépose"
+pub fn action_label(action_type: &str) -> &'static str {
+    if action_kind(action_type) == "pose" {
+        "Pose"
+    } else {
+        "Dépose"
+    }
+}
+
+/// Type enregistré pour une nouvelle action, le même que l'interface web ("pose" ou "retrait")
+pub fn stored_action_type(action_type: &str) -> &'static str {
+    if action_kind(action_type) == "pose" {
+        "pose"
+    } else {
+        "retrait"
+    }
+}
+
+/// Condition SQL équivalente à `action_kind` sur la colonne action.type
+pub const ACTION_KIND_SQL: &str = "CASE WHEN type = 'pose' THEN 'pose' ELSE 'depose' END";
+
+/// Convertit un horodatage en millisecondes (parcours.start_time, saisi en heure locale
+/// côté interface) en date locale
+pub fn timestamp_ms_to_local(timestamp_ms: i64) -> Option<NaiveDateTime> {
+    DateTime::from_timestamp_millis(timestamp_ms).map(|dt| dt.with_timezone(&Local).naive_local())
+}
+
+/// Échappe les caractères spéciaux XML (SVG, GPX, KML...)
+pub fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Échappe un texte inséré dans une source Typst (titres, cellules de tableau...)
+pub fn escape_typst(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(
+            c,
+            '\\' | '#' | '[' | ']' | '*' | '_' | '`' | '$' | '<' | '>' | '@' | '~' | '/'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -0,0 +1,291 @@
+// Analyse spatiale zones / parcours / équipements
+// Signale les zones (villages, espaces spectateurs...) qui empiètent sur le tracé, les
+// équipements qui coupent un parcours et ceux qui entrent dans une zone interdite.
+// Chaque alerte est accompagnée du fragment de géométrie en cause (WKT).
+
+use crate::crossings::{line_crossings, segment_intersection};
+use crate::db;
+use crate::map::{format_linestring_wkt, format_point_wkt, parse_polygon_wkt, point_in_ring};
+use crate::race::{cumulative_lengths, load_equipement_points, locate_on_line};
+use crate::snapping::{load_snap_lines, SnapLine};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+use tauri::AppHandle;
+
+/// Zone polygonale d'un événement
+#[derive(Debug, Clone)]
+pub struct SpatialZone {
+    pub id: String,
+    pub name: Option<String>,
+    pub polygon: Vec<(f64, f64)>,
+    /// Interdite aux équipements
+    pub restricted: bool,
+}
+
+/// Équipement et sa polyligne (ou son point unique)
+#[derive(Debug, Clone)]
+pub struct SpatialEquipement {
+    pub id: String,
+    pub label: String,
+    pub line: Vec<(f64, f64)>,
+}
+
+/// Alerte d'analyse spatiale
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpatialWarning {
+    /// "zone_parcours", "equipement_parcours" ou "equipement_zone"
+    pub kind: String,
+    pub message: String,
+    pub zone_id: Option<String>,
+    pub parcours_id: Option<String>,
+    pub equipement_id: Option<String>,
+    /// Position du conflit le long du parcours (m)
+    pub chainage_m: Option<f64>,
+    /// Fragment de géométrie en cause (WKT LINESTRING ou POINT)
+    pub fragment_wkt: String,
+}
+
+// ==================== Fonctions helper publiques et testables ====================
+
+/// Portions d'une polyligne situées à l'intérieur d'un polygone
+pub fn clip_line_to_polygon(line: &[(f64, f64)], polygon: &[(f64, f64)]) -> Vec<Vec<(f64, f64)>> {
+    if line.len() < 2 || polygon.len() < 3 {
+        return vec![];
+    }
+
+    let mut fragments = Vec::new();
+    let mut current: Vec<(f64, f64)> = Vec::new();
+
+    for segment in line.windows(2) {
+        let (a, b) = (segment[0], segment[1]);
+        let at = |t: f64| {
+            if t <= 0.0 {
+                a
+            } else if t >= 1.0 {
+                b
+            } else {
+                (a.0 + t * (b.0 - a.0), a.1 + t * (b.1 - a.1))
+            }
+        };
+
+        // Découpage du segment aux intersections avec les bords du polygone
+        let mut cuts = vec![0.0, 1.0];
+        for i in 0..polygon.len() {
+            let (e1, e2) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+            if let Some((t, _)) = segment_intersection(a, b, e1, e2) {
+                cuts.push(t);
+            }
+        }
+        cuts.sort_by(f64::total_cmp);
+        cuts.dedup_by(|x, y| (*x - *y).abs() < 1e-12);
+
+        for w in cuts.windows(2) {
+            let (start, end) = (at(w[0]), at(w[1]));
+            if point_in_ring(at((w[0] + w[1]) / 2.0), polygon) {
+                if current.last() != Some(&start) {
+                    if !current.is_empty() {
+                        fragments.push(std::mem::take(&mut current));
+                    }
+                    current.push(start);
+                }
+                current.push(end);
+            } else if !current.is_empty() {
+                fragments.push(std::mem::take(&mut current));
+            }
+        }
+    }
+
+    if !current.is_empty() {
+        fragments.push(current);
+    }
+    fragments
+}
+
+fn zone_label(zone: &SpatialZone) -> &str {
+    zone.name.as_deref().unwrap_or("Zone sans nom")
+}
+
+fn parcours_label(parcours: &SnapLine) -> &str {
+    parcours.name.as_deref().unwrap_or("Parcours")
+}
+
+/// Analyse complète : zones sur les parcours, équipements coupant un parcours,
+/// équipements dans une zone interdite
+pub fn analyze_spatial_conflicts(
+    zones: &[SpatialZone],
+    parcours: &[SnapLine],
+    equipements: &[SpatialEquipement],
+) -> Vec<SpatialWarning> {
+    let mut warnings = Vec::new();
+
+    // 1. Zones qui empiètent sur un parcours
+    for zone in zones {
+        for p in parcours {
+            for fragment in clip_line_to_polygon(&p.line, &zone.polygon) {
+                let length = cumulative_lengths(&fragment).last().copied().unwrap_or(0.0);
+                warnings.push(SpatialWarning {
+                    kind: "zone_parcours".to_string(),
+                    message: format!(
+                        "La zone « {} » empiète sur {} sur {:.0} m",
+                        zone_label(zone),
+                        parcours_label(p),
+                        length
+                    ),
+                    zone_id: Some(zone.id.clone()),
+                    parcours_id: Some(p.id.clone()),
+                    equipement_id: None,
+                    chainage_m: locate_on_line(&p.line, fragment[0]).map(|pos| pos.chainage_m),
+                    fragment_wkt: format_linestring_wkt(&fragment),
+                });
+            }
+        }
+    }
+
+    for equipement in equipements {
+        // 2. Équipements qui coupent un parcours
+        for p in parcours {
+            for crossing in line_crossings(&equipement.line, Some(&p.line)) {
+                warnings.push(SpatialWarning {
+                    kind: "equipement_parcours".to_string(),
+                    message: format!(
+                        "{} coupe {} au km {:.2}",
+                        equipement.label,
+                        parcours_label(p),
+                        crossing.chainage_b / 1000.0
+                    ),
+                    zone_id: None,
+                    parcours_id: Some(p.id.clone()),
+                    equipement_id: Some(equipement.id.clone()),
+                    chainage_m: Some(crossing.chainage_b),
+                    fragment_wkt: format_point_wkt(crossing.point.0, crossing.point.1),
+                });
+            }
+        }
+
+        // 3. Équipements dans une zone interdite
+        for zone in zones.iter().filter(|z| z.restricted) {
+            let fragments: Vec<String> = match equipement.line.as_slice() {
+                [point] => point_in_ring(*point, &zone.polygon)
+                    .then(|| format_point_wkt(point.0, point.1))
+                    .into_iter()
+                    .collect(),
+                line => clip_line_to_polygon(line, &zone.polygon)
+                    .iter()
+                    .map(|fragment| format_linestring_wkt(fragment))
+                    .collect(),
+            };
+
+            for fragment_wkt in fragments {
+                warnings.push(SpatialWarning {
+                    kind: "equipement_zone".to_string(),
+                    message: format!(
+                        "{} entre dans la zone interdite « {} »",
+                        equipement.label,
+                        zone_label(zone)
+                    ),
+                    zone_id: Some(zone.id.clone()),
+                    parcours_id: None,
+                    equipement_id: Some(equipement.id.clone()),
+                    chainage_m: None,
+                    fragment_wkt,
+                });
+            }
+        }
+    }
+
+    warnings
+}
+
+// ==================== Chargement des données ====================
+
+/// Zones polygonales d'un événement
+pub async fn load_spatial_zones(
+    pool: &SqlitePool,
+    event_id: &str,
+) -> Result<Vec<SpatialZone>, String> {
+    let rows =
+        sqlx::query("SELECT id, name, geometry_json, restricted FROM zone WHERE event_id = ?")
+            .bind(event_id)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let geometry: Option<String> = row.get("geometry_json");
+            let polygon = parse_polygon_wkt(geometry.as_deref().unwrap_or(""));
+            (polygon.len() >= 3).then(|| SpatialZone {
+                id: row.get("id"),
+                name: row.get("name"),
+                polygon,
+                restricted: row.get("restricted"),
+            })
+        })
+        .collect())
+}
+
+/// Équipements d'un événement avec leur libellé (type et description)
+pub async fn load_spatial_equipements(
+    pool: &SqlitePool,
+    event_id: &str,
+) -> Result<Vec<SpatialEquipement>, String> {
+    let mut coordinates = load_equipement_points(pool, event_id).await?;
+
+    let rows = sqlx::query(
+        r#"
+        SELECT e.id, e.description, t.name AS type_name
+        FROM equipement e
+        LEFT JOIN type t ON e.type_id = t.id
+        WHERE e.event_id = ?
+        "#,
+    )
+    .bind(event_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let id: String = row.get("id");
+            let line = coordinates.remove(&id)?;
+            let type_name: Option<String> = row.get("type_name");
+            let description: Option<String> = row.get("description");
+            let label = match (type_name, description.filter(|d| !d.is_empty())) {
+                (Some(t), Some(d)) => format!("{} ({})", t, d),
+                (Some(t), None) => t,
+                (None, Some(d)) => d,
+                (None, None) => "Équipement".to_string(),
+            };
+            Some(SpatialEquipement { id, label, line })
+        })
+        .collect())
+}
+
+// ==================== Commandes Tauri ====================
+
+/// Analyse spatiale d'un événement : zones et équipements en conflit avec les parcours
+#[tauri::command]
+pub async fn analyze_zone_conflicts(
+    app: AppHandle,
+    event_id: String,
+) -> Result<Vec<SpatialWarning>, String> {
+    let pool = db::get_db_pool(&app).await?;
+
+    let zones = load_spatial_zones(&pool, &event_id).await?;
+    let parcours = load_snap_lines(&pool, &event_id).await?;
+    let equipements = load_spatial_equipements(&pool, &event_id).await?;
+
+    let warnings = analyze_spatial_conflicts(&zones, &parcours, &equipements);
+
+    println!(
+        "[SPATIAL] ⚠️ {} alerte(s) pour l'événement {} ({} zone(s), {} parcours, {} équipement(s))",
+        warnings.len(),
+        event_id,
+        zones.len(),
+        parcours.len(),
+        equipements.len()
+    );
+    Ok(warnings)
+}
@@ -1,5 +1,6 @@
-// Script d'extraction des adresses depuis le fichier OSM PBF
+// Script d'extraction des adresses et du réseau de rues depuis le fichier OSM PBF
 // Exécuter une seule fois avec: cargo run --bin extract_addresses
+// Les rues (ways `highway` nommés) servent au calendrier de fermeture des rues (closures.rs)
 
 use osmpbf::{Element, ElementReader};
 use std::collections::HashMap;
@@ -14,6 +15,33 @@ const MAX_LAT: f64 = 49.10; // Nord de l'Alsace (Wissembourg)
 const MIN_LON: f64 = 6.80; // Ouest (Vosges)
 const MAX_LON: f64 = 8.30; // Est (bord du Rhin)
 
+// Types de voies circulables retenus pour le réseau de rues
+const ROAD_HIGHWAYS: [&str; 14] = [
+    "motorway",
+    "trunk",
+    "primary",
+    "secondary",
+    "tertiary",
+    "motorway_link",
+    "trunk_link",
+    "primary_link",
+    "secondary_link",
+    "tertiary_link",
+    "unclassified",
+    "residential",
+    "living_street",
+    "pedestrian",
+];
+
+#[derive(Debug, Clone)]
+struct Street {
+    osm_id: i64,
+    name: String,
+    highway: String,
+    // (lat, lon) dans l'ordre du way
+    coords: Vec<(f64, f64)>,
+}
+
 #[derive(Debug, Clone)]
 struct Address {
     lat: f64,
@@ -24,6 +52,35 @@ struct Address {
     postcode: String,
 }
 
+/// Découpe un way aux nœuds hors de la zone (absents de `node_coords`).
+/// Relier directement les nœuds de part et d'autre d'un trou créerait un segment
+/// qui n'existe pas sur le terrain ; seuls les tronçons d'au moins 2 nœuds sont gardés.
+fn split_way_in_bbox(
+    refs: impl Iterator<Item = i64>,
+    node_coords: &HashMap<i64, (f64, f64)>,
+) -> Vec<Vec<(f64, f64)>> {
+    let mut parts = Vec::new();
+    let mut current: Vec<(f64, f64)> = Vec::new();
+
+    for id in refs {
+        match node_coords.get(&id) {
+            Some(coord) => current.push(*coord),
+            None => {
+                if current.len() >= 2 {
+                    parts.push(std::mem::take(&mut current));
+                } else {
+                    current.clear();
+                }
+            }
+        }
+    }
+    if current.len() >= 2 {
+        parts.push(current);
+    }
+
+    parts
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     // Chemin vers le fichier PBF
     let pbf_path = "../public/alsace-latest.osm.pbf";
@@ -69,6 +126,20 @@ fn main() -> Result<(), Box<dyn Error>> {
         CREATE TRIGGER addresses_ai AFTER INSERT ON addresses BEGIN
             INSERT INTO addresses_fts(rowid, display_name) VALUES (new.id, new.display_name);
         END;
+
+        CREATE TABLE streets (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            osm_id INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            highway TEXT,
+            min_lat REAL NOT NULL,
+            max_lat REAL NOT NULL,
+            min_lon REAL NOT NULL,
+            max_lon REAL NOT NULL,
+            geometry TEXT NOT NULL
+        );
+
+        CREATE INDEX streets_bbox ON streets (min_lon, max_lon, min_lat, max_lat);
         ",
     )?;
 
@@ -83,6 +154,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     // Collecter les nœuds avec leurs coordonnées
     let mut node_coords: HashMap<i64, (f64, f64)> = HashMap::new();
     let mut addresses: Vec<Address> = Vec::new();
+    let mut streets: Vec<Street> = Vec::new();
 
     // Premier passage: collecter les coordonnées des nœuds
     println!("📍 Premier passage: collecte des coordonnées...");
@@ -125,13 +197,27 @@ fn main() -> Result<(), Box<dyn Error>> {
     println!("   {} nœuds dans la zone", node_coords.len());
     println!("   {} adresses trouvées sur les nœuds", addresses.len());
 
-    // Deuxième passage: collecter les adresses des ways
-    println!("🏠 Deuxième passage: collecte des adresses sur les bâtiments...");
+    // Deuxième passage: collecter les adresses des ways et le réseau de rues
+    println!("🏠 Deuxième passage: collecte des adresses sur les bâtiments et des rues...");
     let reader = ElementReader::from_path(pbf_path)?;
     reader.for_each(|element| {
         if let Element::Way(way) = element {
             let tags: HashMap<_, _> = way.tags().collect();
 
+            if let (Some(highway), Some(name)) = (tags.get("highway"), tags.get("name")) {
+                if ROAD_HIGHWAYS.contains(highway) {
+                    // Un way qui sort de la zone donne plusieurs tronçons (même osm_id)
+                    for coords in split_way_in_bbox(way.refs(), &node_coords) {
+                        streets.push(Street {
+                            osm_id: way.id(),
+                            name: name.to_string(),
+                            highway: highway.to_string(),
+                            coords,
+                        });
+                    }
+                }
+            }
+
             if let Some(street) = tags.get("addr:street") {
                 // Calculer le centroïde du way
                 let refs: Vec<i64> = way.refs().collect();
@@ -200,12 +286,44 @@ fn main() -> Result<(), Box<dyn Error>> {
         ])?;
     }
 
+    let mut stmt = conn.prepare(
+        "INSERT INTO streets (osm_id, name, highway, min_lat, max_lat, min_lon, max_lon, geometry)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+    )?;
+
+    for street in &streets {
+        let lats = street.coords.iter().map(|c| c.0);
+        let lons = street.coords.iter().map(|c| c.1);
+        // WKT comme dans la base de l'application (x = longitude, y = latitude)
+        let wkt = format!(
+            "LINESTRING({})",
+            street
+                .coords
+                .iter()
+                .map(|(lat, lon)| format!("{} {}", lon, lat))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        stmt.execute(rusqlite::params![
+            street.osm_id,
+            street.name,
+            street.highway,
+            lats.clone().fold(f64::MAX, f64::min),
+            lats.fold(f64::MIN, f64::max),
+            lons.clone().fold(f64::MAX, f64::min),
+            lons.fold(f64::MIN, f64::max),
+            wkt
+        ])?;
+    }
+
     // Optimiser la base
     conn.execute("ANALYZE", [])?;
 
     let file_size = fs::metadata(db_path)?.len();
     println!("✅ Extraction terminée!");
     println!("   {} adresses exportées", addresses.len());
+    println!("   {} tronçons de rue exportés", streets.len());
     println!(
         "   Taille de la base: {:.2} MB",
         file_size as f64 / 1_048_576.0